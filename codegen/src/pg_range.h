@@ -35,6 +35,7 @@ CATALOG(pg_range,3541) BKI_WITHOUT_OIDS
 {
 	Oid			rngtypid;		/* OID of owning range type */
 	Oid			rngsubtype;		/* OID of range's element type (subtype) */
+	Oid			rngmultitypid;	/* OID of the range's multirange type */
 	Oid			rngcollation;	/* collation for this range type, or 0 */
 	Oid			rngsubopc;		/* subtype's btree opclass */
 	regproc		rngcanonical;	/* canonicalize range, or 0 */
@@ -52,25 +53,26 @@ typedef FormData_pg_range *Form_pg_range;
  *		compiler constants for pg_range
  * ----------------
  */
-#define Natts_pg_range					6
+#define Natts_pg_range					7
 #define Anum_pg_range_rngtypid			1
 #define Anum_pg_range_rngsubtype		2
-#define Anum_pg_range_rngcollation		3
-#define Anum_pg_range_rngsubopc			4
-#define Anum_pg_range_rngcanonical		5
-#define Anum_pg_range_rngsubdiff		6
+#define Anum_pg_range_rngmultitypid		3
+#define Anum_pg_range_rngcollation		4
+#define Anum_pg_range_rngsubopc			5
+#define Anum_pg_range_rngcanonical		6
+#define Anum_pg_range_rngsubdiff		7
 
 
 /* ----------------
  *		initial contents of pg_range
  * ----------------
  */
-DATA(insert ( 3904 23	0 1978 int4range_canonical int4range_subdiff));
-DATA(insert ( 3906 1700 0 3125 - numrange_subdiff));
-DATA(insert ( 3908 1114 0 3128 - tsrange_subdiff));
-DATA(insert ( 3910 1184 0 3127 - tstzrange_subdiff));
-DATA(insert ( 3912 1082 0 3122 daterange_canonical daterange_subdiff));
-DATA(insert ( 3926 20	0 3124 int8range_canonical int8range_subdiff));
+DATA(insert ( 3904 23	4451 0 1978 int4range_canonical int4range_subdiff));
+DATA(insert ( 3906 1700 4532 0 3125 - numrange_subdiff));
+DATA(insert ( 3908 1114 4533 0 3128 - tsrange_subdiff));
+DATA(insert ( 3910 1184 4534 0 3127 - tstzrange_subdiff));
+DATA(insert ( 3912 1082 4535 0 3122 daterange_canonical daterange_subdiff));
+DATA(insert ( 3926 20	4536 0 3124 int8range_canonical int8range_subdiff));
 
 
 /*
@@ -62,7 +62,8 @@ CATALOG(pg_type,1247) BKI_BOOTSTRAP BKI_ROWTYPE_OID(71) BKI_SCHEMA_MACRO
 	/*
 	 * typtype is 'b' for a base type, 'c' for a composite type (e.g., a
 	 * table's rowtype), 'd' for a domain, 'e' for an enum type, 'p' for a
-	 * pseudo-type, or 'r' for a range type. (Use the TYPTYPE macros below.)
+	 * pseudo-type, 'r' for a range type, or 'm' for a multirange type. (Use
+	 * the TYPTYPE macros below.)
 	 *
 	 * If typtype is 'c', typrelid is the OID of the class' entry in pg_class.
 	 */
@@ -638,6 +639,12 @@ DESCR("Binary JSON");
 #define JSONBOID 3802
 DATA(insert OID = 3807 ( _jsonb			PGNSP PGUID -1 f b A f t \054 0 3802 0 array_in array_out array_recv array_send - - array_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
 
+/* jsonpath */
+DATA(insert OID = 4072 ( jsonpath		PGNSP PGUID -1 f b U f t \054 0 0 4073 jsonpath_in jsonpath_out jsonpath_recv jsonpath_send - - - i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("JSON path");
+#define JSONPATHOID 4072
+DATA(insert OID = 4073 ( _jsonpath		PGNSP PGUID -1 f b A f t \054 0 4072 0 array_in array_out array_recv array_send - - array_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+
 DATA(insert OID = 2970 ( txid_snapshot	PGNSP PGUID -1 f b U f t \054 0 0 2949 txid_snapshot_in txid_snapshot_out txid_snapshot_recv txid_snapshot_send - - - d x f 0 -1 0 0 _null_ _null_ _null_ ));
 DESCR("txid snapshot");
 DATA(insert OID = 2949 ( _txid_snapshot PGNSP PGUID -1 f b A f t \054 0 2970 0 array_in array_out array_recv array_send - - array_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
@@ -663,6 +670,27 @@ DATA(insert OID = 3926 ( int8range		PGNSP PGUID  -1 f r R f t \054 0 0 3927 rang
 DESCR("range of bigints");
 DATA(insert OID = 3927 ( _int8range		PGNSP PGUID  -1 f b A f t \054 0 3926 0 array_in array_out array_recv array_send - - array_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
 
+/* multirange types */
+DATA(insert OID = 4451 ( int4multirange		PGNSP PGUID  -1 f m R f t \054 0 0 6150 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of integers");
+#define INT4MULTIRANGEOID		4451
+DATA(insert OID = 6150 ( _int4multirange	PGNSP PGUID  -1 f b A f t \054 0 4451 0 array_in array_out array_recv array_send - - array_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DATA(insert OID = 4532 ( nummultirange		PGNSP PGUID  -1 f m R f t \054 0 0 6151 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of numerics");
+DATA(insert OID = 6151 ( _nummultirange		PGNSP PGUID  -1 f b A f t \054 0 4532 0 array_in array_out array_recv array_send - - array_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DATA(insert OID = 4533 ( tsmultirange		PGNSP PGUID  -1 f m R f t \054 0 0 6152 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of timestamps without time zone");
+DATA(insert OID = 6152 ( _tsmultirange		PGNSP PGUID  -1 f b A f t \054 0 4533 0 array_in array_out array_recv array_send - - array_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+DATA(insert OID = 4534 ( tstzmultirange		PGNSP PGUID  -1 f m R f t \054 0 0 6153 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of timestamps with time zone");
+DATA(insert OID = 6153 ( _tstzmultirange	PGNSP PGUID  -1 f b A f t \054 0 4534 0 array_in array_out array_recv array_send - - array_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+DATA(insert OID = 4535 ( datemultirange		PGNSP PGUID  -1 f m R f t \054 0 0 6155 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of dates");
+DATA(insert OID = 6155 ( _datemultirange	PGNSP PGUID  -1 f b A f t \054 0 4535 0 array_in array_out array_recv array_send - - array_typanalyze i x f 0 -1 0 0 _null_ _null_ _null_ ));
+DATA(insert OID = 4536 ( int8multirange		PGNSP PGUID  -1 f m R f t \054 0 0 6157 multirange_in multirange_out multirange_recv multirange_send - - multirange_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+DESCR("multirange of bigints");
+DATA(insert OID = 6157 ( _int8multirange	PGNSP PGUID  -1 f b A f t \054 0 4536 0 array_in array_out array_recv array_send - - array_typanalyze d x f 0 -1 0 0 _null_ _null_ _null_ ));
+
 /*
  * pseudo-types
  *
@@ -712,6 +740,8 @@ DATA(insert OID = 3310 ( tsm_handler	PGNSP PGUID  4 t p P f t \054 0 0 0 tsm_han
 #define TSM_HANDLEROID	3310
 DATA(insert OID = 3831 ( anyrange		PGNSP PGUID  -1 f p P f t \054 0 0 0 anyrange_in anyrange_out - - - - - d x f 0 -1 0 0 _null_ _null_ _null_ ));
 #define ANYRANGEOID		3831
+DATA(insert OID = 4537 ( anymultirange	PGNSP PGUID  -1 f p P f t \054 0 0 0 anymultirange_in anymultirange_out - - - - - d x f 0 -1 0 0 _null_ _null_ _null_ ));
+#define ANYMULTIRANGEOID	4537
 
 
 /*
@@ -723,6 +753,7 @@ DATA(insert OID = 3831 ( anyrange		PGNSP PGUID  -1 f p P f t \054 0 0 0 anyrange
 #define  TYPTYPE_ENUM		'e' /* enumerated type */
 #define  TYPTYPE_PSEUDO		'p' /* pseudo-type */
 #define  TYPTYPE_RANGE		'r' /* range type */
+#define  TYPTYPE_MULTIRANGE	'm' /* multirange type */
 
 #define  TYPCATEGORY_INVALID	'\0'	/* not an allowed category */
 #define  TYPCATEGORY_ARRAY		'A'
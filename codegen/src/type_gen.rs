@@ -43,8 +43,11 @@ fn parse_ranges() -> BTreeMap<u32, u32> {
 
         let oid = split[2].parse().unwrap();
         let element = split[3].parse().unwrap();
+        let multirange = split[4].parse().unwrap();
 
         ranges.insert(oid, element);
+        // a multirange's "element" is its range type
+        ranges.insert(multirange, oid);
     }
 
     ranges
@@ -52,7 +55,7 @@ fn parse_ranges() -> BTreeMap<u32, u32> {
 
 fn parse_types(ranges: &BTreeMap<u32, u32>) -> BTreeMap<u32, Type> {
     let doc_re = Regex::new(r#"DESCR\("([^"]+)"\)"#).unwrap();
-    let range_vector_re = Regex::new("(range|vector)$").unwrap();
+    let range_vector_re = Regex::new("(multirange|range|vector)$").unwrap();
     let array_re = Regex::new("^_(.*)").unwrap();
 
     let mut types = BTreeMap::new();
@@ -74,7 +77,8 @@ fn parse_types(ranges: &BTreeMap<u32, u32>) -> BTreeMap<u32, Type> {
         let variant = snake_to_camel(&ident);
         let ident = ident.to_ascii_uppercase();
 
-        let kind = split[11];
+        // multiranges share the range type category, so they're identified by their type instead
+        let kind = if split[10] == "m" { "M" } else { split[11] };
 
         // we need to be able to pull composite fields and enum variants at runtime
         if kind == "C" || kind == "E" {
@@ -225,6 +229,10 @@ fn make_impl(w: &mut BufWriter<File>, types: &BTreeMap<u32, Type>) {
             "P" => "Pseudo".to_owned(),
             "A" => format!("Array(Type(Inner::{}))", types[&type_.element].variant),
             "R" => format!("Range(Type(Inner::{}))", types[&type_.element].variant),
+            "M" => format!(
+                "Multirange(Type(Inner::{}))",
+                types[&type_.element].variant
+            ),
             _ => "Simple".to_owned(),
         };
 
@@ -64,6 +64,7 @@ port = 5433
 ssl = on
 ssl_cert_file = 'server.crt'
 ssl_key_file = 'server.key'
+wal_level = logical
 EOCONF
 
 cat > "$PGDATA/pg_hba.conf" <<-EOCONF
@@ -86,6 +87,9 @@ host    all             postgres        0.0.0.0/0            trust
 host    all             postgres        ::0/0                trust
 # Unix socket connections:
 local   all             postgres                             trust
+# Replication connections:
+host    replication     postgres        0.0.0.0/0            trust
+host    replication     postgres        ::0/0                trust
 EOCONF
 
 psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" <<-EOSQL
@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Path};
+
+use crate::composites::Field;
+use crate::enums::Variant;
+
+pub fn transparent_body(crate_path: &Path, trait_: &Ident, field: &syn::Field) -> TokenStream {
+    let ty = &field.ty;
+
+    quote! {
+        <#ty as #crate_path::types::#trait_>::accepts(type_)
+    }
+}
+
+pub fn json_body(crate_path: &Path, trait_: &Ident) -> TokenStream {
+    quote! {
+        <#crate_path::types::Json<Self> as #crate_path::types::#trait_>::accepts(type_)
+    }
+}
+
+pub fn domain_body(
+    crate_path: &Path,
+    trait_: &Ident,
+    name: &str,
+    field: &syn::Field,
+) -> TokenStream {
+    let ty = &field.ty;
+
+    quote! {
+        if type_.name() != #name {
+            return false;
+        }
+
+        match *type_.kind() {
+            #crate_path::types::Kind::Domain(ref type_) => {
+                <#ty as #crate_path::types::#trait_>::accepts(type_)
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn enum_body(crate_path: &Path, name: &str, variants: &[Variant]) -> TokenStream {
+    let num_variants = variants.len();
+    let variant_names = variants.iter().map(|v| &v.name);
+
+    quote! {
+        if type_.name() != #name {
+            return false;
+        }
+
+        match *type_.kind() {
+            #crate_path::types::Kind::Enum(ref variants) => {
+                if variants.len() != #num_variants {
+                    return false;
+                }
+
+                variants.iter().all(|v| {
+                    match &**v {
+                        #(
+                            #variant_names => true,
+                        )*
+                        _ => false,
+                    }
+                })
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn composite_body(
+    crate_path: &Path,
+    trait_: &Ident,
+    name: &str,
+    fields: &[Field],
+) -> TokenStream {
+    let num_fields = fields.len();
+    let field_names = fields.iter().map(|f| &f.name);
+    let field_types = fields.iter().map(|f| &f.type_);
+
+    quote! {
+        if type_.name() != #name {
+            return false;
+        }
+
+        match *type_.kind() {
+            #crate_path::types::Kind::Composite(ref fields) => {
+                if fields.len() != #num_fields {
+                    return false;
+                }
+
+                fields.iter().all(|f| {
+                    match f.name() {
+                        #(
+                            #field_names => {
+                                <#field_types as #crate_path::types::#trait_>::accepts(f.type_())
+                            }
+                        )*
+                        _ => false,
+                    }
+                })
+            }
+            _ => false,
+        }
+    }
+}
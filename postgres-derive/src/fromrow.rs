@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DataStruct, DeriveInput, Error, Fields, Path};
+
+use crate::composites::Field;
+use crate::overrides::Overrides;
+
+pub fn expand_derive_fromrow(input: DeriveInput) -> Result<TokenStream, Error> {
+    let overrides = Overrides::extract(&input.attrs)?;
+    let crate_path = crate::crate_path(&overrides);
+
+    let fields = match input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => fields
+            .named
+            .iter()
+            .map(Field::parse)
+            .collect::<Result<Vec<_>, _>>()?,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "#[derive(FromRow)] may only be applied to structs with named fields",
+            ));
+        }
+    };
+
+    let field_idents = fields.iter().map(|f| &f.ident);
+    let field_values = fields.iter().map(|f| field_value(&crate_path, f));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let out = quote! {
+        impl #impl_generics #crate_path::row::FromRow for #ident #ty_generics #where_clause {
+            fn from_row(
+                row: &#crate_path::row::Row,
+            ) -> ::std::result::Result<#ident #ty_generics, #crate_path::Error> {
+                ::std::result::Result::Ok(#ident {
+                    #(
+                        #field_idents: #field_values,
+                    )*
+                })
+            }
+        }
+    };
+
+    Ok(out)
+}
+
+fn field_value(crate_path: &Path, field: &Field) -> TokenStream {
+    let ty = &field.type_;
+    let name = &field.name;
+
+    if field.flatten {
+        quote! {
+            <#ty as #crate_path::row::FromRow>::from_row(row)?
+        }
+    } else if field.default {
+        quote! {
+            match #crate_path::row::__from_row_get_opt::<#ty>(row, #name)? {
+                ::std::option::Option::Some(value) => value,
+                ::std::option::Option::None => ::std::default::Default::default(),
+            }
+        }
+    } else {
+        quote! {
+            #crate_path::row::__from_row_get::<#ty>(row, #name)?
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::iter;
+use syn::{
+    Data, DataStruct, DeriveInput, Error, Fields, GenericParam, Generics, Ident, Lifetime,
+    LifetimeDef, Path,
+};
+
+use crate::accepts;
+use crate::composites::Field;
+use crate::enums::Variant;
+use crate::overrides::Overrides;
+
+pub fn expand_derive_fromsql(input: DeriveInput) -> Result<TokenStream, Error> {
+    let overrides = Overrides::extract(&input.attrs)?;
+
+    let name = overrides
+        .name
+        .clone()
+        .unwrap_or_else(|| input.ident.to_string());
+    let crate_path = crate::crate_path(&overrides);
+    let trait_ = Ident::new("FromSql", Span::call_site());
+
+    let (accepts_body, from_sql_body) = match input.data {
+        _ if overrides.json => (
+            accepts::json_body(&crate_path, &trait_),
+            json_body(&crate_path),
+        ),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref fields),
+            ..
+        }) if fields.unnamed.len() == 1 && overrides.transparent => {
+            let field = fields.unnamed.first().unwrap();
+            (
+                accepts::transparent_body(&crate_path, &trait_, field),
+                transparent_body(&crate_path, &input.ident, field),
+            )
+        }
+        Data::Enum(ref data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(Variant::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            (
+                accepts::enum_body(&crate_path, &name, &variants),
+                enum_body(&crate_path, &input.ident, &variants),
+            )
+        }
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref fields),
+            ..
+        }) if fields.unnamed.len() == 1 => {
+            let field = fields.unnamed.first().unwrap();
+            (
+                domain_accepts_body(&crate_path, &trait_, &name, field),
+                domain_body(&crate_path, &input.ident, field),
+            )
+        }
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(Field::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            (
+                accepts::composite_body(&crate_path, &trait_, &name, &fields),
+                composite_body(&crate_path, &input.ident, &fields),
+            )
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "#[derive(FromSql)] may only be applied to structs, single field tuple structs, and enums",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (generics, lifetime) = build_generics(&input.generics);
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let out = quote! {
+        impl #impl_generics #crate_path::types::FromSql<#lifetime> for #ident #ty_generics #where_clause {
+            fn from_sql(
+                _type: &#crate_path::types::Type,
+                buf: &#lifetime [u8],
+            ) -> ::std::result::Result<
+                #ident #ty_generics,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Sync + ::std::marker::Send>,
+            > {
+                #from_sql_body
+            }
+
+            fn accepts(type_: &#crate_path::types::Type) -> bool {
+                #accepts_body
+            }
+        }
+    };
+
+    Ok(out)
+}
+
+fn transparent_body(crate_path: &Path, ident: &Ident, field: &syn::Field) -> TokenStream {
+    let ty = &field.ty;
+
+    quote! {
+        <#ty as #crate_path::types::FromSql>::from_sql(_type, buf).map(#ident)
+    }
+}
+
+fn json_body(crate_path: &Path) -> TokenStream {
+    quote! {
+        <#crate_path::types::Json<Self> as #crate_path::types::FromSql>::from_sql(_type, buf)
+            .map(|json| json.0)
+    }
+}
+
+fn enum_body(crate_path: &Path, ident: &Ident, variants: &[Variant]) -> TokenStream {
+    let variant_names = variants.iter().map(|v| &v.name);
+    let idents = iter::repeat(ident);
+    let variant_idents = variants.iter().map(|v| &v.ident);
+
+    quote! {
+        match #crate_path::types::__text_from_sql(buf)? {
+            #(
+                #variant_names => ::std::result::Result::Ok(#idents::#variant_idents),
+            )*
+            s => {
+                ::std::result::Result::Err(
+                    ::std::convert::Into::into(format!("invalid variant `{}`", s))
+                )
+            }
+        }
+    }
+}
+
+// Postgres reports the base type rather than the domain for values returned by queries, so both are accepted.
+fn domain_accepts_body(
+    crate_path: &Path,
+    trait_: &Ident,
+    name: &str,
+    field: &syn::Field,
+) -> TokenStream {
+    let ty = &field.ty;
+    let main_body = accepts::domain_body(crate_path, trait_, name, field);
+
+    quote! {
+        if <#ty as #crate_path::types::FromSql>::accepts(type_) {
+            return true;
+        }
+
+        #main_body
+    }
+}
+
+fn domain_body(crate_path: &Path, ident: &Ident, field: &syn::Field) -> TokenStream {
+    let ty = &field.ty;
+
+    quote! {
+        let type_ = match *_type.kind() {
+            #crate_path::types::Kind::Domain(ref type_) => type_,
+            _ => _type,
+        };
+
+        <#ty as #crate_path::types::FromSql>::from_sql(type_, buf).map(#ident)
+    }
+}
+
+fn composite_body(crate_path: &Path, ident: &Ident, fields: &[Field]) -> TokenStream {
+    let temp_vars = &fields
+        .iter()
+        .map(|f| Ident::new(&format!("__{}", f.ident), Span::call_site()))
+        .collect::<Vec<_>>();
+    let field_names = &fields.iter().map(|f| &f.name).collect::<Vec<_>>();
+    let field_idents = &fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+
+    quote! {
+        let fields = match *_type.kind() {
+            #crate_path::types::Kind::Composite(ref fields) => fields,
+            _ => unreachable!(),
+        };
+
+        let mut buf = buf;
+        let num_fields = #crate_path::types::__read_be_i32(&mut buf)?;
+        if num_fields as usize != fields.len() {
+            return ::std::result::Result::Err(
+                ::std::convert::Into::into(format!("invalid field count: {} vs {}", num_fields, fields.len()))
+            );
+        }
+
+        #(
+            let mut #temp_vars = ::std::option::Option::None;
+        )*
+
+        for field in fields {
+            let oid = #crate_path::types::__read_be_i32(&mut buf)? as u32;
+            if oid != field.type_().oid() {
+                return ::std::result::Result::Err(
+                    ::std::convert::Into::into("unexpected OID"),
+                );
+            }
+
+            match field.name() {
+                #(
+                    #field_names => {
+                        #temp_vars = ::std::option::Option::Some(
+                            #crate_path::types::__read_value(field.type_(), &mut buf)?
+                        );
+                    }
+                )*
+                _ => unreachable!(),
+            }
+        }
+
+        ::std::result::Result::Ok(#ident {
+            #(
+                #field_idents: #temp_vars.unwrap(),
+            )*
+        })
+    }
+}
+
+// If the type already has a lifetime parameter, values are borrowed for that lifetime. Otherwise, a new one is
+// introduced.
+fn build_generics(source: &Generics) -> (Generics, Lifetime) {
+    if let Some(lifetime) = source.lifetimes().next() {
+        return (source.clone(), lifetime.lifetime.clone());
+    }
+
+    let lifetime = Lifetime::new("'a", Span::call_site());
+    let mut out = source.clone();
+    out.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+    );
+
+    (out, lifetime)
+}
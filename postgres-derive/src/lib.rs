@@ -0,0 +1,62 @@
+//! Custom derives for the `ToSql`, `FromSql`, and `FromRow` traits of `tokio-postgres`.
+//!
+//! This crate should not be used directly. Enable the `derive` Cargo feature of `tokio-postgres` or `postgres`
+//! instead, which re-export the derives from their `types` modules.
+//!
+//! The derives support Postgres enums, domains, and composite types. The name of the Postgres type and of its enum
+//! variants or composite fields default to the Rust identifiers, and can be overridden with
+//! `#[postgres(name = "...")]`. A single-field tuple struct annotated with `#[postgres(transparent)]` is treated as a
+//! wrapper around its field rather than as a domain. A type annotated with `#[postgres(json)]` is instead converted to
+//! and from a `JSON` or `JSONB` value with serde, which requires the `with-serde_json-1` Cargo feature.
+//!
+//! `FromRow` can be derived for structs with named fields, each of which is read from the column of the same name.
+//! Fields support the `#[postgres(name = "...")]`, `#[postgres(default)]`, and `#[postgres(flatten)]` attributes.
+//!
+//! The generated code refers to `::tokio_postgres` by default. Users of the synchronous `postgres` crate should add
+//! `#[postgres(crate = "postgres")]` to the type.
+#![recursion_limit = "256"]
+#![warn(clippy::all, rust_2018_idioms)]
+
+use proc_macro::TokenStream;
+use syn::{parse_quote, Path};
+
+use crate::overrides::Overrides;
+
+mod accepts;
+mod composites;
+mod enums;
+mod fromrow;
+mod fromsql;
+mod overrides;
+mod tosql;
+
+#[proc_macro_derive(ToSql, attributes(postgres))]
+pub fn derive_tosql(input: TokenStream) -> TokenStream {
+    let input = syn::parse(input).unwrap();
+    tosql::expand_derive_tosql(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromSql, attributes(postgres))]
+pub fn derive_fromsql(input: TokenStream) -> TokenStream {
+    let input = syn::parse(input).unwrap();
+    fromsql::expand_derive_fromsql(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromRow, attributes(postgres))]
+pub fn derive_fromrow(input: TokenStream) -> TokenStream {
+    let input = syn::parse(input).unwrap();
+    fromrow::expand_derive_fromrow(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn crate_path(overrides: &Overrides) -> Path {
+    match overrides.crate_path {
+        Some(ref path) => path.clone(),
+        None => parse_quote!(::tokio_postgres),
+    }
+}
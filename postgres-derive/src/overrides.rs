@@ -0,0 +1,74 @@
+use syn::{Attribute, Error, Lit, Meta, NestedMeta, Path};
+
+pub struct Overrides {
+    pub name: Option<String>,
+    pub transparent: bool,
+    pub json: bool,
+    pub flatten: bool,
+    pub default: bool,
+    pub crate_path: Option<Path>,
+}
+
+impl Overrides {
+    pub fn extract(attrs: &[Attribute]) -> Result<Overrides, Error> {
+        let mut overrides = Overrides {
+            name: None,
+            transparent: false,
+            json: false,
+            flatten: false,
+            default: false,
+            crate_path: None,
+        };
+
+        for attr in attrs {
+            // other attributes may not be valid meta items, so only ours are parsed
+            if !attr.path.is_ident("postgres") {
+                continue;
+            }
+
+            let attr = attr.parse_meta()?;
+
+            let list = match attr {
+                Meta::List(ref list) => list,
+                bad => return Err(Error::new_spanned(bad, "expected a #[postgres(...)]")),
+            };
+
+            for item in &list.nested {
+                match item {
+                    NestedMeta::Meta(Meta::NameValue(meta)) => {
+                        let value = match &meta.lit {
+                            Lit::Str(s) => s,
+                            bad => {
+                                return Err(Error::new_spanned(bad, "expected a string literal"));
+                            }
+                        };
+
+                        if meta.path.is_ident("name") {
+                            overrides.name = Some(value.value());
+                        } else if meta.path.is_ident("crate") {
+                            overrides.crate_path = Some(value.parse()?);
+                        } else {
+                            return Err(Error::new_spanned(&meta.path, "unknown override"));
+                        }
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) => {
+                        if path.is_ident("transparent") {
+                            overrides.transparent = true;
+                        } else if path.is_ident("json") {
+                            overrides.json = true;
+                        } else if path.is_ident("flatten") {
+                            overrides.flatten = true;
+                        } else if path.is_ident("default") {
+                            overrides.default = true;
+                        } else {
+                            return Err(Error::new_spanned(path, "unknown override"));
+                        }
+                    }
+                    bad => return Err(Error::new_spanned(bad, "unknown attribute")),
+                }
+            }
+        }
+
+        Ok(overrides)
+    }
+}
@@ -0,0 +1,198 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::iter;
+use syn::{Data, DataStruct, DeriveInput, Error, Fields, Ident, Path};
+
+use crate::accepts;
+use crate::composites::Field;
+use crate::enums::Variant;
+use crate::overrides::Overrides;
+
+pub fn expand_derive_tosql(input: DeriveInput) -> Result<TokenStream, Error> {
+    let overrides = Overrides::extract(&input.attrs)?;
+
+    let name = overrides
+        .name
+        .clone()
+        .unwrap_or_else(|| input.ident.to_string());
+    let crate_path = crate::crate_path(&overrides);
+    let trait_ = Ident::new("ToSql", Span::call_site());
+
+    let (accepts_body, to_sql_body) = match input.data {
+        _ if overrides.json => (
+            accepts::json_body(&crate_path, &trait_),
+            json_body(&crate_path),
+        ),
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref fields),
+            ..
+        }) if fields.unnamed.len() == 1 && overrides.transparent => {
+            let field = fields.unnamed.first().unwrap();
+            (
+                accepts::transparent_body(&crate_path, &trait_, field),
+                transparent_body(&crate_path),
+            )
+        }
+        Data::Enum(ref data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(Variant::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            (
+                accepts::enum_body(&crate_path, &name, &variants),
+                enum_body(&crate_path, &input.ident, &variants),
+            )
+        }
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(ref fields),
+            ..
+        }) if fields.unnamed.len() == 1 => {
+            let field = fields.unnamed.first().unwrap();
+            (
+                accepts::domain_body(&crate_path, &trait_, &name, field),
+                domain_body(&crate_path),
+            )
+        }
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(Field::parse)
+                .collect::<Result<Vec<_>, _>>()?;
+            (
+                accepts::composite_body(&crate_path, &trait_, &name, &fields),
+                composite_body(&crate_path, &fields),
+            )
+        }
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "#[derive(ToSql)] may only be applied to structs, single field tuple structs, and enums",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let out = quote! {
+        impl #impl_generics #crate_path::types::ToSql for #ident #ty_generics #where_clause {
+            fn to_sql(
+                &self,
+                _type: &#crate_path::types::Type,
+                buf: &mut ::std::vec::Vec<u8>,
+            ) -> ::std::result::Result<
+                #crate_path::types::IsNull,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Sync + ::std::marker::Send>,
+            > {
+                #to_sql_body
+            }
+
+            fn accepts(type_: &#crate_path::types::Type) -> bool {
+                #accepts_body
+            }
+
+            fn to_sql_checked(
+                &self,
+                ty: &#crate_path::types::Type,
+                out: &mut ::std::vec::Vec<u8>,
+            ) -> ::std::result::Result<
+                #crate_path::types::IsNull,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Sync + ::std::marker::Send>,
+            > {
+                #crate_path::types::__to_sql_checked(self, ty, out)
+            }
+        }
+    };
+
+    Ok(out)
+}
+
+fn transparent_body(crate_path: &Path) -> TokenStream {
+    quote! {
+        #crate_path::types::ToSql::to_sql(&self.0, _type, buf)
+    }
+}
+
+fn json_body(crate_path: &Path) -> TokenStream {
+    quote! {
+        #crate_path::types::ToSql::to_sql(&#crate_path::types::Json(self), _type, buf)
+    }
+}
+
+fn enum_body(crate_path: &Path, ident: &Ident, variants: &[Variant]) -> TokenStream {
+    let idents = iter::repeat(ident);
+    let variant_idents = variants.iter().map(|v| &v.ident);
+    let variant_names = variants.iter().map(|v| &v.name);
+
+    quote! {
+        let s = match *self {
+            #(
+                #idents::#variant_idents => #variant_names,
+            )*
+        };
+
+        buf.extend_from_slice(s.as_bytes());
+        ::std::result::Result::Ok(#crate_path::types::IsNull::No)
+    }
+}
+
+fn domain_body(crate_path: &Path) -> TokenStream {
+    quote! {
+        let type_ = match *_type.kind() {
+            #crate_path::types::Kind::Domain(ref type_) => type_,
+            _ => unreachable!(),
+        };
+
+        #crate_path::types::ToSql::to_sql(&self.0, type_, buf)
+    }
+}
+
+fn composite_body(crate_path: &Path, fields: &[Field]) -> TokenStream {
+    let field_names = fields.iter().map(|f| &f.name);
+    let field_idents = fields.iter().map(|f| &f.ident);
+
+    quote! {
+        let fields = match *_type.kind() {
+            #crate_path::types::Kind::Composite(ref fields) => fields,
+            _ => unreachable!(),
+        };
+
+        buf.extend_from_slice(&(fields.len() as i32).to_be_bytes());
+
+        for field in fields {
+            buf.extend_from_slice(&field.type_().oid().to_be_bytes());
+
+            let base = buf.len();
+            buf.extend_from_slice(&[0; 4]);
+            let r = match field.name() {
+                #(
+                    #field_names => {
+                        #crate_path::types::ToSql::to_sql(&self.#field_idents, field.type_(), buf)
+                    }
+                )*
+                _ => unreachable!(),
+            };
+
+            let count = match r? {
+                #crate_path::types::IsNull::Yes => -1,
+                #crate_path::types::IsNull::No => {
+                    let len = buf.len() - base - 4;
+                    if len > i32::max_value() as usize {
+                        return ::std::result::Result::Err(
+                            ::std::convert::Into::into("value too large to transmit"),
+                        );
+                    }
+                    len as i32
+                }
+            };
+
+            buf[base..base + 4].copy_from_slice(&count.to_be_bytes());
+        }
+
+        ::std::result::Result::Ok(#crate_path::types::IsNull::No)
+    }
+}
@@ -0,0 +1,62 @@
+use postgres::types::{FromSql, ToSql, WrongType};
+use std::error::Error;
+
+use crate::{connect, test_type};
+
+#[test]
+fn defaults() {
+    #[derive(FromSql, ToSql, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct SessionId(Vec<u8>);
+
+    let mut conn = connect();
+    conn.simple_query(
+        "CREATE DOMAIN pg_temp.\"SessionId\" AS bytea CHECK(octet_length(VALUE) = 16);",
+    )
+    .unwrap();
+
+    test_type(
+        &mut conn,
+        "\"SessionId\"",
+        &[(
+            SessionId(b"0123456789abcdef".to_vec()),
+            "'0123456789abcdef'",
+        )],
+    );
+}
+
+#[test]
+fn name_overrides() {
+    #[derive(FromSql, ToSql, Debug, PartialEq)]
+    #[postgres(crate = "postgres", name = "session_id")]
+    struct SessionId(Vec<u8>);
+
+    let mut conn = connect();
+    conn.simple_query("CREATE DOMAIN pg_temp.session_id AS bytea CHECK(octet_length(VALUE) = 16);")
+        .unwrap();
+
+    test_type(
+        &mut conn,
+        "session_id",
+        &[(
+            SessionId(b"0123456789abcdef".to_vec()),
+            "'0123456789abcdef'",
+        )],
+    );
+}
+
+#[test]
+fn wrong_type() {
+    #[derive(FromSql, ToSql, Debug, PartialEq)]
+    #[postgres(crate = "postgres", name = "session_id")]
+    struct SessionId(i32);
+
+    let mut conn = connect();
+    conn.simple_query("CREATE DOMAIN pg_temp.session_id AS bytea CHECK(octet_length(VALUE) = 16);")
+        .unwrap();
+
+    let err = conn
+        .execute("SELECT $1::session_id", &[&SessionId(0)])
+        .unwrap_err();
+    assert!(err.source().unwrap().is::<WrongType>());
+}
@@ -0,0 +1,172 @@
+use postgres::FromRow;
+
+use crate::connect;
+
+#[test]
+fn defaults() {
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        id: i32,
+        name: String,
+        nickname: Option<String>,
+    }
+
+    let mut conn = connect();
+
+    let people = conn
+        .query_as::<Person, _>(
+            "SELECT * FROM (VALUES (1, 'alice', 'al'), (2, 'bob', NULL)) AS t (id, name, nickname)",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        people,
+        vec![
+            Person {
+                id: 1,
+                name: "alice".to_string(),
+                nickname: Some("al".to_string()),
+            },
+            Person {
+                id: 2,
+                name: "bob".to_string(),
+                nickname: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn name_overrides() {
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        #[postgres(name = "person_id")]
+        id: i32,
+    }
+
+    let mut conn = connect();
+
+    let people = conn
+        .query_as::<Person, _>("SELECT 1 AS person_id", &[])
+        .unwrap();
+    assert_eq!(people, vec![Person { id: 1 }]);
+}
+
+#[test]
+fn foreign_attributes() {
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        // not a valid meta item
+        #[doc = concat!("the person's ", "id")]
+        id: i32,
+    }
+
+    let mut conn = connect();
+
+    let people = conn.query_as::<Person, _>("SELECT 1 AS id", &[]).unwrap();
+    assert_eq!(people, vec![Person { id: 1 }]);
+}
+
+#[test]
+fn default() {
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        id: i32,
+        #[postgres(default)]
+        tags: Vec<String>,
+    }
+
+    let mut conn = connect();
+
+    let people = conn.query_as::<Person, _>("SELECT 1 AS id", &[]).unwrap();
+    assert_eq!(
+        people,
+        vec![Person {
+            id: 1,
+            tags: vec![],
+        }]
+    );
+
+    let people = conn
+        .query_as::<Person, _>("SELECT 1 AS id, ARRAY['a']::TEXT[] AS tags", &[])
+        .unwrap();
+    assert_eq!(
+        people,
+        vec![Person {
+            id: 1,
+            tags: vec!["a".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn flatten() {
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(FromRow, Debug, PartialEq)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        id: i32,
+        #[postgres(flatten)]
+        address: Address,
+    }
+
+    let mut conn = connect();
+
+    let people = conn
+        .query_as::<Person, _>("SELECT 1 AS id, 'Paris' AS city", &[])
+        .unwrap();
+    assert_eq!(
+        people,
+        vec![Person {
+            id: 1,
+            address: Address {
+                city: "Paris".to_string(),
+            },
+        }]
+    );
+}
+
+#[test]
+fn missing_column() {
+    #[derive(FromRow, Debug)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        #[allow(dead_code)]
+        id: i32,
+    }
+
+    let mut conn = connect();
+
+    let err = conn
+        .query_as::<Person, _>("SELECT 1 AS person_id", &[])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid column `id`");
+}
+
+#[test]
+fn wrong_type() {
+    #[derive(FromRow, Debug)]
+    #[postgres(crate = "postgres")]
+    struct Person {
+        #[allow(dead_code)]
+        id: String,
+    }
+
+    let mut conn = connect();
+
+    let err = conn
+        .query_as::<Person, _>("SELECT 1 AS id", &[])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("error deserializing column `id`: "));
+}
@@ -0,0 +1,55 @@
+use postgres::types::{FromSql, ToSql};
+use serde::{Deserialize, Serialize};
+
+use crate::{connect, test_type};
+
+#[test]
+fn json() {
+    #[derive(FromSql, ToSql, Serialize, Deserialize, Debug, PartialEq)]
+    #[postgres(crate = "postgres", json)]
+    struct Settings {
+        theme: String,
+        font_size: u32,
+    }
+
+    let mut conn = connect();
+
+    let settings = || Settings {
+        theme: "dark".to_string(),
+        font_size: 12,
+    };
+    test_type(
+        &mut conn,
+        "JSON",
+        &[(settings(), "'{\"theme\": \"dark\", \"font_size\": 12}'")],
+    );
+    test_type(
+        &mut conn,
+        "JSONB",
+        &[(settings(), "'{\"font_size\": 12, \"theme\": \"dark\"}'")],
+    );
+}
+
+#[test]
+fn json_enum() {
+    #[derive(FromSql, ToSql, Serialize, Deserialize, Debug, PartialEq)]
+    #[postgres(crate = "postgres", json)]
+    enum Shape {
+        Circle { radius: f64 },
+        Square(f64),
+    }
+
+    let mut conn = connect();
+
+    test_type(
+        &mut conn,
+        "JSONB",
+        &[
+            (
+                Shape::Circle { radius: 1.5 },
+                "'{\"Circle\": {\"radius\": 1.5}}'",
+            ),
+            (Shape::Square(2.), "'{\"Square\": 2}'"),
+        ],
+    );
+}
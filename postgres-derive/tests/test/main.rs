@@ -0,0 +1,32 @@
+use postgres::types::{FromSqlOwned, ToSql};
+use postgres::{Client, NoTls};
+use std::fmt;
+
+mod composites;
+mod domains;
+mod enums;
+mod from_row;
+mod json;
+mod transparent;
+
+fn connect() -> Client {
+    Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap()
+}
+
+fn test_type<T, S>(conn: &mut Client, sql_type: &str, checks: &[(T, S)])
+where
+    T: PartialEq + FromSqlOwned + ToSql,
+    S: fmt::Display,
+{
+    for (val, repr) in checks {
+        let stmt = conn
+            .prepare(&format!("SELECT {}::{}", repr, sql_type))
+            .unwrap();
+        let result = conn.query(&stmt, &[]).unwrap()[0].get(0);
+        assert_eq!(val, &result);
+
+        let stmt = conn.prepare(&format!("SELECT $1::{}", sql_type)).unwrap();
+        let result = conn.query(&stmt, &[val]).unwrap()[0].get(0);
+        assert_eq!(val, &result);
+    }
+}
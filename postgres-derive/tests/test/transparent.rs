@@ -0,0 +1,18 @@
+use postgres::types::{FromSql, ToSql};
+
+use crate::{connect, test_type};
+
+#[test]
+fn transparent() {
+    #[derive(FromSql, ToSql, Debug, PartialEq)]
+    #[postgres(crate = "postgres", transparent)]
+    struct UserId(i32);
+
+    let mut conn = connect();
+
+    test_type(
+        &mut conn,
+        "INT",
+        &[(UserId(123), "123"), (UserId(-1), "-1")],
+    );
+}
@@ -49,8 +49,16 @@
 #![warn(rust_2018_idioms, clippy::all, missing_docs)]
 
 use futures::{try_ready, Async, Future, Poll};
+#[cfg(feature = "runtime")]
+use native_tls::{Certificate, Identity};
+#[cfg(feature = "runtime")]
+use std::error::Error;
+#[cfg(feature = "runtime")]
+use std::fs;
 use tokio_io::{AsyncRead, AsyncWrite};
 #[cfg(feature = "runtime")]
+use tokio_postgres::config::{Config, SslMode};
+#[cfg(feature = "runtime")]
 use tokio_postgres::tls::MakeTlsConnect;
 use tokio_postgres::tls::{ChannelBinding, TlsConnect};
 use tokio_tls::{Connect, TlsStream};
@@ -71,6 +79,61 @@ impl MakeTlsConnector {
     pub fn new(connector: native_tls::TlsConnector) -> MakeTlsConnector {
         MakeTlsConnector(connector)
     }
+
+    /// Creates a new connector from the TLS settings of a `Config`.
+    ///
+    /// Only the certificates in `sslrootcert` are trusted, or the system's root certificates if it is not set. The client
+    /// certificate is read from `sslcert` and its PKCS #8 private key from `sslkey`. The server's certificate is
+    /// verified as required by the `sslmode`, and SNI is controlled by `sslsni`.
+    ///
+    /// `native-tls` does not support certificate revocation lists or encrypted private keys, so an error is returned
+    /// if `sslcrl` or `sslpassword` is set.
+    pub fn from_config(config: &Config) -> Result<MakeTlsConnector, Box<dyn Error + Sync + Send>> {
+        let mut builder = native_tls::TlsConnector::builder();
+
+        if let Some(path) = config.get_ssl_root_cert() {
+            builder.disable_built_in_roots(true);
+            // native-tls only parses the first certificate in a PEM file
+            const END: &str = "-----END CERTIFICATE-----";
+            let pem = fs::read_to_string(path)?;
+            let mut rest = &*pem;
+            while let Some(idx) = rest.find(END) {
+                let (cert, tail) = rest.split_at(idx + END.len());
+                builder.add_root_certificate(Certificate::from_pem(cert.as_bytes())?);
+                rest = tail;
+            }
+        }
+        if config.get_ssl_crl().is_some() {
+            return Err("certificate revocation lists are not supported by native-tls".into());
+        }
+        match (config.get_ssl_cert(), config.get_ssl_key()) {
+            (Some(cert), Some(key)) => {
+                if config.get_ssl_password().is_some() {
+                    return Err("encrypted private keys are not supported by native-tls".into());
+                }
+                builder.identity(Identity::from_pkcs8(&fs::read(cert)?, &fs::read(key)?)?);
+            }
+            (None, None) => {}
+            _ => return Err("sslcert and sslkey must be set together".into()),
+        }
+
+        match config.get_ssl_mode() {
+            SslMode::VerifyFull => {}
+            SslMode::VerifyCa => {
+                builder.danger_accept_invalid_hostnames(true);
+            }
+            // as in libpq, `require` verifies the certificate authority if one was provided
+            SslMode::Require if config.get_ssl_root_cert().is_some() => {
+                builder.danger_accept_invalid_hostnames(true);
+            }
+            _ => {
+                builder.danger_accept_invalid_certs(true);
+            }
+        }
+        builder.use_sni(config.get_ssl_sni());
+
+        Ok(MakeTlsConnector::new(builder.build()?))
+    }
 }
 
 #[cfg(feature = "runtime")]
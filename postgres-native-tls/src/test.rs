@@ -103,3 +103,69 @@ fn runtime() {
     let execute = client.simple_query("SELECT 1").for_each(|_| Ok(()));
     runtime.block_on(execute).unwrap();
 }
+
+#[cfg(feature = "runtime")]
+fn config_test(s: &str) -> Result<(), tokio_postgres::Error> {
+    let mut runtime = Runtime::new().unwrap();
+
+    let config = s.parse::<tokio_postgres::Config>().unwrap();
+    let connector = MakeTlsConnector::from_config(&config).unwrap();
+
+    let (mut client, connection) = runtime.block_on(config.connect(connector))?;
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let execute = client.simple_query("SELECT 1").for_each(|_| Ok(()));
+    runtime.block_on(execute)
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_require() {
+    config_test("host=localhost port=5433 user=ssl_user dbname=postgres sslmode=require").unwrap();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_allow() {
+    // ssl_user is rejected without TLS, so the connection is retried with it
+    config_test("host=localhost port=5433 user=ssl_user dbname=postgres sslmode=allow").unwrap();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_verify_ca() {
+    config_test(
+        "host=127.0.0.1 port=5433 user=ssl_user dbname=postgres sslmode=verify-ca \
+         sslrootcert=../test/server.crt",
+    )
+    .unwrap();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_verify_ca_untrusted_root() {
+    // the server's certificate is not signed by the only trusted authority
+    config_test(
+        "host=localhost port=5433 user=ssl_user dbname=postgres sslmode=verify-ca \
+         sslrootcert=../test/other_ca.crt",
+    )
+    .unwrap_err();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_verify_full_wrong_host() {
+    config_test(
+        "host=127.0.0.1 port=5433 user=ssl_user dbname=postgres sslmode=verify-full \
+         sslrootcert=../test/server.crt",
+    )
+    .unwrap_err();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn from_config_crl_unsupported() {
+    let config = "sslcrl=root.crl".parse::<tokio_postgres::Config>().unwrap();
+    assert!(MakeTlsConnector::from_config(&config).is_err());
+}
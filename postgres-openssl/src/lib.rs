@@ -48,14 +48,26 @@ use openssl::error::ErrorStack;
 use openssl::hash::MessageDigest;
 use openssl::nid::Nid;
 #[cfg(feature = "runtime")]
-use openssl::ssl::SslConnector;
+use openssl::pkey::PKey;
 use openssl::ssl::{ConnectConfiguration, HandshakeError, SslRef};
+#[cfg(feature = "runtime")]
+use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode};
+#[cfg(feature = "runtime")]
+use openssl::x509::store::{X509Lookup, X509StoreBuilder};
+#[cfg(feature = "runtime")]
+use openssl::x509::verify::X509VerifyFlags;
+#[cfg(feature = "runtime")]
+use std::error::Error;
 use std::fmt::Debug;
 #[cfg(feature = "runtime")]
+use std::fs;
+#[cfg(feature = "runtime")]
 use std::sync::Arc;
 use tokio_io::{AsyncRead, AsyncWrite};
 use tokio_openssl::{ConnectAsync, ConnectConfigurationExt, SslStream};
 #[cfg(feature = "runtime")]
+use tokio_postgres::config::{Config, SslMode};
+#[cfg(feature = "runtime")]
 use tokio_postgres::tls::MakeTlsConnect;
 use tokio_postgres::tls::{ChannelBinding, TlsConnect};
 
@@ -82,6 +94,75 @@ impl MakeTlsConnector {
         }
     }
 
+    /// Creates a new connector from the TLS settings of a `Config`.
+    ///
+    /// Only the certificate authorities in `sslrootcert` are trusted, or those in the system's default locations if it
+    /// is not set, and revocation lists are read from `sslcrl`. The client certificate and its private key are read from `sslcert` and
+    /// `sslkey`, decrypting the key with `sslpassword` if set. The server's certificate is verified as required by the
+    /// `sslmode`, and SNI is controlled by `sslsni`.
+    pub fn from_config(config: &Config) -> Result<MakeTlsConnector, Box<dyn Error + Sync + Send>> {
+        let mut builder = SslConnector::builder(SslMethod::tls())?;
+
+        // the builder trusts the system's certificate authorities, so a separate store is used to trust only those
+        // in `sslrootcert`
+        let mut root_store = match config.get_ssl_root_cert() {
+            Some(path) => {
+                let mut store = X509StoreBuilder::new()?;
+                store
+                    .add_lookup(X509Lookup::file())?
+                    .load_cert_file(path, SslFiletype::PEM)?;
+                Some(store)
+            }
+            None => None,
+        };
+        if let Some(path) = config.get_ssl_crl() {
+            let store = match &mut root_store {
+                Some(store) => store,
+                None => builder.cert_store_mut(),
+            };
+            store
+                .add_lookup(X509Lookup::file())?
+                .load_crl_file(path, SslFiletype::PEM)?;
+            store.set_flags(X509VerifyFlags::CRL_CHECK | X509VerifyFlags::CRL_CHECK_ALL)?;
+        }
+        if let Some(store) = root_store {
+            builder.set_verify_cert_store(store.build())?;
+        }
+        if let Some(path) = config.get_ssl_cert() {
+            builder.set_certificate_chain_file(path)?;
+        }
+        if let Some(path) = config.get_ssl_key() {
+            match config.get_ssl_password() {
+                Some(password) => {
+                    let key = PKey::private_key_from_pem_passphrase(&fs::read(path)?, password)?;
+                    builder.set_private_key(&key)?;
+                }
+                None => builder.set_private_key_file(path, SslFiletype::PEM)?,
+            }
+            builder.check_private_key()?;
+        }
+
+        let (verify, verify_hostname) = match config.get_ssl_mode() {
+            SslMode::VerifyFull => (true, true),
+            SslMode::VerifyCa => (true, false),
+            // as in libpq, `require` verifies the certificate authority if one was provided
+            SslMode::Require => (config.get_ssl_root_cert().is_some(), false),
+            _ => (false, false),
+        };
+        if !verify {
+            builder.set_verify(SslVerifyMode::NONE);
+        }
+
+        let sni = config.get_ssl_sni();
+        let mut connector = MakeTlsConnector::new(builder.build());
+        connector.set_callback(move |ssl, _| {
+            ssl.set_verify_hostname(verify_hostname);
+            ssl.set_use_server_name_indication(sni);
+            Ok(())
+        });
+        Ok(connector)
+    }
+
     /// Sets a callback used to apply per-connection configuration.
     ///
     /// The the callback is provided the domain name along with the `ConnectConfiguration`.
@@ -22,6 +22,7 @@ pub const DATA_ROW_TAG: u8 = b'D';
 pub const ERROR_RESPONSE_TAG: u8 = b'E';
 pub const COPY_IN_RESPONSE_TAG: u8 = b'G';
 pub const COPY_OUT_RESPONSE_TAG: u8 = b'H';
+pub const COPY_BOTH_RESPONSE_TAG: u8 = b'W';
 pub const EMPTY_QUERY_RESPONSE_TAG: u8 = b'I';
 pub const BACKEND_KEY_DATA_TAG: u8 = b'K';
 pub const NO_DATA_TAG: u8 = b'n';
@@ -92,6 +93,7 @@ pub enum Message {
     CopyDone,
     CopyInResponse(CopyInResponseBody),
     CopyOutResponse(CopyOutResponseBody),
+    CopyBothResponse(CopyBothResponseBody),
     DataRow(DataRowBody),
     EmptyQueryResponse,
     ErrorResponse(ErrorResponseBody),
@@ -191,6 +193,16 @@ impl Message {
                     storage,
                 })
             }
+            COPY_BOTH_RESPONSE_TAG => {
+                let format = buf.read_u8()?;
+                let len = buf.read_u16::<BigEndian>()?;
+                let storage = buf.read_all();
+                Message::CopyBothResponse(CopyBothResponseBody {
+                    format,
+                    len,
+                    storage,
+                })
+            }
             EMPTY_QUERY_RESPONSE_TAG => Message::EmptyQueryResponse,
             BACKEND_KEY_DATA_TAG => {
                 let process_id = buf.read_i32::<BigEndian>()?;
@@ -293,6 +305,12 @@ impl Buffer {
         self.slice().is_empty()
     }
 
+    // Returns the capacity to reserve for `len` elements encoded in at least `size` bytes each. It's bounded by the
+    // remaining data so that a malformed count can't force a large allocation.
+    fn capacity(&self, len: i32, size: usize) -> usize {
+        cmp::min(cmp::max(len, 0) as usize, self.slice().len() / size)
+    }
+
     fn read_cstr(&mut self) -> io::Result<Bytes> {
         match memchr(0, self.slice()) {
             Some(pos) => {
@@ -309,6 +327,19 @@ impl Buffer {
         }
     }
 
+    fn read_bytes(&mut self, len: usize) -> io::Result<Bytes> {
+        if self.slice().len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF",
+            ));
+        }
+
+        let start = self.idx;
+        self.idx += len;
+        Ok(self.bytes.slice(start, self.idx))
+    }
+
     fn read_all(&mut self) -> Bytes {
         let buf = self.bytes.slice_from(self.idx);
         self.idx = self.bytes.len();
@@ -520,6 +551,27 @@ impl CopyOutResponseBody {
     }
 }
 
+pub struct CopyBothResponseBody {
+    storage: Bytes,
+    len: u16,
+    format: u8,
+}
+
+impl CopyBothResponseBody {
+    #[inline]
+    pub fn format(&self) -> u8 {
+        self.format
+    }
+
+    #[inline]
+    pub fn column_formats(&self) -> ColumnFormats<'_> {
+        ColumnFormats {
+            remaining: self.len,
+            buf: &self.storage,
+        }
+    }
+}
+
 pub struct DataRowBody {
     storage: Bytes,
     len: u16,
@@ -875,6 +927,639 @@ fn find_null(buf: &[u8], start: usize) -> io::Result<usize> {
     }
 }
 
+pub const XLOG_DATA_TAG: u8 = b'w';
+pub const PRIMARY_KEEPALIVE_TAG: u8 = b'k';
+
+pub const BEGIN_TAG: u8 = b'B';
+pub const COMMIT_TAG: u8 = b'C';
+pub const ORIGIN_TAG: u8 = b'O';
+pub const RELATION_TAG: u8 = b'R';
+pub const TYPE_TAG: u8 = b'Y';
+pub const INSERT_TAG: u8 = b'I';
+pub const UPDATE_TAG: u8 = b'U';
+pub const DELETE_TAG: u8 = b'D';
+pub const TRUNCATE_TAG: u8 = b'T';
+
+const TUPLE_NEW_TAG: u8 = b'N';
+const TUPLE_KEY_TAG: u8 = b'K';
+const TUPLE_OLD_TAG: u8 = b'O';
+
+const TUPLE_DATA_NULL_TAG: u8 = b'n';
+const TUPLE_DATA_TOAST_TAG: u8 = b'u';
+const TUPLE_DATA_TEXT_TAG: u8 = b't';
+const TUPLE_DATA_BINARY_TAG: u8 = b'b';
+
+/// A message sent by the server in a `CopyData` message during replication.
+pub enum ReplicationMessage<D> {
+    XLogData(XLogDataBody<D>),
+    PrimaryKeepAlive(PrimaryKeepAliveBody),
+    #[doc(hidden)]
+    __ForExtensibility,
+}
+
+impl ReplicationMessage<Bytes> {
+    #[inline]
+    pub fn parse(buf: &Bytes) -> io::Result<ReplicationMessage<Bytes>> {
+        let mut buf = Buffer {
+            bytes: buf.clone(),
+            idx: 0,
+        };
+
+        let message = match buf.read_u8()? {
+            XLOG_DATA_TAG => {
+                let wal_start = buf.read_u64::<BigEndian>()?;
+                let wal_end = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                let data = buf.read_all();
+                ReplicationMessage::XLogData(XLogDataBody {
+                    wal_start,
+                    wal_end,
+                    timestamp,
+                    data,
+                })
+            }
+            PRIMARY_KEEPALIVE_TAG => {
+                let wal_end = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                let reply = buf.read_u8()?;
+                ReplicationMessage::PrimaryKeepAlive(PrimaryKeepAliveBody {
+                    wal_end,
+                    timestamp,
+                    reply,
+                })
+            }
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown replication message tag `{}`", tag),
+                ));
+            }
+        };
+
+        Ok(message)
+    }
+}
+
+pub struct XLogDataBody<D> {
+    wal_start: u64,
+    wal_end: u64,
+    timestamp: i64,
+    data: D,
+}
+
+impl<D> XLogDataBody<D> {
+    #[inline]
+    pub fn wal_start(&self) -> u64 {
+        self.wal_start
+    }
+
+    #[inline]
+    pub fn wal_end(&self) -> u64 {
+        self.wal_end
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn data(&self) -> &D {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_data(self) -> D {
+        self.data
+    }
+
+    pub fn map_data<F, D2, E>(self, f: F) -> Result<XLogDataBody<D2>, E>
+    where
+        F: FnOnce(D) -> Result<D2, E>,
+    {
+        let data = f(self.data)?;
+        Ok(XLogDataBody {
+            wal_start: self.wal_start,
+            wal_end: self.wal_end,
+            timestamp: self.timestamp,
+            data,
+        })
+    }
+}
+
+pub struct PrimaryKeepAliveBody {
+    wal_end: u64,
+    timestamp: i64,
+    reply: u8,
+}
+
+impl PrimaryKeepAliveBody {
+    #[inline]
+    pub fn wal_end(&self) -> u64 {
+        self.wal_end
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn reply(&self) -> u8 {
+        self.reply
+    }
+}
+
+/// A message produced by the `pgoutput` logical decoding plugin.
+pub enum LogicalReplicationMessage {
+    Begin(BeginBody),
+    Commit(CommitBody),
+    Origin(OriginBody),
+    Relation(RelationBody),
+    Type(TypeBody),
+    Insert(InsertBody),
+    Update(UpdateBody),
+    Delete(DeleteBody),
+    Truncate(TruncateBody),
+    #[doc(hidden)]
+    __ForExtensibility,
+}
+
+impl LogicalReplicationMessage {
+    pub fn parse(buf: &Bytes) -> io::Result<LogicalReplicationMessage> {
+        let mut buf = Buffer {
+            bytes: buf.clone(),
+            idx: 0,
+        };
+
+        let message = match buf.read_u8()? {
+            BEGIN_TAG => {
+                let final_lsn = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                let xid = buf.read_u32::<BigEndian>()?;
+                LogicalReplicationMessage::Begin(BeginBody {
+                    final_lsn,
+                    timestamp,
+                    xid,
+                })
+            }
+            COMMIT_TAG => {
+                let flags = buf.read_i8()?;
+                let commit_lsn = buf.read_u64::<BigEndian>()?;
+                let end_lsn = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                LogicalReplicationMessage::Commit(CommitBody {
+                    flags,
+                    commit_lsn,
+                    end_lsn,
+                    timestamp,
+                })
+            }
+            ORIGIN_TAG => {
+                let commit_lsn = buf.read_u64::<BigEndian>()?;
+                let name = buf.read_cstr()?;
+                LogicalReplicationMessage::Origin(OriginBody { commit_lsn, name })
+            }
+            RELATION_TAG => {
+                let rel_id = buf.read_u32::<BigEndian>()?;
+                let namespace = buf.read_cstr()?;
+                let name = buf.read_cstr()?;
+                let replica_identity = match buf.read_u8()? {
+                    b'd' => ReplicaIdentity::Default,
+                    b'n' => ReplicaIdentity::Nothing,
+                    b'f' => ReplicaIdentity::Full,
+                    b'i' => ReplicaIdentity::Index,
+                    tag => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("unknown replica identity tag `{}`", tag),
+                        ));
+                    }
+                };
+                let len = buf.read_i16::<BigEndian>()?;
+
+                let mut columns = Vec::with_capacity(buf.capacity(i32::from(len), 10));
+                for _ in 0..len {
+                    let flags = buf.read_i8()?;
+                    let name = buf.read_cstr()?;
+                    let type_id = buf.read_u32::<BigEndian>()?;
+                    let type_modifier = buf.read_i32::<BigEndian>()?;
+                    columns.push(Column {
+                        flags,
+                        name,
+                        type_id,
+                        type_modifier,
+                    });
+                }
+
+                LogicalReplicationMessage::Relation(RelationBody {
+                    rel_id,
+                    namespace,
+                    name,
+                    replica_identity,
+                    columns,
+                })
+            }
+            TYPE_TAG => {
+                let id = buf.read_u32::<BigEndian>()?;
+                let namespace = buf.read_cstr()?;
+                let name = buf.read_cstr()?;
+                LogicalReplicationMessage::Type(TypeBody {
+                    id,
+                    namespace,
+                    name,
+                })
+            }
+            INSERT_TAG => {
+                let rel_id = buf.read_u32::<BigEndian>()?;
+                match buf.read_u8()? {
+                    TUPLE_NEW_TAG => {}
+                    tag => return Err(unexpected_tuple_tag(tag)),
+                }
+                let tuple = Tuple::parse(&mut buf)?;
+                LogicalReplicationMessage::Insert(InsertBody { rel_id, tuple })
+            }
+            UPDATE_TAG => {
+                let rel_id = buf.read_u32::<BigEndian>()?;
+                let mut old_tuple = None;
+                let mut key_tuple = None;
+                let new_tuple = loop {
+                    match buf.read_u8()? {
+                        TUPLE_KEY_TAG if old_tuple.is_none() && key_tuple.is_none() => {
+                            key_tuple = Some(Tuple::parse(&mut buf)?);
+                        }
+                        TUPLE_OLD_TAG if old_tuple.is_none() && key_tuple.is_none() => {
+                            old_tuple = Some(Tuple::parse(&mut buf)?);
+                        }
+                        TUPLE_NEW_TAG => break Tuple::parse(&mut buf)?,
+                        tag => return Err(unexpected_tuple_tag(tag)),
+                    }
+                };
+                LogicalReplicationMessage::Update(UpdateBody {
+                    rel_id,
+                    old_tuple,
+                    key_tuple,
+                    new_tuple,
+                })
+            }
+            DELETE_TAG => {
+                let rel_id = buf.read_u32::<BigEndian>()?;
+                let mut old_tuple = None;
+                let mut key_tuple = None;
+                match buf.read_u8()? {
+                    TUPLE_KEY_TAG => key_tuple = Some(Tuple::parse(&mut buf)?),
+                    TUPLE_OLD_TAG => old_tuple = Some(Tuple::parse(&mut buf)?),
+                    tag => return Err(unexpected_tuple_tag(tag)),
+                }
+                LogicalReplicationMessage::Delete(DeleteBody {
+                    rel_id,
+                    old_tuple,
+                    key_tuple,
+                })
+            }
+            TRUNCATE_TAG => {
+                let len = buf.read_i32::<BigEndian>()?;
+                let options = buf.read_i8()?;
+
+                let mut rel_ids = Vec::with_capacity(buf.capacity(len, 4));
+                for _ in 0..len {
+                    rel_ids.push(buf.read_u32::<BigEndian>()?);
+                }
+
+                LogicalReplicationMessage::Truncate(TruncateBody { options, rel_ids })
+            }
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown logical replication message tag `{}`", tag),
+                ));
+            }
+        };
+
+        if !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid message length",
+            ));
+        }
+
+        Ok(message)
+    }
+}
+
+fn unexpected_tuple_tag(tag: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("unexpected tuple tag `{}`", tag),
+    )
+}
+
+pub struct BeginBody {
+    final_lsn: u64,
+    timestamp: i64,
+    xid: u32,
+}
+
+impl BeginBody {
+    #[inline]
+    pub fn final_lsn(&self) -> u64 {
+        self.final_lsn
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    #[inline]
+    pub fn xid(&self) -> u32 {
+        self.xid
+    }
+}
+
+pub struct CommitBody {
+    flags: i8,
+    commit_lsn: u64,
+    end_lsn: u64,
+    timestamp: i64,
+}
+
+impl CommitBody {
+    #[inline]
+    pub fn flags(&self) -> i8 {
+        self.flags
+    }
+
+    #[inline]
+    pub fn commit_lsn(&self) -> u64 {
+        self.commit_lsn
+    }
+
+    #[inline]
+    pub fn end_lsn(&self) -> u64 {
+        self.end_lsn
+    }
+
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+pub struct OriginBody {
+    commit_lsn: u64,
+    name: Bytes,
+}
+
+impl OriginBody {
+    #[inline]
+    pub fn commit_lsn(&self) -> u64 {
+        self.commit_lsn
+    }
+
+    #[inline]
+    pub fn name(&self) -> io::Result<&str> {
+        get_str(&self.name)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplicaIdentity {
+    /// The primary key columns, if any.
+    Default,
+    /// No columns.
+    Nothing,
+    /// All columns.
+    Full,
+    /// The columns of a specific index.
+    Index,
+}
+
+pub struct RelationBody {
+    rel_id: u32,
+    namespace: Bytes,
+    name: Bytes,
+    replica_identity: ReplicaIdentity,
+    columns: Vec<Column>,
+}
+
+impl RelationBody {
+    #[inline]
+    pub fn rel_id(&self) -> u32 {
+        self.rel_id
+    }
+
+    #[inline]
+    pub fn namespace(&self) -> io::Result<&str> {
+        get_str(&self.namespace)
+    }
+
+    #[inline]
+    pub fn name(&self) -> io::Result<&str> {
+        get_str(&self.name)
+    }
+
+    #[inline]
+    pub fn replica_identity(&self) -> ReplicaIdentity {
+        self.replica_identity
+    }
+
+    #[inline]
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+}
+
+pub struct Column {
+    flags: i8,
+    name: Bytes,
+    type_id: Oid,
+    type_modifier: i32,
+}
+
+impl Column {
+    /// Returns the column's flags. A value of 1 marks the column as part of the key.
+    #[inline]
+    pub fn flags(&self) -> i8 {
+        self.flags
+    }
+
+    #[inline]
+    pub fn name(&self) -> io::Result<&str> {
+        get_str(&self.name)
+    }
+
+    #[inline]
+    pub fn type_id(&self) -> Oid {
+        self.type_id
+    }
+
+    #[inline]
+    pub fn type_modifier(&self) -> i32 {
+        self.type_modifier
+    }
+}
+
+pub struct TypeBody {
+    id: Oid,
+    namespace: Bytes,
+    name: Bytes,
+}
+
+impl TypeBody {
+    #[inline]
+    pub fn id(&self) -> Oid {
+        self.id
+    }
+
+    #[inline]
+    pub fn namespace(&self) -> io::Result<&str> {
+        get_str(&self.namespace)
+    }
+
+    #[inline]
+    pub fn name(&self) -> io::Result<&str> {
+        get_str(&self.name)
+    }
+}
+
+pub struct InsertBody {
+    rel_id: u32,
+    tuple: Tuple,
+}
+
+impl InsertBody {
+    #[inline]
+    pub fn rel_id(&self) -> u32 {
+        self.rel_id
+    }
+
+    #[inline]
+    pub fn tuple(&self) -> &Tuple {
+        &self.tuple
+    }
+}
+
+pub struct UpdateBody {
+    rel_id: u32,
+    old_tuple: Option<Tuple>,
+    key_tuple: Option<Tuple>,
+    new_tuple: Tuple,
+}
+
+impl UpdateBody {
+    #[inline]
+    pub fn rel_id(&self) -> u32 {
+        self.rel_id
+    }
+
+    /// The entire old row, present if the table's replica identity is `FULL`.
+    #[inline]
+    pub fn old_tuple(&self) -> Option<&Tuple> {
+        self.old_tuple.as_ref()
+    }
+
+    /// The key columns of the old row, present if the key was changed.
+    #[inline]
+    pub fn key_tuple(&self) -> Option<&Tuple> {
+        self.key_tuple.as_ref()
+    }
+
+    #[inline]
+    pub fn new_tuple(&self) -> &Tuple {
+        &self.new_tuple
+    }
+}
+
+pub struct DeleteBody {
+    rel_id: u32,
+    old_tuple: Option<Tuple>,
+    key_tuple: Option<Tuple>,
+}
+
+impl DeleteBody {
+    #[inline]
+    pub fn rel_id(&self) -> u32 {
+        self.rel_id
+    }
+
+    /// The entire old row, present if the table's replica identity is `FULL`.
+    #[inline]
+    pub fn old_tuple(&self) -> Option<&Tuple> {
+        self.old_tuple.as_ref()
+    }
+
+    /// The key columns of the old row, present otherwise.
+    #[inline]
+    pub fn key_tuple(&self) -> Option<&Tuple> {
+        self.key_tuple.as_ref()
+    }
+}
+
+pub struct TruncateBody {
+    options: i8,
+    rel_ids: Vec<u32>,
+}
+
+impl TruncateBody {
+    /// Returns the truncate options. Bit 1 is set for `CASCADE` and bit 2 for `RESTART IDENTITY`.
+    #[inline]
+    pub fn options(&self) -> i8 {
+        self.options
+    }
+
+    #[inline]
+    pub fn rel_ids(&self) -> &[u32] {
+        &self.rel_ids
+    }
+}
+
+pub struct Tuple(Vec<TupleData>);
+
+impl Tuple {
+    fn parse(buf: &mut Buffer) -> io::Result<Tuple> {
+        let len = buf.read_i16::<BigEndian>()?;
+
+        let mut data = Vec::with_capacity(buf.capacity(i32::from(len), 1));
+        for _ in 0..len {
+            let value = match buf.read_u8()? {
+                TUPLE_DATA_NULL_TAG => TupleData::Null,
+                TUPLE_DATA_TOAST_TAG => TupleData::UnchangedToast,
+                TUPLE_DATA_TEXT_TAG => {
+                    let len = buf.read_i32::<BigEndian>()?;
+                    TupleData::Text(buf.read_bytes(cmp::max(len, 0) as usize)?)
+                }
+                TUPLE_DATA_BINARY_TAG => {
+                    let len = buf.read_i32::<BigEndian>()?;
+                    TupleData::Binary(buf.read_bytes(cmp::max(len, 0) as usize)?)
+                }
+                tag => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("unknown tuple data tag `{}`", tag),
+                    ));
+                }
+            };
+            data.push(value);
+        }
+
+        Ok(Tuple(data))
+    }
+
+    #[inline]
+    pub fn tuple_data(&self) -> &[TupleData] {
+        &self.0
+    }
+}
+
+pub enum TupleData {
+    Null,
+    /// An unchanged TOASTed value, whose contents are not sent.
+    UnchangedToast,
+    Text(Bytes),
+    Binary(Bytes),
+}
+
 #[inline]
 fn get_str(buf: &[u8]) -> io::Result<&str> {
     str::from_utf8(buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
@@ -382,6 +382,29 @@ pub fn ssl_request(buf: &mut Vec<u8>) {
     write_body(buf, |buf| buf.write_i32::<BigEndian>(80_877_103)).unwrap();
 }
 
+/// Serializes a standby status update, which is sent to the server as the contents of a `CopyData` message during
+/// replication.
+///
+/// The LSNs are the positions of the last WAL byte + 1 written to disk, flushed to disk, and applied by the standby,
+/// and the timestamp is the number of microseconds since midnight on 2000-01-01. If `reply` is set, the server will
+/// respond with a keepalive message immediately.
+#[inline]
+pub fn standby_status_update(
+    write_lsn: u64,
+    flush_lsn: u64,
+    apply_lsn: u64,
+    timestamp: i64,
+    reply: bool,
+    buf: &mut Vec<u8>,
+) {
+    buf.push(b'r');
+    buf.write_u64::<BigEndian>(write_lsn).unwrap();
+    buf.write_u64::<BigEndian>(flush_lsn).unwrap();
+    buf.write_u64::<BigEndian>(apply_lsn).unwrap();
+    buf.write_i64::<BigEndian>(timestamp).unwrap();
+    buf.push(reply as u8);
+}
+
 #[inline]
 pub fn startup_message<'a, I>(parameters: I, buf: &mut Vec<u8>) -> io::Result<()>
 where
@@ -2,6 +2,7 @@
 use byteorder::{BigEndian, ByteOrder, ReadBytesExt, WriteBytesExt};
 use fallible_iterator::FallibleIterator;
 use std::boxed::Box as StdBox;
+use std::cmp;
 use std::error::Error;
 use std::io::Read;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
@@ -18,9 +19,20 @@ const RANGE_UPPER_INCLUSIVE: u8 = 0b0000_0100;
 const RANGE_LOWER_INCLUSIVE: u8 = 0b0000_0010;
 const RANGE_EMPTY: u8 = 0b0000_0001;
 
+const JSONB_VERSION: u8 = 1;
+const JSONPATH_VERSION: u8 = 1;
+
 const PGSQL_AF_INET: u8 = 2;
 const PGSQL_AF_INET6: u8 = 3;
 
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+const NUMERIC_MAX_DSCALE: u16 = 0x3FFF;
+const NUMERIC_NBASE: i16 = 10000;
+
 /// Serializes a `BOOL` value.
 #[inline]
 pub fn bool_to_sql(v: bool, buf: &mut Vec<u8>) {
@@ -61,6 +73,41 @@ pub fn text_from_sql(buf: &[u8]) -> Result<&str, StdBox<dyn Error + Sync + Send>
     Ok(str::from_utf8(buf)?)
 }
 
+/// Serializes the header of a `JSONB` value.
+///
+/// The header must be followed by the value's JSON text.
+#[inline]
+pub fn jsonb_header_to_sql(buf: &mut Vec<u8>) {
+    buf.push(JSONB_VERSION);
+}
+
+/// Deserializes a `JSONB` value, returning its JSON text.
+#[inline]
+pub fn jsonb_from_sql(buf: &[u8]) -> Result<&[u8], StdBox<dyn Error + Sync + Send>> {
+    match buf.split_first() {
+        Some((&JSONB_VERSION, json)) => Ok(json),
+        Some(_) => Err("unsupported JSONB encoding version".into()),
+        None => Err("invalid buffer size".into()),
+    }
+}
+
+/// Serializes a `JSONPATH` value.
+#[inline]
+pub fn jsonpath_to_sql(v: &str, buf: &mut Vec<u8>) {
+    buf.push(JSONPATH_VERSION);
+    buf.extend_from_slice(v.as_bytes());
+}
+
+/// Deserializes a `JSONPATH` value.
+#[inline]
+pub fn jsonpath_from_sql(buf: &[u8]) -> Result<&str, StdBox<dyn Error + Sync + Send>> {
+    match buf.split_first() {
+        Some((&JSONPATH_VERSION, path)) => Ok(str::from_utf8(path)?),
+        Some(_) => Err("unsupported JSONPATH encoding version".into()),
+        None => Err("invalid buffer size".into()),
+    }
+}
+
 /// Serializes a `"char"` value.
 #[inline]
 pub fn char_to_sql(v: i8, buf: &mut Vec<u8>) {
@@ -386,7 +433,7 @@ pub fn date_from_sql(mut buf: &[u8]) -> Result<i32, StdBox<dyn Error + Sync + Se
     Ok(v)
 }
 
-/// Serializes a `TIME` or `TIMETZ` value.
+/// Serializes a `TIME` value.
 ///
 /// The value should represent the number of microseconds since midnight.
 #[inline]
@@ -394,7 +441,7 @@ pub fn time_to_sql(v: i64, buf: &mut Vec<u8>) {
     buf.write_i64::<BigEndian>(v).unwrap();
 }
 
-/// Deserializes a `TIME` or `TIMETZ` value.
+/// Deserializes a `TIME` value.
 ///
 /// The value represents the number of microseconds since midnight.
 #[inline]
@@ -406,6 +453,124 @@ pub fn time_from_sql(mut buf: &[u8]) -> Result<i64, StdBox<dyn Error + Sync + Se
     Ok(v)
 }
 
+/// Serializes a `TIMETZ` value.
+///
+/// `time` should represent the number of microseconds since midnight, and `offset` the time zone's offset from UTC in
+/// seconds, positive east of Greenwich.
+#[inline]
+pub fn timetz_to_sql(time: i64, offset: i32, buf: &mut Vec<u8>) {
+    buf.write_i64::<BigEndian>(time).unwrap();
+    // Postgres stores the offset as seconds west of Greenwich
+    buf.write_i32::<BigEndian>(-offset).unwrap();
+}
+
+/// Deserializes a `TIMETZ` value.
+#[inline]
+pub fn timetz_from_sql(mut buf: &[u8]) -> Result<TimeTz, StdBox<dyn Error + Sync + Send>> {
+    let time = buf.read_i64::<BigEndian>()?;
+    let zone = buf.read_i32::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length".into());
+    }
+    let offset = zone.checked_neg().ok_or("invalid time zone offset")?;
+    Ok(TimeTz { time, offset })
+}
+
+/// A Postgres time with time zone.
+pub struct TimeTz {
+    time: i64,
+    offset: i32,
+}
+
+impl TimeTz {
+    /// Returns the number of microseconds since midnight.
+    #[inline]
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// Returns the time zone's offset from UTC in seconds, positive east of Greenwich.
+    #[inline]
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+/// Serializes a `MONEY` value.
+///
+/// The value should represent the amount in the currency's smallest unit, for example cents.
+#[inline]
+pub fn money_to_sql(v: i64, buf: &mut Vec<u8>) {
+    buf.write_i64::<BigEndian>(v).unwrap();
+}
+
+/// Deserializes a `MONEY` value.
+///
+/// The value represents the amount in the currency's smallest unit, for example cents.
+#[inline]
+pub fn money_from_sql(mut buf: &[u8]) -> Result<i64, StdBox<dyn Error + Sync + Send>> {
+    let v = buf.read_i64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length".into());
+    }
+    Ok(v)
+}
+
+/// Serializes an `INTERVAL` value.
+///
+/// `microseconds` is the time part of the interval, while `days` and `months` are stored separately since their
+/// lengths vary.
+#[inline]
+pub fn interval_to_sql(microseconds: i64, days: i32, months: i32, buf: &mut Vec<u8>) {
+    buf.write_i64::<BigEndian>(microseconds).unwrap();
+    buf.write_i32::<BigEndian>(days).unwrap();
+    buf.write_i32::<BigEndian>(months).unwrap();
+}
+
+/// Deserializes an `INTERVAL` value.
+#[inline]
+pub fn interval_from_sql(mut buf: &[u8]) -> Result<Interval, StdBox<dyn Error + Sync + Send>> {
+    let microseconds = buf.read_i64::<BigEndian>()?;
+    let days = buf.read_i32::<BigEndian>()?;
+    let months = buf.read_i32::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length".into());
+    }
+    Ok(Interval {
+        microseconds,
+        days,
+        months,
+    })
+}
+
+/// A Postgres interval.
+#[derive(Copy, Clone)]
+pub struct Interval {
+    microseconds: i64,
+    days: i32,
+    months: i32,
+}
+
+impl Interval {
+    /// Returns the time part of the interval in microseconds.
+    #[inline]
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Returns the number of days in the interval.
+    #[inline]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the number of months in the interval.
+    #[inline]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+}
+
 /// Serializes a `MACADDR` value.
 #[inline]
 pub fn macaddr_to_sql(v: [u8; 6], buf: &mut Vec<u8>) {
@@ -440,6 +605,165 @@ pub fn uuid_from_sql(buf: &[u8]) -> Result<[u8; 16], StdBox<dyn Error + Sync + S
     Ok(out)
 }
 
+/// Serializes a `NUMERIC` value.
+///
+/// `digits` are the base-10000 digits of the value's magnitude, most significant first, and `weight` is the power
+/// of 10000 of the first digit. `scale` is the number of decimal digits displayed after the decimal point. `NaN` and
+/// infinite values must have no digits.
+#[inline]
+pub fn numeric_to_sql<I>(
+    sign: NumericSign,
+    weight: i16,
+    scale: u16,
+    digits: I,
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = i16>,
+{
+    if scale > NUMERIC_MAX_DSCALE {
+        return Err("numeric scale out of range".into());
+    }
+
+    let base = buf.len();
+    buf.extend_from_slice(&[0; 2]);
+    buf.write_i16::<BigEndian>(weight).unwrap();
+    let sign = match sign {
+        NumericSign::Positive => NUMERIC_POS,
+        NumericSign::Negative => NUMERIC_NEG,
+        NumericSign::NaN => NUMERIC_NAN,
+        NumericSign::PositiveInfinity => NUMERIC_PINF,
+        NumericSign::NegativeInfinity => NUMERIC_NINF,
+    };
+    buf.write_u16::<BigEndian>(sign).unwrap();
+    buf.write_u16::<BigEndian>(scale).unwrap();
+
+    let mut count = 0;
+    for digit in digits {
+        if !(0..NUMERIC_NBASE).contains(&digit) {
+            return Err("invalid numeric digit".into());
+        }
+        buf.write_i16::<BigEndian>(digit).unwrap();
+        count += 1;
+    }
+
+    if count > 0 && sign != NUMERIC_POS && sign != NUMERIC_NEG {
+        return Err("special numeric values cannot have digits".into());
+    }
+
+    let count = i16::from_usize(count)?;
+    BigEndian::write_i16(&mut buf[base..], count);
+
+    Ok(())
+}
+
+/// Deserializes a `NUMERIC` value.
+#[inline]
+pub fn numeric_from_sql(mut buf: &[u8]) -> Result<Numeric<'_>, StdBox<dyn Error + Sync + Send>> {
+    let count = buf.read_i16::<BigEndian>()?;
+    let weight = buf.read_i16::<BigEndian>()?;
+    let sign = match buf.read_u16::<BigEndian>()? {
+        NUMERIC_POS => NumericSign::Positive,
+        NUMERIC_NEG => NumericSign::Negative,
+        NUMERIC_NAN => NumericSign::NaN,
+        NUMERIC_PINF => NumericSign::PositiveInfinity,
+        NUMERIC_NINF => NumericSign::NegativeInfinity,
+        _ => return Err("invalid numeric sign".into()),
+    };
+    let scale = buf.read_u16::<BigEndian>()?;
+    if scale > NUMERIC_MAX_DSCALE {
+        return Err("invalid numeric scale".into());
+    }
+
+    if count < 0 || buf.len() != count as usize * 2 {
+        return Err("invalid message length".into());
+    }
+
+    Ok(Numeric {
+        sign,
+        weight,
+        scale,
+        buf,
+    })
+}
+
+/// The sign of a Postgres numeric.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumericSign {
+    /// A positive number or zero.
+    Positive,
+    /// A negative number.
+    Negative,
+    /// Not a number.
+    NaN,
+    /// Positive infinity.
+    PositiveInfinity,
+    /// Negative infinity.
+    NegativeInfinity,
+}
+
+/// A Postgres numeric.
+pub struct Numeric<'a> {
+    sign: NumericSign,
+    weight: i16,
+    scale: u16,
+    buf: &'a [u8],
+}
+
+impl<'a> Numeric<'a> {
+    /// Returns the sign of the value.
+    #[inline]
+    pub fn sign(&self) -> NumericSign {
+        self.sign
+    }
+
+    /// Returns the power of 10000 of the first digit.
+    #[inline]
+    pub fn weight(&self) -> i16 {
+        self.weight
+    }
+
+    /// Returns the number of decimal digits displayed after the decimal point.
+    #[inline]
+    pub fn scale(&self) -> u16 {
+        self.scale
+    }
+
+    /// Returns an iterator over the base-10000 digits of the value, most significant first.
+    #[inline]
+    pub fn digits(&self) -> NumericDigits<'a> {
+        NumericDigits(self.buf)
+    }
+}
+
+/// An iterator over the digits of a numeric.
+pub struct NumericDigits<'a>(&'a [u8]);
+
+impl<'a> FallibleIterator for NumericDigits<'a> {
+    type Item = i16;
+    type Error = StdBox<dyn Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<i16>, StdBox<dyn Error + Sync + Send>> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+
+        let digit = self.0.read_i16::<BigEndian>()?;
+        if !(0..NUMERIC_NBASE).contains(&digit) {
+            return Err("invalid numeric digit".into());
+        }
+
+        Ok(Some(digit))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 2;
+        (len, Some(len))
+    }
+}
+
 /// Serializes an array value.
 #[inline]
 pub fn array_to_sql<T, I, J, F>(
@@ -591,7 +915,7 @@ impl<'a> FallibleIterator for ArrayDimensions<'a> {
 }
 
 /// Information about a dimension of an array.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct ArrayDimension {
     /// The length of this dimension.
     pub len: i32,
@@ -637,7 +961,8 @@ impl<'a> FallibleIterator for ArrayValues<'a> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.remaining as usize;
+        // each value has at least a 4 byte length, so the buffer bounds the count of a malformed array
+        let len = cmp::min(self.remaining as usize, self.buf.len() / 4);
         (len, Some(len))
     }
 }
@@ -780,6 +1105,106 @@ pub enum Range<'a> {
     Nonempty(RangeBound<Option<&'a [u8]>>, RangeBound<Option<&'a [u8]>>),
 }
 
+/// Serializes a multirange value.
+///
+/// `serializer` should write each range with `range_to_sql` or `empty_range_to_sql`.
+#[inline]
+pub fn multirange_to_sql<T, I, F>(
+    ranges: I,
+    mut serializer: F,
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = T>,
+    F: FnMut(T, &mut Vec<u8>) -> Result<(), StdBox<dyn Error + Sync + Send>>,
+{
+    let count_idx = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+
+    let mut count = 0;
+    for range in ranges {
+        let base = buf.len();
+        buf.extend_from_slice(&[0; 4]);
+        serializer(range, buf)?;
+        let len = i32::from_usize(buf.len() - base - 4)?;
+        BigEndian::write_i32(&mut buf[base..], len);
+        count += 1;
+    }
+
+    let count = i32::from_usize(count)?;
+    BigEndian::write_i32(&mut buf[count_idx..], count);
+
+    Ok(())
+}
+
+/// Deserializes a multirange value.
+#[inline]
+pub fn multirange_from_sql<'a>(
+    mut buf: &'a [u8],
+) -> Result<Multirange<'a>, StdBox<dyn Error + Sync + Send>> {
+    let count = buf.read_i32::<BigEndian>()?;
+    if count < 0 {
+        return Err("invalid range count".into());
+    }
+
+    Ok(Multirange { count, buf })
+}
+
+/// A Postgres multirange.
+pub struct Multirange<'a> {
+    count: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> Multirange<'a> {
+    /// Returns an iterator over the ranges of the multirange.
+    #[inline]
+    pub fn ranges(&self) -> MultirangeRanges<'a> {
+        MultirangeRanges {
+            remaining: self.count,
+            buf: self.buf,
+        }
+    }
+}
+
+/// An iterator over the ranges of a multirange.
+pub struct MultirangeRanges<'a> {
+    remaining: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> FallibleIterator for MultirangeRanges<'a> {
+    type Item = Range<'a>;
+    type Error = StdBox<dyn Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<Range<'a>>, StdBox<dyn Error + Sync + Send>> {
+        if self.remaining == 0 {
+            if !self.buf.is_empty() {
+                return Err("invalid message length".into());
+            }
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        let len = self.buf.read_i32::<BigEndian>()?;
+        if len < 0 || self.buf.len() < len as usize {
+            return Err("invalid range length".into());
+        }
+        let (range, buf) = self.buf.split_at(len as usize);
+        self.buf = buf;
+
+        range_from_sql(range).map(Some)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // each range has a 4 byte length, so the buffer bounds the count of a malformed multirange
+        let len = cmp::min(self.remaining as usize, self.buf.len() / 4);
+        (len, Some(len))
+    }
+}
+
 /// Serializes a point value.
 #[inline]
 pub fn point_to_sql(x: f64, y: f64, buf: &mut Vec<u8>) {
@@ -957,11 +1382,207 @@ impl<'a> FallibleIterator for PathPoints<'a> {
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.remaining as usize;
+        // each point is 16 bytes, so the buffer bounds the count of a malformed path or polygon
+        let len = cmp::min(self.remaining.max(0) as usize, self.buf.len() / 16);
         (len, Some(len))
     }
 }
 
+/// Serializes a Postgres line.
+///
+/// The line is represented by the coefficients of its equation, `ax + by + c = 0`.
+#[inline]
+pub fn line_to_sql(a: f64, b: f64, c: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(a).unwrap();
+    buf.write_f64::<BigEndian>(b).unwrap();
+    buf.write_f64::<BigEndian>(c).unwrap();
+}
+
+/// Deserializes a Postgres line.
+#[inline]
+pub fn line_from_sql(mut buf: &[u8]) -> Result<Line, StdBox<dyn Error + Sync + Send>> {
+    let a = buf.read_f64::<BigEndian>()?;
+    let b = buf.read_f64::<BigEndian>()?;
+    let c = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Line { a, b, c })
+}
+
+/// A Postgres line.
+#[derive(Copy, Clone)]
+pub struct Line {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Line {
+    /// Returns the `a` coefficient of the line's equation.
+    #[inline]
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    /// Returns the `b` coefficient of the line's equation.
+    #[inline]
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Returns the `c` coefficient of the line's equation.
+    #[inline]
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+}
+
+/// Serializes a Postgres line segment.
+#[inline]
+pub fn lseg_to_sql(x1: f64, y1: f64, x2: f64, y2: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(x1).unwrap();
+    buf.write_f64::<BigEndian>(y1).unwrap();
+    buf.write_f64::<BigEndian>(x2).unwrap();
+    buf.write_f64::<BigEndian>(y2).unwrap();
+}
+
+/// Deserializes a Postgres line segment.
+#[inline]
+pub fn lseg_from_sql(mut buf: &[u8]) -> Result<LineSegment, StdBox<dyn Error + Sync + Send>> {
+    let x1 = buf.read_f64::<BigEndian>()?;
+    let y1 = buf.read_f64::<BigEndian>()?;
+    let x2 = buf.read_f64::<BigEndian>()?;
+    let y2 = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(LineSegment {
+        start: Point { x: x1, y: y1 },
+        end: Point { x: x2, y: y2 },
+    })
+}
+
+/// A Postgres line segment.
+#[derive(Copy, Clone)]
+pub struct LineSegment {
+    start: Point,
+    end: Point,
+}
+
+impl LineSegment {
+    /// Returns the start point of the line segment.
+    #[inline]
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Returns the end point of the line segment.
+    #[inline]
+    pub fn end(&self) -> Point {
+        self.end
+    }
+}
+
+/// Serializes a Postgres polygon.
+#[inline]
+pub fn polygon_to_sql<I>(
+    points: I,
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<dyn Error + Sync + Send>>
+where
+    I: IntoIterator<Item = (f64, f64)>,
+{
+    let points_idx = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+
+    let mut num_points = 0;
+    for (x, y) in points {
+        num_points += 1;
+        buf.write_f64::<BigEndian>(x).unwrap();
+        buf.write_f64::<BigEndian>(y).unwrap();
+    }
+
+    let num_points = i32::from_usize(num_points)?;
+    BigEndian::write_i32(&mut buf[points_idx..], num_points);
+
+    Ok(())
+}
+
+/// Deserializes a Postgres polygon.
+#[inline]
+pub fn polygon_from_sql<'a>(
+    mut buf: &'a [u8],
+) -> Result<Polygon<'a>, StdBox<dyn Error + Sync + Send>> {
+    let points = buf.read_i32::<BigEndian>()?;
+    if points < 0 {
+        return Err("invalid point count".into());
+    }
+
+    Ok(Polygon { points, buf })
+}
+
+/// A Postgres polygon.
+pub struct Polygon<'a> {
+    points: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> Polygon<'a> {
+    /// Returns an iterator over the vertices of the polygon.
+    #[inline]
+    pub fn points(&self) -> PathPoints<'a> {
+        PathPoints {
+            remaining: self.points,
+            buf: self.buf,
+        }
+    }
+}
+
+/// Serializes a Postgres circle.
+#[inline]
+pub fn circle_to_sql(x: f64, y: f64, radius: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(x).unwrap();
+    buf.write_f64::<BigEndian>(y).unwrap();
+    buf.write_f64::<BigEndian>(radius).unwrap();
+}
+
+/// Deserializes a Postgres circle.
+#[inline]
+pub fn circle_from_sql(mut buf: &[u8]) -> Result<Circle, StdBox<dyn Error + Sync + Send>> {
+    let x = buf.read_f64::<BigEndian>()?;
+    let y = buf.read_f64::<BigEndian>()?;
+    let radius = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Circle {
+        center: Point { x, y },
+        radius,
+    })
+}
+
+/// A Postgres circle.
+#[derive(Copy, Clone)]
+pub struct Circle {
+    center: Point,
+    radius: f64,
+}
+
+impl Circle {
+    /// Returns the center of the circle.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the radius of the circle.
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
 /// Serializes a Postgres inet.
 #[inline]
 pub fn inet_to_sql(addr: IpAddr, netmask: u8, buf: &mut Vec<u8>) {
@@ -984,6 +1605,46 @@ pub fn inet_to_sql(addr: IpAddr, netmask: u8, buf: &mut Vec<u8>) {
     }
 }
 
+/// Serializes a Postgres cidr.
+///
+/// Unlike an inet, a cidr cannot have any bits of its address set to the right of the netmask.
+#[inline]
+pub fn cidr_to_sql(
+    addr: IpAddr,
+    netmask: u8,
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<dyn Error + Sync + Send>> {
+    let (family, octets) = match addr {
+        IpAddr::V4(addr) => {
+            if netmask > 32 {
+                return Err("invalid IPv4 netmask".into());
+            }
+            (PGSQL_AF_INET, addr.octets().to_vec())
+        }
+        IpAddr::V6(addr) => {
+            if netmask > 128 {
+                return Err("invalid IPv6 netmask".into());
+            }
+            (PGSQL_AF_INET6, addr.octets().to_vec())
+        }
+    };
+
+    for (i, &octet) in octets.iter().enumerate() {
+        let bits = (netmask as usize).saturating_sub(i * 8).min(8);
+        let host_mask = (0xffu16 >> bits) as u8;
+        if octet & host_mask != 0 {
+            return Err("cidr has bits set to the right of the netmask".into());
+        }
+    }
+
+    buf.push(family);
+    buf.push(netmask);
+    buf.push(1); // is_cidr
+    buf.push(octets.len() as u8);
+    buf.extend_from_slice(&octets);
+    Ok(())
+}
+
 /// Deserializes a Postgres inet.
 #[inline]
 pub fn inet_from_sql(mut buf: &[u8]) -> Result<Inet, StdBox<dyn Error + Sync + Send>> {
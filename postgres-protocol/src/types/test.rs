@@ -52,6 +52,18 @@ fn float8() {
     assert_eq!(float8_from_sql(&buf).unwrap(), 10343.95);
 }
 
+#[test]
+fn interval() {
+    let mut buf = vec![];
+    interval_to_sql(-3_600_000_000, 14, -25, &mut buf);
+    let interval = interval_from_sql(&buf).unwrap();
+    assert_eq!(interval.microseconds(), -3_600_000_000);
+    assert_eq!(interval.days(), 14);
+    assert_eq!(interval.months(), -25);
+
+    assert!(interval_from_sql(&buf[..15]).is_err());
+}
+
 #[test]
 fn hstore() {
     let mut map = HashMap::new();
@@ -154,3 +166,219 @@ fn non_null_array() {
     assert_eq!(array.dimensions().collect::<Vec<_>>().unwrap(), dimensions);
     assert_eq!(array.values().collect::<Vec<_>>().unwrap(), values);
 }
+
+#[test]
+fn truncated_array() {
+    let mut buf = vec![];
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&0i32.to_be_bytes());
+    buf.extend_from_slice(&25u32.to_be_bytes());
+    buf.extend_from_slice(&i32::MAX.to_be_bytes());
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&(-1i32).to_be_bytes());
+
+    let array = array_from_sql(&buf).unwrap();
+    assert_eq!(array.values().size_hint(), (1, Some(1)));
+    assert!(array.values().collect::<Vec<_>>().is_err());
+}
+
+#[test]
+fn numeric() {
+    // 12345.6789 at scale 5
+    let mut buf = vec![];
+    numeric_to_sql(NumericSign::Negative, 1, 5, vec![1, 2345, 6789], &mut buf).unwrap();
+    assert_eq!(
+        buf,
+        [0, 3, 0, 1, 0x40, 0, 0, 5, 0, 1, 0x09, 0x29, 0x1a, 0x85]
+    );
+
+    let numeric = numeric_from_sql(&buf).unwrap();
+    assert_eq!(numeric.sign(), NumericSign::Negative);
+    assert_eq!(numeric.weight(), 1);
+    assert_eq!(numeric.scale(), 5);
+    assert_eq!(
+        numeric.digits().collect::<Vec<_>>().unwrap(),
+        [1, 2345, 6789]
+    );
+}
+
+#[test]
+fn numeric_special() {
+    for &sign in &[
+        NumericSign::NaN,
+        NumericSign::PositiveInfinity,
+        NumericSign::NegativeInfinity,
+    ] {
+        let mut buf = vec![];
+        numeric_to_sql(sign, 0, 0, vec![], &mut buf).unwrap();
+        let numeric = numeric_from_sql(&buf).unwrap();
+        assert_eq!(numeric.sign(), sign);
+        assert_eq!(numeric.digits().count().unwrap(), 0);
+    }
+
+    let mut buf = vec![];
+    numeric_to_sql(NumericSign::NaN, 0, 0, vec![1], &mut buf).unwrap_err();
+}
+
+#[test]
+fn numeric_invalid() {
+    let mut buf = vec![];
+    numeric_to_sql(NumericSign::Positive, 0, 0, vec![10000], &mut buf).unwrap_err();
+
+    let buf = [0, 1, 0, 0, 0, 0, 0, 0, 0x27, 0x10];
+    let numeric = numeric_from_sql(&buf).unwrap();
+    numeric.digits().collect::<Vec<_>>().unwrap_err();
+
+    assert!(numeric_from_sql(&[0, 2, 0, 0, 0, 0, 0, 0, 0, 1]).is_err());
+    assert!(numeric_from_sql(&[0, 0, 0, 0, 0x80, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn multirange() {
+    let ranges = [Some((1, 5)), None, Some((10, 20))];
+
+    let mut buf = vec![];
+    multirange_to_sql(
+        ranges.iter(),
+        |range, buf| match *range {
+            Some((lower, upper)) => range_to_sql(
+                |buf| {
+                    int4_to_sql(lower, buf);
+                    Ok(RangeBound::Inclusive(IsNull::No))
+                },
+                |buf| {
+                    int4_to_sql(upper, buf);
+                    Ok(RangeBound::Exclusive(IsNull::No))
+                },
+                buf,
+            ),
+            None => {
+                empty_range_to_sql(buf);
+                Ok(())
+            }
+        },
+        &mut buf,
+    )
+    .unwrap();
+
+    let multirange = multirange_from_sql(&buf).unwrap();
+    let out = multirange
+        .ranges()
+        .map(|range| match range {
+            Range::Empty => Ok(None),
+            Range::Nonempty(
+                RangeBound::Inclusive(Some(lower)),
+                RangeBound::Exclusive(Some(upper)),
+            ) => Ok(Some((int4_from_sql(lower)?, int4_from_sql(upper)?))),
+            _ => panic!("unexpected range"),
+        })
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(out, ranges);
+
+    assert!(multirange_from_sql(&buf[..buf.len() - 1])
+        .unwrap()
+        .ranges()
+        .count()
+        .is_err());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn lseg() {
+    let mut buf = vec![];
+    lseg_to_sql(1., 2., -3., 4.5, &mut buf);
+    let lseg = lseg_from_sql(&buf).unwrap();
+    assert_eq!((lseg.start().x(), lseg.start().y()), (1., 2.));
+    assert_eq!((lseg.end().x(), lseg.end().y()), (-3., 4.5));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn polygon() {
+    let points = [(0., 0.), (0., 1.), (1., 0.)];
+
+    let mut buf = vec![];
+    polygon_to_sql(points.iter().cloned(), &mut buf).unwrap();
+    let polygon = polygon_from_sql(&buf).unwrap();
+    let out = polygon
+        .points()
+        .map(|p| Ok((p.x(), p.y())))
+        .collect::<Vec<_>>()
+        .unwrap();
+    assert_eq!(out, points);
+
+    assert!(polygon_from_sql(&(-1i32).to_be_bytes()).is_err());
+
+    let buf = i32::MAX.to_be_bytes();
+    let polygon = polygon_from_sql(&buf).unwrap();
+    assert_eq!(polygon.points().size_hint(), (0, Some(0)));
+    assert!(polygon.points().collect::<Vec<_>>().is_err());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn circle() {
+    let mut buf = vec![];
+    circle_to_sql(1., -2., 3.5, &mut buf);
+    let circle = circle_from_sql(&buf).unwrap();
+    assert_eq!(circle.center().x(), 1.);
+    assert_eq!(circle.center().y(), -2.);
+    assert_eq!(circle.radius(), 3.5);
+
+    assert!(circle_from_sql(&buf[..16]).is_err());
+}
+
+#[test]
+fn cidr() {
+    let addr = "10.1.0.0".parse::<IpAddr>().unwrap();
+    let mut buf = vec![];
+    cidr_to_sql(addr, 16, &mut buf).unwrap();
+    let inet = inet_from_sql(&buf).unwrap();
+    assert_eq!(inet.addr(), addr);
+    assert_eq!(inet.netmask(), 16);
+
+    assert!(cidr_to_sql(addr, 15, &mut vec![]).is_err());
+    assert!(cidr_to_sql(addr, 33, &mut vec![]).is_err());
+
+    let addr = "2001:db8::".parse::<IpAddr>().unwrap();
+    cidr_to_sql(addr, 32, &mut vec![]).unwrap();
+    assert!(cidr_to_sql(addr, 20, &mut vec![]).is_err());
+}
+
+#[test]
+fn timetz() {
+    let mut buf = vec![];
+    timetz_to_sql(45_296_000_001, -18_000, &mut buf);
+    assert_eq!(&buf[8..], &18_000i32.to_be_bytes());
+    let timetz = timetz_from_sql(&buf).unwrap();
+    assert_eq!(timetz.time(), 45_296_000_001);
+    assert_eq!(timetz.offset(), -18_000);
+}
+
+#[test]
+fn money() {
+    let mut buf = vec![];
+    money_to_sql(-123_456, &mut buf);
+    assert_eq!(money_from_sql(&buf).unwrap(), -123_456);
+}
+
+#[test]
+fn jsonb() {
+    let mut buf = vec![];
+    jsonb_header_to_sql(&mut buf);
+    buf.extend_from_slice(b"{\"a\": 1}");
+    assert_eq!(jsonb_from_sql(&buf).unwrap(), b"{\"a\": 1}");
+
+    assert!(jsonb_from_sql(b"\x02{}").is_err());
+    assert!(jsonb_from_sql(b"").is_err());
+}
+
+#[test]
+fn jsonpath() {
+    let mut buf = vec![];
+    jsonpath_to_sql("$.a[*] ? (@ > 1)", &mut buf);
+    assert_eq!(jsonpath_from_sql(&buf).unwrap(), "$.a[*] ? (@ > 1)");
+
+    assert!(jsonpath_from_sql(b"\x02$").is_err());
+}
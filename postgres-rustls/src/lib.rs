@@ -0,0 +1,248 @@
+//! TLS support for `tokio-postgres` and `postgres` via `rustls`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use postgres_rustls::MakeTlsConnector;
+//! use rustls::ClientConfig;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! # fn main() -> Result<(), Box<std::error::Error>> {
+//! let mut config = ClientConfig::new();
+//! let mut cert = BufReader::new(File::open("database_cert.pem")?);
+//! config.root_store.add_pem_file(&mut cert).map_err(|_| "invalid certificate")?;
+//! let connector = MakeTlsConnector::new(config);
+//!
+//! let connect_future = tokio_postgres::connect(
+//!     "host=localhost user=postgres sslmode=require",
+//!     connector,
+//! );
+//!
+//! // ...
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```no_run
+//! use postgres_rustls::MakeTlsConnector;
+//! use rustls::internal::pemfile;
+//! use rustls::ClientConfig;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! # fn main() -> Result<(), Box<std::error::Error>> {
+//! let mut config = ClientConfig::new();
+//! let mut cert = BufReader::new(File::open("database_cert.pem")?);
+//! config.root_store.add_pem_file(&mut cert).map_err(|_| "invalid certificate")?;
+//!
+//! let mut client_cert = BufReader::new(File::open("client_cert.pem")?);
+//! let client_cert = pemfile::certs(&mut client_cert).map_err(|_| "invalid certificate")?;
+//! let mut client_key = BufReader::new(File::open("client_key.pem")?);
+//! let mut client_keys = pemfile::pkcs8_private_keys(&mut client_key).map_err(|_| "invalid key")?;
+//! config.set_single_client_cert(client_cert, client_keys.remove(0));
+//! let connector = MakeTlsConnector::new(config);
+//!
+//! let mut client = postgres::Client::connect(
+//!     "host=localhost user=postgres sslmode=require",
+//!     connector,
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+#![doc(html_root_url = "https://docs.rs/postgres-rustls/0.1.0")]
+#![warn(rust_2018_idioms, clippy::all, missing_docs)]
+
+use futures::{try_ready, Async, Future, Poll};
+use rustls::{ClientConfig, Session};
+use sha2::digest::Digest;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use std::io;
+use std::sync::Arc;
+use tokio_io::{AsyncRead, AsyncWrite};
+#[cfg(feature = "runtime")]
+use tokio_postgres::tls::MakeTlsConnect;
+use tokio_postgres::tls::{ChannelBinding, TlsConnect};
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::Connect;
+use webpki::DNSNameRef;
+
+#[cfg(test)]
+mod test;
+
+/// A `MakeTlsConnect` implementation using the `rustls` crate.
+///
+/// Requires the `runtime` Cargo feature (enabled by default).
+#[cfg(feature = "runtime")]
+#[derive(Clone)]
+pub struct MakeTlsConnector(Arc<ClientConfig>);
+
+#[cfg(feature = "runtime")]
+impl MakeTlsConnector {
+    /// Creates a new connector.
+    ///
+    /// The trusted root certificates, client certificate, and use of SNI are taken from the `ClientConfig`.
+    pub fn new(config: ClientConfig) -> MakeTlsConnector {
+        MakeTlsConnector(Arc::new(config))
+    }
+}
+
+#[cfg(feature = "runtime")]
+impl<S> MakeTlsConnect<S> for MakeTlsConnector
+where
+    S: AsyncRead + AsyncWrite,
+{
+    type Stream = TlsStream<S>;
+    type TlsConnect = TlsConnector;
+    type Error = io::Error;
+
+    fn make_tls_connect(&mut self, domain: &str) -> Result<TlsConnector, io::Error> {
+        Ok(TlsConnector::new(self.0.clone(), domain))
+    }
+}
+
+/// A `TlsConnect` implementation using the `rustls` crate.
+pub struct TlsConnector {
+    connector: tokio_rustls::TlsConnector,
+    domain: String,
+}
+
+impl TlsConnector {
+    /// Creates a new connector configured to connect to the specified domain.
+    ///
+    /// `rustls` verifies server certificates against DNS names only, so the handshake fails if the domain is not a
+    /// valid DNS name, such as an IP address.
+    pub fn new(config: Arc<ClientConfig>, domain: &str) -> TlsConnector {
+        TlsConnector {
+            connector: tokio_rustls::TlsConnector::from(config),
+            domain: domain.to_string(),
+        }
+    }
+}
+
+impl<S> TlsConnect<S> for TlsConnector
+where
+    S: AsyncRead + AsyncWrite,
+{
+    type Stream = TlsStream<S>;
+    type Error = io::Error;
+    type Future = TlsConnectFuture<S>;
+
+    fn connect(self, stream: S) -> TlsConnectFuture<S> {
+        let state = match DNSNameRef::try_from_ascii_str(&self.domain) {
+            Ok(domain) => State::Connecting(self.connector.connect(domain, stream)),
+            Err(e) => State::Failed(Some(io::Error::new(io::ErrorKind::InvalidInput, e))),
+        };
+        TlsConnectFuture(state)
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+enum State<S> {
+    Connecting(Connect<S>),
+    Failed(Option<io::Error>),
+}
+
+/// The future returned by `TlsConnector`.
+pub struct TlsConnectFuture<S>(State<S>);
+
+impl<S> Future for TlsConnectFuture<S>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    type Item = (TlsStream<S>, ChannelBinding);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<(TlsStream<S>, ChannelBinding), io::Error> {
+        let stream = match &mut self.0 {
+            State::Connecting(future) => try_ready!(future.poll()),
+            State::Failed(e) => return Err(e.take().expect("future polled after completion")),
+        };
+
+        let channel_binding = match tls_server_end_point(stream.get_ref().1) {
+            Some(buf) => ChannelBinding::tls_server_end_point(buf),
+            None => ChannelBinding::none(),
+        };
+
+        Ok(Async::Ready((stream, channel_binding)))
+    }
+}
+
+fn tls_server_end_point(session: &dyn Session) -> Option<Vec<u8>> {
+    let certs = session.get_peer_certificates()?;
+    let cert = &certs.first()?.0;
+
+    // RFC 5929 hashes the certificate with the digest of its signature algorithm, using SHA-256 in place of MD5 and
+    // SHA-1.
+    let digest = match signature_algorithm(cert)? {
+        MD5_WITH_RSA | SHA1_WITH_RSA | ECDSA_WITH_SHA1 | DSA_WITH_SHA1 => {
+            Sha256::digest(cert).to_vec()
+        }
+        SHA224_WITH_RSA | ECDSA_WITH_SHA224 | DSA_WITH_SHA224 => Sha224::digest(cert).to_vec(),
+        SHA256_WITH_RSA | ECDSA_WITH_SHA256 | DSA_WITH_SHA256 => Sha256::digest(cert).to_vec(),
+        SHA384_WITH_RSA | ECDSA_WITH_SHA384 => Sha384::digest(cert).to_vec(),
+        SHA512_WITH_RSA | ECDSA_WITH_SHA512 => Sha512::digest(cert).to_vec(),
+        _ => return None,
+    };
+    Some(digest)
+}
+
+// DER encoded signature algorithm object identifiers
+const MD5_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x04";
+const SHA1_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x05";
+const SHA256_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b";
+const SHA384_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0c";
+const SHA512_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0d";
+const SHA224_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0e";
+const ECDSA_WITH_SHA1: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x01";
+const ECDSA_WITH_SHA224: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x01";
+const ECDSA_WITH_SHA256: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x02";
+const ECDSA_WITH_SHA384: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x03";
+const ECDSA_WITH_SHA512: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x04";
+const DSA_WITH_SHA1: &[u8] = b"\x2a\x86\x48\xce\x38\x04\x03";
+const DSA_WITH_SHA224: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x03\x01";
+const DSA_WITH_SHA256: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x03\x02";
+
+// Returns the object identifier of the signatureAlgorithm of a DER encoded X.509 certificate:
+//
+// Certificate ::= SEQUENCE {
+//     tbsCertificate       TBSCertificate,
+//     signatureAlgorithm   AlgorithmIdentifier,
+//     signatureValue       BIT STRING }
+//
+// AlgorithmIdentifier ::= SEQUENCE {
+//     algorithm            OBJECT IDENTIFIER,
+//     parameters           ANY DEFINED BY algorithm OPTIONAL }
+fn signature_algorithm(cert: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const OBJECT_IDENTIFIER: u8 = 0x06;
+
+    let (cert, _) = der_element(cert, SEQUENCE)?;
+    let (_, rest) = der_element(cert, SEQUENCE)?;
+    let (algorithm, _) = der_element(rest, SEQUENCE)?;
+    let (oid, _) = der_element(algorithm, OBJECT_IDENTIFIER)?;
+    Some(oid)
+}
+
+// Splits a DER element with the given tag off the front of the buffer, returning its contents and the remainder.
+fn der_element(buf: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    if *buf.first()? != tag {
+        return None;
+    }
+
+    let (len, buf) = match *buf.get(1)? {
+        len @ 0..=0x7f => (len as usize, &buf[2..]),
+        0x81..=0x84 => {
+            let n = (buf[1] & 0x7f) as usize;
+            let bytes = buf.get(2..2 + n)?;
+            let len = bytes.iter().fold(0, |len, &b| len << 8 | b as usize);
+            (len, &buf[2 + n..])
+        }
+        _ => return None,
+    };
+
+    if buf.len() < len {
+        return None;
+    }
+    Some(buf.split_at(len))
+}
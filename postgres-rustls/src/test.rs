@@ -0,0 +1,151 @@
+use futures::{Future, Stream};
+use rustls::internal::pemfile;
+use rustls::{
+    Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError,
+};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::tls::TlsConnect;
+
+use super::*;
+
+// webpki requires a subjectAltName matching the domain, which the test server's certificate lacks
+struct NoVerification;
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _: &RootCertStore,
+        _: &[Certificate],
+        _: DNSNameRef<'_>,
+        _: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+fn config() -> ClientConfig {
+    let mut config = ClientConfig::new();
+    config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(NoVerification));
+    config
+}
+
+fn smoke_test<T>(s: &str, tls: T)
+where
+    T: TlsConnect<TcpStream>,
+    T::Stream: 'static,
+{
+    let mut runtime = Runtime::new().unwrap();
+
+    let builder = s.parse::<tokio_postgres::Config>().unwrap();
+
+    let handshake = TcpStream::connect(&"127.0.0.1:5433".parse().unwrap())
+        .map_err(|e| panic!("{}", e))
+        .and_then(|s| builder.connect_raw(s, tls));
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let prepare = client.prepare("SELECT 1::INT4");
+    let statement = runtime.block_on(prepare).unwrap();
+    let select = client.query(&statement, &[]).collect().map(|rows| {
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<_, i32>(0), 1);
+    });
+    runtime.block_on(select).unwrap();
+
+    drop(statement);
+    drop(client);
+    runtime.run().unwrap();
+}
+
+#[test]
+fn require() {
+    smoke_test(
+        "user=ssl_user dbname=postgres sslmode=require",
+        TlsConnector::new(Arc::new(config()), "localhost"),
+    );
+}
+
+#[test]
+fn prefer() {
+    smoke_test(
+        "user=ssl_user dbname=postgres",
+        TlsConnector::new(Arc::new(config()), "localhost"),
+    );
+}
+
+#[test]
+fn scram_user() {
+    smoke_test(
+        "user=scram_user password=password dbname=postgres sslmode=require",
+        TlsConnector::new(Arc::new(config()), "localhost"),
+    );
+}
+
+#[test]
+fn invalid_domain() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let builder = "user=ssl_user dbname=postgres sslmode=require"
+        .parse::<tokio_postgres::Config>()
+        .unwrap();
+    let handshake = TcpStream::connect(&"127.0.0.1:5433".parse().unwrap())
+        .map_err(|e| panic!("{}", e))
+        .and_then(move |s| {
+            builder.connect_raw(s, TlsConnector::new(Arc::new(config()), "127.0.0.1"))
+        });
+    runtime.block_on(handshake).err().unwrap();
+}
+
+#[test]
+fn server_signature_algorithm() {
+    let mut cert = BufReader::new(File::open("../test/server.crt").unwrap());
+    let certs = pemfile::certs(&mut cert).unwrap();
+    assert_eq!(signature_algorithm(&certs[0].0), Some(SHA1_WITH_RSA));
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn runtime() {
+    connect_runtime("host=localhost port=5433 user=postgres sslmode=require");
+}
+
+#[cfg(feature = "runtime")]
+fn connect_runtime(s: &str) {
+    let mut runtime = Runtime::new().unwrap();
+
+    let connector = MakeTlsConnector::new(config());
+
+    let connect = tokio_postgres::connect(s, connector);
+    let (mut client, connection) = runtime.block_on(connect).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let execute = client.simple_query("SELECT 1").for_each(|_| Ok(()));
+    runtime.block_on(execute).unwrap();
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn runtime_ip_prefer() {
+    // the handshake can't verify the server's certificate against an IP address, so the session falls back to plaintext
+    connect_runtime("host=127.0.0.1 port=5433 user=postgres sslmode=prefer");
+}
+
+#[test]
+#[cfg(feature = "runtime")]
+fn runtime_ip_disable() {
+    connect_runtime("host=127.0.0.1 port=5433 user=postgres sslmode=disable");
+}
+
+#[test]
+#[cfg(all(feature = "runtime", unix))]
+fn runtime_unix_socket() {
+    connect_runtime("host=/tmp port=5433 user=postgres sslmode=prefer");
+}
@@ -0,0 +1,176 @@
+//! Utilities for working with the PostgreSQL binary copy format.
+
+use bytes::Bytes;
+use fallible_iterator::FallibleIterator;
+use futures::stream::{self, Stream};
+use futures::{future, Async, Future, Poll};
+use std::marker::PhantomData;
+use tokio_postgres::binary_copy::{self, BinaryCopyInStream, BinaryCopyOutStream};
+use tokio_postgres::impls;
+use tokio_postgres::types::{ToSql, Type};
+use tokio_postgres::Error;
+
+use crate::{Client, CopyOutReader, ToStatement};
+
+#[doc(inline)]
+pub use tokio_postgres::binary_copy::BinaryCopyOutRow;
+
+/// A writer which serializes rows into the PostgreSQL binary copy format and sends them to the server.
+///
+/// The copy is aborted if the writer is dropped without calling `finish`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use postgres::binary_copy::BinaryCopyInWriter;
+/// use postgres::types::Type;
+/// use postgres::{Client, NoTls};
+///
+/// # fn main() -> Result<(), postgres::Error> {
+/// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+///
+/// let mut writer = BinaryCopyInWriter::new(
+///     &mut client,
+///     "COPY people (id, name) FROM STDIN (FORMAT binary)",
+///     &[Type::INT4, Type::TEXT],
+/// )?;
+/// writer.write(&[&1i32, &"john"])?;
+/// writer.write(&[&2i32, &"jane"])?;
+/// writer.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BinaryCopyInWriter<'a> {
+    writer: Option<binary_copy::BinaryCopyInWriter>,
+    copy: impls::CopyIn<BinaryCopyInStream>,
+    rows: Option<u64>,
+    _p: PhantomData<&'a mut ()>,
+}
+
+impl<'a> BinaryCopyInWriter<'a> {
+    /// Starts a `COPY ... FROM STDIN (FORMAT binary)` query, returning a writer which will serialize rows with the
+    /// specified types.
+    ///
+    /// The `query` argument can either be a `Statement`, or a raw query string.
+    pub fn new<T>(
+        client: &'a mut Client,
+        query: &T,
+        types: &[Type],
+    ) -> Result<BinaryCopyInWriter<'a>, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        let statement = query.__statement(client)?;
+        let (writer, stream) = binary_copy::BinaryCopyInWriter::new(types);
+        let copy = client.get_mut().copy_in(&statement, &[], stream);
+
+        Ok(BinaryCopyInWriter {
+            writer: Some(writer),
+            copy,
+            rows: None,
+            _p: PhantomData,
+        })
+    }
+
+    /// Writes a single row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values provided does not match the number expected, or if the writer is used after a
+    /// previous call returned an error.
+    pub fn write(&mut self, values: &[&dyn ToSql]) -> Result<(), Error> {
+        self.write_iter(values.iter().cloned())
+    }
+
+    /// Like `write`, except that it takes an iterator of values rather than a slice.
+    pub fn write_iter<'b, I>(&mut self, values: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'b dyn ToSql>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let writer = self
+            .writer
+            .take()
+            .expect("BinaryCopyInWriter used after an error");
+        let writer = self.drive(writer.write_iter(values))?;
+        self.writer = Some(writer);
+        Ok(())
+    }
+
+    /// Completes the copy, returning the number of rows written.
+    pub fn finish(mut self) -> Result<u64, Error> {
+        let writer = self
+            .writer
+            .take()
+            .expect("BinaryCopyInWriter used after an error");
+        self.drive(writer.finish())?;
+
+        match self.rows {
+            Some(rows) => Ok(rows),
+            None => (&mut self.copy).wait(),
+        }
+    }
+
+    // The copy is polled alongside the writer's future so that the data it hands off is sent to the server.
+    fn drive<F>(&mut self, mut future: F) -> Result<F::Item, Error>
+    where
+        F: Future<Error = Error>,
+    {
+        let copy = &mut self.copy;
+        let rows = &mut self.rows;
+
+        future::poll_fn(|| {
+            if let Async::Ready(item) = future.poll()? {
+                return Ok(Async::Ready(item));
+            }
+
+            if rows.is_none() {
+                if let Async::Ready(n) = copy.poll()? {
+                    *rows = Some(n);
+                }
+            }
+
+            Ok(Async::NotReady)
+        })
+        .wait()
+    }
+}
+
+/// An iterator of rows deserialized from the PostgreSQL binary copy format.
+pub struct BinaryCopyOutIter<'a> {
+    it: stream::Wait<BinaryCopyOutStream<Chunks<'a>>>,
+}
+
+impl<'a> BinaryCopyOutIter<'a> {
+    /// Creates an iterator which will parse rows with the specified types from the reader returned by
+    /// `Client::copy_out` for a `COPY ... TO STDOUT (FORMAT binary)` query.
+    pub fn new(reader: CopyOutReader<'a>, types: &[Type]) -> BinaryCopyOutIter<'a> {
+        BinaryCopyOutIter {
+            it: BinaryCopyOutStream::new(Chunks(reader), types).wait(),
+        }
+    }
+}
+
+impl<'a> FallibleIterator for BinaryCopyOutIter<'a> {
+    type Item = BinaryCopyOutRow;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<BinaryCopyOutRow>, Error> {
+        match self.it.next() {
+            Some(Ok(row)) => Ok(Some(row)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+struct Chunks<'a>(CopyOutReader<'a>);
+
+impl<'a> Stream for Chunks<'a> {
+    type Item = Bytes;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, Error> {
+        self.0.next_chunk().map(Async::Ready)
+    }
+}
@@ -2,10 +2,10 @@ use fallible_iterator::FallibleIterator;
 use futures::{Async, Future, Poll, Stream};
 use std::io::{self, Read};
 use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
-use tokio_postgres::types::{ToSql, Type};
+use tokio_postgres::types::{Format, ToSql, Type};
 #[cfg(feature = "runtime")]
 use tokio_postgres::Socket;
-use tokio_postgres::{Error, Row, SimpleQueryMessage};
+use tokio_postgres::{Error, FromRow, Row, SimpleQueryMessage};
 
 #[cfg(feature = "runtime")]
 use crate::Config;
@@ -123,6 +123,50 @@ impl Client {
         self.query_iter(query, params)?.collect()
     }
 
+    /// Like `query`, except that each of the resulting rows is converted into a value of type `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use postgres::{Client, Error, FromRow, NoTls, Row};
+    ///
+    /// struct Person {
+    ///     name: String,
+    ///     age: i32,
+    /// }
+    ///
+    /// impl FromRow for Person {
+    ///     fn from_row(row: &Row) -> Result<Person, Error> {
+    ///         Ok(Person {
+    ///             name: row.try_get("name")?,
+    ///             age: row.try_get("age")?,
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), postgres::Error> {
+    /// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+    ///
+    /// for person in client.query_as::<Person, _>("SELECT name, age FROM people", &[])? {
+    ///     println!("{} is {} years old", person.name, person.age);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query_as<R, T>(&mut self, query: &T, params: &[&dyn ToSql]) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.query_iter(query, params)?
+            .map(|row| R::from_row(&row))
+            .collect()
+    }
+
     /// Like `query`, except that it returns a fallible iterator over the resulting rows rather than buffering the
     /// response in memory.
     ///
@@ -161,6 +205,53 @@ impl Client {
         Ok(QueryIter::new(self.0.query(&statement, params)))
     }
 
+    /// Like `query`, but allows the formats of the parameters and of the resulting columns to be specified.
+    ///
+    /// See `tokio_postgres::Client::query_with_formats` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected, or if the number of formats
+    /// provided is neither 0, 1, nor the number of parameters or columns respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use postgres::{Client, NoTls};
+    /// use postgres::types::Format;
+    ///
+    /// # fn main() -> Result<(), postgres::Error> {
+    /// let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+    ///
+    /// let rows = client.query_with_formats(
+    ///     "SELECT to_tsvector($1)",
+    ///     &[&"a fat cat"],
+    ///     &[],
+    ///     &[Format::Text],
+    /// )?;
+    /// let vector: &str = rows[0].get(0);
+    /// println!("vector: {}", vector);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query_with_formats<T>(
+        &mut self,
+        query: &T,
+        params: &[&dyn ToSql],
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> Result<Vec<Row>, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        let statement = query.__statement(self)?;
+        QueryIter::new(
+            self.0
+                .query_with_formats(&statement, params, param_formats, result_formats),
+        )
+        .collect()
+    }
+
     /// Creates a new prepared statement.
     ///
     /// Prepared statements can be executed repeatedly, and may contain query parameters (indicated by `$1`, `$2`, etc),
@@ -315,6 +406,36 @@ impl Client {
         Ok(SimpleQueryIter::new(self.0.simple_query(query)))
     }
 
+    /// Like `simple_query`, but additionally returns a `SimpleQueryMessage::RowDescription` at the start of the
+    /// result set of each statement which returns rows.
+    ///
+    /// # Warning
+    ///
+    /// As with [`simple_query`], prepared statements should be used for any query which contains user-specified data.
+    ///
+    /// [`simple_query`]: #method.simple_query
+    pub fn simple_query_described(
+        &mut self,
+        query: &str,
+    ) -> Result<Vec<SimpleQueryMessage>, Error> {
+        self.simple_query_described_iter(query)?.collect()
+    }
+
+    /// Like `simple_query_described`, except that it returns a fallible iterator over the resulting values rather
+    /// than buffering the response in memory.
+    ///
+    /// # Warning
+    ///
+    /// As with [`simple_query`], prepared statements should be used for any query which contains user-specified data.
+    ///
+    /// [`simple_query`]: #method.simple_query
+    pub fn simple_query_described_iter(
+        &mut self,
+        query: &str,
+    ) -> Result<SimpleQueryIter<'_>, Error> {
+        Ok(SimpleQueryIter::new(self.0.simple_query_described(query)))
+    }
+
     /// Begins a new database transaction.
     ///
     /// The transaction will roll back by default - use the `commit` method to commit it.
@@ -15,7 +15,7 @@ use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
 use tokio_postgres::{Error, Socket};
 
 #[doc(inline)]
-use tokio_postgres::config::{SslMode, TargetSessionAttrs};
+use tokio_postgres::config::{ReplicationMode, SslMode, TargetSessionAttrs};
 
 use crate::{Client, RUNTIME};
 
@@ -37,24 +37,35 @@ type DynExecutor = dyn Executor<Box<dyn Future<Item = (), Error = ()> + Send>> +
 /// * `dbname` - The name of the database to connect to. Defaults to the username.
 /// * `options` - Command line options used to configure the server.
 /// * `application_name` - Sets the `application_name` parameter on the server.
-/// * `sslmode` - Controls usage of TLS. If set to `disable`, TLS will not be used. If set to `prefer`, TLS will be used
-///     if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
+/// * `sslmode` - Controls usage of TLS. One of `disable`, `allow`, `prefer`, `require`, `verify-ca` or `verify-full`,
+///   with the same meanings as in libpq (see `SslMode`). Defaults to `prefer`.
+/// * `sslrootcert` - The path of a file containing the certificate authorities trusted to sign the server's certificate.
+/// * `sslcert` - The path of the client certificate file.
+/// * `sslkey` - The path of the private key file of the client certificate.
+/// * `sslpassword` - The password used to decrypt the private key of the client certificate.
+/// * `sslcrl` - The path of a file containing certificate revocation lists checked when verifying the server.
+/// * `sslsni` - Controls the use of TLS Server Name Indication. A value of 0 disables it. Defaults to on.
 /// * `host` - The host to connect to. On Unix platforms, if the host starts with a `/` character it is treated as the
-///     path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
-///     can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
-///     with the `connect` method.
+///   path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
+///   can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
+///   with the `connect` method.
 /// * `port` - The port to connect to. Multiple ports can be specified, separated by commas. The number of ports must be
-///     either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
-///     omitted or the empty string.
+///   either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
+///   omitted or the empty string.
 /// * `connect_timeout` - The time limit in seconds applied to each socket-level connection attempt. Note that hostnames
-///     can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
+///   can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
 /// * `keepalives` - Controls the use of TCP keepalive. A value of 0 disables keepalive and nonzero integers enable it.
-///     This option is ignored when connecting with Unix sockets. Defaults to on.
+///   This option is ignored when connecting with Unix sockets. Defaults to on.
 /// * `keepalives_idle` - The number of seconds of inactivity after which a keepalive message is sent to the server.
-///     This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
+///   This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
 /// * `target_session_attrs` - Specifies requirements of the session. If set to `read-write`, the client will check that
-///     the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
-///     in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+///   the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
+///   in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+/// * `replication` - Starts the session as a walsender. If set to `database`, the session uses the logical replication
+///   protocol, and if set to `true`, the physical replication protocol. Defaults to no replication.
+/// * `service` - The name of a service in the service file (see below) to read any unspecified settings from. Only
+///   used by `Config::parse_with_env`; parsing with `FromStr` does not read the service file and ignores this key.
+/// * `passfile` - The path of the password file used if no password is specified. See `Config::passfile`.
 ///
 /// ## Examples
 ///
@@ -94,9 +105,35 @@ type DynExecutor = dyn Executor<Box<dyn Future<Item = (), Error = ()> + Send>> +
 /// ```not_rust
 /// postgresql:///mydb?user=user&host=/var/lib/postgresql
 /// ```
+///
+/// # Service File
+///
+/// Connection parameters can be grouped into named services in a libpq-compatible connection service file. Each
+/// service is a section starting with its name in square brackets, followed by `key=value` lines using the keys
+/// described above. Lines starting with `#` are ignored.
+///
+/// ```not_rust
+/// [mydb]
+/// host=somehost
+/// port=5433
+/// user=admin
+/// ```
+///
+/// A service is looked up first in the per-user service file, which is `~/.pg_service.conf` (or
+/// `%APPDATA%\postgresql\.pg_service.conf` on Windows) unless overridden by the `PGSERVICEFILE` environment variable,
+/// and then in `pg_service.conf` in the directory named by the `PGSYSCONFDIR` environment variable. Services are only
+/// resolved by `Config::from_env` and `Config::parse_with_env`.
+///
+/// # Environment
+///
+/// `Config::from_env` and `Config::parse_with_env` additionally read settings from the same environment variables as
+/// libpq: `PGHOST`, `PGPORT`, `PGDATABASE`, `PGUSER`, `PGPASSWORD`, `PGOPTIONS`, `PGAPPNAME`, `PGSSLMODE`,
+/// `PGSSLROOTCERT`, `PGSSLCERT`, `PGSSLKEY`, `PGSSLCRL`, `PGSSLSNI`, `PGCONNECT_TIMEOUT`, `PGTARGETSESSIONATTRS` and
+/// `PGSERVICE`. As in libpq, settings in a connection string take precedence over those of its service, which take
+/// precedence over the environment.
 #[derive(Clone)]
 pub struct Config {
-    config: tokio_postgres::Config,
+    pub(crate) config: tokio_postgres::Config,
     // this is an option since we don't want to boot up our default runtime unless we're actually going to use it.
     executor: Option<Arc<DynExecutor>>,
 }
@@ -124,6 +161,22 @@ impl Config {
         }
     }
 
+    /// Creates a configuration from the libpq environment variables and the service named by `PGSERVICE`.
+    ///
+    /// This is equivalent to `Config::parse_with_env("")`.
+    pub fn from_env() -> Result<Config, Error> {
+        tokio_postgres::Config::from_env().map(Config::from)
+    }
+
+    /// Parses a connection string, using the libpq environment variables for any settings it does not specify.
+    ///
+    /// Settings are resolved in the same order as libpq: those in the connection string take precedence over those of
+    /// the service named by its `service` key (or by `PGSERVICE`), which take precedence over the environment
+    /// variables.
+    pub fn parse_with_env(s: &str) -> Result<Config, Error> {
+        tokio_postgres::Config::parse_with_env(s).map(Config::from)
+    }
+
     /// Sets the user to authenticate with.
     ///
     /// Required.
@@ -169,6 +222,59 @@ impl Config {
         self
     }
 
+    /// Sets the path of the file containing the certificate authorities trusted to sign the server's certificate.
+    pub fn ssl_root_cert<T>(&mut self, ssl_root_cert: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        self.config.ssl_root_cert(ssl_root_cert);
+        self
+    }
+
+    /// Sets the path of the client certificate file.
+    pub fn ssl_cert<T>(&mut self, ssl_cert: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        self.config.ssl_cert(ssl_cert);
+        self
+    }
+
+    /// Sets the path of the private key file of the client certificate.
+    pub fn ssl_key<T>(&mut self, ssl_key: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        self.config.ssl_key(ssl_key);
+        self
+    }
+
+    /// Sets the password used to decrypt the private key of the client certificate.
+    pub fn ssl_password<T>(&mut self, ssl_password: T) -> &mut Config
+    where
+        T: AsRef<[u8]>,
+    {
+        self.config.ssl_password(ssl_password);
+        self
+    }
+
+    /// Sets the path of the file containing certificate revocation lists checked when verifying the server.
+    pub fn ssl_crl<T>(&mut self, ssl_crl: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        self.config.ssl_crl(ssl_crl);
+        self
+    }
+
+    /// Controls the use of TLS Server Name Indication.
+    ///
+    /// Defaults to `true`.
+    pub fn ssl_sni(&mut self, ssl_sni: bool) -> &mut Config {
+        self.config.ssl_sni(ssl_sni);
+        self
+    }
+
     /// Adds a host to the configuration.
     ///
     /// Multiple hosts can be specified by calling this method multiple times, and each will be tried in order. On Unix
@@ -237,6 +343,41 @@ impl Config {
         self
     }
 
+    /// Sets the maximum number of prepared statements cached by the client.
+    ///
+    /// When enabled, `Client::prepare` and `Client::prepare_typed` reuse a previously prepared statement with the same
+    /// query and parameter types rather than preparing a new one, and the least recently used statement is closed once
+    /// the capacity is exceeded. Defaults to 0, which disables the cache.
+    pub fn statement_cache_capacity(&mut self, statement_cache_capacity: usize) -> &mut Config {
+        self.config
+            .statement_cache_capacity(statement_cache_capacity);
+        self
+    }
+
+    /// Sets the replication mode of the session.
+    ///
+    /// A session in replication mode can run replication commands such as `CREATE_REPLICATION_SLOT` with
+    /// `Client::simple_query`. Only the simple query protocol is supported by the server in this mode. Defaults to no
+    /// replication.
+    pub fn replication_mode(&mut self, replication_mode: ReplicationMode) -> &mut Config {
+        self.config.replication_mode(replication_mode);
+        self
+    }
+
+    /// Sets the path of the password file.
+    ///
+    /// If no password is configured and the server requests one, it is looked up in this file by host, port, database
+    /// and user, as described in the libpq documentation. The file is ignored if it is readable by users other than its
+    /// owner. Defaults to the value of the `PGPASSFILE` environment variable, or `~/.pgpass`
+    /// (`%APPDATA%\postgresql\pgpass.conf` on Windows) if it is unset.
+    pub fn passfile<T>(&mut self, passfile: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        self.config.passfile(passfile);
+        self
+    }
+
     /// Sets the executor used to run the connection futures.
     ///
     /// Defaults to a postgres-specific tokio `Runtime`.
@@ -272,7 +413,7 @@ impl Config {
         Ok(Client::from(client))
     }
 
-    fn with_executor<F, T>(&self, f: F) -> T
+    pub(crate) fn with_executor<F, T>(&self, f: F) -> T
     where
         F: FnOnce(&dyn Executor<Box<dyn Future<Item = (), Error = ()> + Send>>) -> T,
     {
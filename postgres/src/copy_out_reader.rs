@@ -33,6 +33,21 @@ impl<'a> CopyOutReader<'a> {
             _p: PhantomData,
         })
     }
+
+    pub(crate) fn next_chunk(&mut self) -> Result<Option<Bytes>, Error> {
+        if self.cur.remaining() > 0 {
+            let pos = self.cur.position() as usize;
+            let chunk = self.cur.get_ref().slice_from(pos);
+            self.cur = Cursor::new(Bytes::new());
+            return Ok(Some(chunk));
+        }
+
+        match self.it.next() {
+            Some(Ok(chunk)) => Ok(Some(chunk)),
+            Some(Err(e)) => Err(e),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<'a> Read for CopyOutReader<'a> {
@@ -63,7 +63,8 @@ use tokio::runtime::{self, Runtime};
 #[cfg(feature = "runtime")]
 pub use tokio_postgres::Socket;
 pub use tokio_postgres::{
-    accepts, error, row, tls, to_sql_checked, types, Column, Portal, SimpleQueryMessage, Statement,
+    accepts, error, row, tls, to_sql_checked, types, Column, Portal, SimpleColumn, SimpleQueryMessage,
+    Statement,
 };
 
 pub use crate::client::*;
@@ -75,17 +76,20 @@ pub use crate::error::Error;
 pub use crate::query_iter::*;
 pub use crate::query_portal_iter::*;
 #[doc(no_inline)]
-pub use crate::row::{Row, SimpleQueryRow};
+pub use crate::row::{FromRow, Row, RowRef, SimpleQueryRow};
 pub use crate::simple_query_iter::*;
 #[doc(no_inline)]
 pub use crate::tls::NoTls;
 pub use crate::to_statement::*;
 pub use crate::transaction::*;
 
+pub mod binary_copy;
 mod client;
 #[cfg(feature = "runtime")]
 pub mod config;
 mod copy_out_reader;
+#[cfg(feature = "runtime")]
+pub mod pool;
 mod query_iter;
 mod query_portal_iter;
 mod simple_query_iter;
@@ -0,0 +1,201 @@
+//! Connection pooling.
+//!
+//! This is a blocking wrapper over the asynchronous pool in `tokio_postgres::pool`. Checkouts run on the executor of
+//! the pool's `Config`.
+//!
+//! Requires the `runtime` Cargo feature (enabled by default).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use postgres::pool::{Builder, Recycle};
+//! use postgres::NoTls;
+//!
+//! # fn main() -> Result<(), postgres::Error> {
+//! let config = "host=localhost user=postgres".parse()?;
+//! let pool = Builder::new()
+//!     .max_size(16)
+//!     .recycle(Recycle::DiscardAll)
+//!     .build(config, NoTls);
+//!
+//! let mut client = pool.get()?;
+//! client.simple_query("SELECT 1")?;
+//! # Ok(())
+//! # }
+//! ```
+use futures::sync::oneshot;
+use futures::Future;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
+use tokio_postgres::{Error, Socket};
+
+#[doc(inline)]
+pub use tokio_postgres::pool::{PoolState, Recycle};
+
+use crate::{Client, Config};
+
+/// A builder for `Pool`s.
+///
+/// See `tokio_postgres::pool::Builder` for details about each setting.
+#[derive(Debug, Clone, Default)]
+pub struct Builder(tokio_postgres::pool::Builder);
+
+impl Builder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Builder {
+        Builder(tokio_postgres::pool::Builder::new())
+    }
+
+    /// Sets the maximum number of connections managed by the pool.
+    ///
+    /// Defaults to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is 0.
+    pub fn max_size(&mut self, max_size: usize) -> &mut Builder {
+        self.0.max_size(max_size);
+        self
+    }
+
+    /// Sets the number of connections the pool will try to keep open.
+    ///
+    /// Defaults to 0.
+    pub fn min_size(&mut self, min_size: usize) -> &mut Builder {
+        self.0.min_size(min_size);
+        self
+    }
+
+    /// Sets the amount of time a connection may sit idle in the pool before it is closed.
+    ///
+    /// Idle connections are evicted when a connection is checked out of the pool rather than by a background task, so
+    /// connections in a pool that is not being used remain open past the timeout. Defaults to 10 minutes.
+    pub fn idle_timeout(&mut self, idle_timeout: Option<Duration>) -> &mut Builder {
+        self.0.idle_timeout(idle_timeout);
+        self
+    }
+
+    /// Sets the maximum age of a connection, after which it is closed rather than being returned to the pool.
+    ///
+    /// Defaults to 30 minutes.
+    pub fn max_lifetime(&mut self, max_lifetime: Option<Duration>) -> &mut Builder {
+        self.0.max_lifetime(max_lifetime);
+        self
+    }
+
+    /// Sets the time limit for checking a connection out of the pool.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn checkout_timeout(&mut self, checkout_timeout: Option<Duration>) -> &mut Builder {
+        self.0.checkout_timeout(checkout_timeout);
+        self
+    }
+
+    /// Determines if connections are checked for health before being handed out.
+    ///
+    /// Defaults to `true`.
+    pub fn test_on_checkout(&mut self, test_on_checkout: bool) -> &mut Builder {
+        self.0.test_on_checkout(test_on_checkout);
+        self
+    }
+
+    /// Sets the action taken on a previously used connection before it is handed out again.
+    ///
+    /// Defaults to `Recycle::Never`.
+    pub fn recycle(&mut self, recycle: Recycle) -> &mut Builder {
+        self.0.recycle(recycle);
+        self
+    }
+
+    /// Creates a pool which opens connections with the specified configuration and TLS implementation.
+    pub fn build<T>(&self, config: Config, tls: T) -> Pool<T>
+    where
+        T: MakeTlsConnect<Socket>,
+    {
+        Pool {
+            pool: self.0.build(config.config.clone(), tls),
+            config,
+        }
+    }
+}
+
+/// A pool of connections.
+pub struct Pool<T> {
+    pool: tokio_postgres::pool::Pool<T>,
+    config: Config,
+}
+
+impl<T> Clone for Pool<T> {
+    fn clone(&self) -> Pool<T> {
+        Pool {
+            pool: self.pool.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Pool").field("pool", &self.pool).finish()
+    }
+}
+
+impl<T> Pool<T> {
+    /// Returns information about the current state of the pool.
+    pub fn state(&self) -> PoolState {
+        self.pool.state()
+    }
+}
+
+impl<T> Pool<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    T::TlsConnect: Send,
+    T::Stream: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    /// Checks a connection out of the pool, blocking until one is available.
+    pub fn get(&self) -> Result<PooledClient<T>, Error> {
+        let (tx, rx) = oneshot::channel();
+        let checkout = self.pool.get().then(|r| tx.send(r).map_err(|_| ()));
+        self.config
+            .with_executor(|e| e.execute(Box::new(checkout)))
+            .unwrap();
+        rx.wait().unwrap().map(|mut pooled| PooledClient {
+            client: Some(Client::from(pooled.__take_client())),
+            pooled,
+        })
+    }
+}
+
+/// A client checked out of a `Pool`.
+///
+/// The connection is returned to the pool when this is dropped.
+pub struct PooledClient<T> {
+    client: Option<Client>,
+    pooled: tokio_postgres::pool::PooledClient<T>,
+}
+
+impl<T> Drop for PooledClient<T> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            self.pooled.__restore_client(client.into_inner());
+        }
+    }
+}
+
+impl<T> Deref for PooledClient<T> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for PooledClient<T> {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client.as_mut().unwrap()
+    }
+}
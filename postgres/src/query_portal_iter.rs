@@ -1,8 +1,9 @@
 use fallible_iterator::FallibleIterator;
 use futures::stream::{self, Stream};
+use futures::{future, Future};
 use std::marker::PhantomData;
 use tokio_postgres::impls;
-use tokio_postgres::{Error, Row};
+use tokio_postgres::{Error, Row, RowRef};
 
 /// The iterator returned by the `query_portal_iter` method.
 pub struct QueryPortalIter<'a> {
@@ -22,6 +23,31 @@ impl<'a> QueryPortalIter<'a> {
             _p: PhantomData,
         }
     }
+
+    /// Like `next`, but passes a borrowed view of the next row to `f` rather than returning an owned `Row`.
+    ///
+    /// The view borrows directly from the buffer the row was read into, and `&str` and `&[u8]` values can be borrowed
+    /// from it. Unlike `next`, no allocation is performed per row.
+    pub fn next_row_ref<F, R>(&mut self, f: F) -> Result<Option<R>, Error>
+    where
+        F: FnOnce(RowRef<'_>) -> Result<R, Error>,
+    {
+        let stream = self.it.get_mut();
+        let mut f = Some(f);
+        future::poll_fn(|| stream.poll_row_ref(|row| f.take().expect("row already visited")(row)))
+            .wait()
+    }
+
+    /// Calls `f` with a borrowed view of each of the remaining rows.
+    ///
+    /// See `next_row_ref` for details. Iteration stops at the first error returned by `f`.
+    pub fn for_each_row<F>(mut self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(RowRef<'_>) -> Result<(), Error>,
+    {
+        while self.next_row_ref(&mut f)?.is_some() {}
+        Ok(())
+    }
 }
 
 impl<'a> FallibleIterator for QueryPortalIter<'a> {
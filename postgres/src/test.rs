@@ -1,6 +1,7 @@
+use fallible_iterator::FallibleIterator;
 use std::io::Read;
-use tokio_postgres::types::Type;
-use tokio_postgres::NoTls;
+use tokio_postgres::types::{Format, Type};
+use tokio_postgres::{FromRow, NoTls, Row};
 
 use super::*;
 
@@ -34,6 +35,97 @@ fn query_unprepared() {
     assert_eq!(rows[0].get::<_, &str>(0), "hello");
 }
 
+#[test]
+fn query_with_formats() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let rows = client
+        .query_with_formats(
+            "SELECT $1::TSVECTOR, 2::INT4",
+            &[&"b a"],
+            &[Format::Text],
+            &[Format::Text],
+        )
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, &str>(0), "'a' 'b'");
+    assert_eq!(rows[0].get_text::<_, i32>(1), 2);
+}
+
+#[test]
+fn simple_query_described() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let messages = client
+        .simple_query_described("SELECT 1 AS a; SELECT 'x' AS b, 2::INT8 AS c")
+        .unwrap();
+    let descriptions = messages
+        .iter()
+        .filter_map(|m| match m {
+            SimpleQueryMessage::RowDescription(columns) => Some(
+                columns
+                    .iter()
+                    .map(|c| (c.name(), c.type_oid()))
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        descriptions,
+        vec![
+            vec![("a", Type::INT4.oid())],
+            vec![("b", Type::TEXT.oid()), ("c", Type::INT8.oid())],
+        ]
+    );
+    assert_eq!(messages.len(), 6);
+}
+
+#[test]
+fn query_iter_row_ref() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let mut it = client
+        .query_iter("SELECT $1::TEXT || generate_series(1, 3)", &[&"row "])
+        .unwrap();
+    let first = it.next_row_ref(|row| Ok(row.get::<_, &str>(0).to_string()));
+    assert_eq!(first.unwrap().as_ref().map(|s| &**s), Some("row 1"));
+
+    let mut total = 0;
+    it.for_each_row(|row| {
+        total += row.get::<_, &str>(0).len();
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(total, 10);
+}
+
+#[test]
+fn query_as() {
+    struct Person {
+        id: i32,
+        name: String,
+    }
+
+    impl FromRow for Person {
+        fn from_row(row: &Row) -> Result<Person, Error> {
+            Ok(Person {
+                id: row.try_get("id")?,
+                name: row.try_get("name")?,
+            })
+        }
+    }
+
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    let people = client
+        .query_as::<Person, _>("SELECT 1 AS id, $1::TEXT AS name", &[&"alice"])
+        .unwrap();
+    assert_eq!(people.len(), 1);
+    assert_eq!(people[0].id, 1);
+    assert_eq!(people[0].name, "alice");
+}
+
 #[test]
 fn transaction_commit() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -196,6 +288,86 @@ fn copy_out() {
     client.simple_query("SELECT 1").unwrap();
 }
 
+#[test]
+fn binary_copy_in() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut writer = binary_copy::BinaryCopyInWriter::new(
+        &mut client,
+        "COPY foo (id, name) FROM STDIN (FORMAT binary)",
+        &[Type::INT4, Type::TEXT],
+    )
+    .unwrap();
+    for i in 0..1000i32 {
+        writer
+            .write(&[&i, &format!("the value for {}", i)])
+            .unwrap();
+    }
+    assert_eq!(writer.finish().unwrap(), 1000);
+
+    let rows = client
+        .query("SELECT id, name FROM foo ORDER BY id", &[])
+        .unwrap();
+
+    assert_eq!(rows.len(), 1000);
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, i32>(0), i as i32);
+        assert_eq!(row.get::<_, &str>(1), format!("the value for {}", i));
+    }
+}
+
+#[test]
+fn binary_copy_in_dropped() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)")
+        .unwrap();
+
+    let mut writer = binary_copy::BinaryCopyInWriter::new(
+        &mut client,
+        "COPY foo (id, name) FROM STDIN (FORMAT binary)",
+        &[Type::INT4, Type::TEXT],
+    )
+    .unwrap();
+    writer.write(&[&1i32, &"steven"]).unwrap();
+    drop(writer);
+
+    let rows = client.query("SELECT id FROM foo", &[]).unwrap();
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn binary_copy_out() {
+    let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
+
+    client
+        .simple_query(
+            "CREATE TEMPORARY TABLE foo (id INT, name TEXT);
+             INSERT INTO foo (id, name) VALUES (1, 'steven'), (2, NULL);",
+        )
+        .unwrap();
+
+    let reader = client
+        .copy_out("COPY foo (id, name) TO STDOUT (FORMAT binary)", &[])
+        .unwrap();
+    let rows = binary_copy::BinaryCopyOutIter::new(reader, &[Type::INT4, Type::TEXT])
+        .collect::<Vec<_>>()
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32>(0), 1);
+    assert_eq!(rows[0].get::<Option<&str>>(1), Some("steven"));
+    assert_eq!(rows[1].get::<i32>(0), 2);
+    assert_eq!(rows[1].get::<Option<&str>>(1), None);
+
+    client.simple_query("SELECT 1").unwrap();
+}
+
 #[test]
 fn portal() {
     let mut client = Client::connect("host=localhost port=5433 user=postgres", NoTls).unwrap();
@@ -222,3 +394,19 @@ fn portal() {
     assert_eq!(rows.len(), 1);
     assert_eq!(rows[0].get::<_, i32>(0), 3);
 }
+
+#[test]
+fn pool() {
+    let config = "host=localhost port=5433 user=postgres".parse().unwrap();
+    let pool = crate::pool::Builder::new().max_size(1).build(config, NoTls);
+
+    let mut client = pool.get().unwrap();
+    let rows = client.query("SELECT $1::TEXT", &[&"hello"]).unwrap();
+    assert_eq!(rows[0].get::<_, &str>(0), "hello");
+    drop(client);
+    assert_eq!(pool.state().idle_connections, 1);
+
+    let mut client = pool.get().unwrap();
+    client.simple_query("SELECT 1").unwrap();
+    assert_eq!(pool.state().connections, 1);
+}
@@ -1,8 +1,8 @@
 use fallible_iterator::FallibleIterator;
 use futures::Future;
 use std::io::Read;
-use tokio_postgres::types::{ToSql, Type};
-use tokio_postgres::{Error, Row, SimpleQueryMessage};
+use tokio_postgres::types::{Format, ToSql, Type};
+use tokio_postgres::{Error, FromRow, Row, SimpleQueryMessage};
 
 use crate::{
     Client, CopyOutReader, Portal, QueryIter, QueryPortalIter, SimpleQueryIter, Statement,
@@ -92,6 +92,15 @@ impl<'a> Transaction<'a> {
         self.client.query(query, params)
     }
 
+    /// Like `Client::query_as`.
+    pub fn query_as<R, T>(&mut self, query: &T, params: &[&dyn ToSql]) -> Result<Vec<R>, Error>
+    where
+        R: FromRow,
+        T: ?Sized + ToStatement,
+    {
+        self.client.query_as(query, params)
+    }
+
     /// Like `Client::query_iter`.
     pub fn query_iter<T>(
         &mut self,
@@ -104,6 +113,21 @@ impl<'a> Transaction<'a> {
         self.client.query_iter(query, params)
     }
 
+    /// Like `Client::query_with_formats`.
+    pub fn query_with_formats<T>(
+        &mut self,
+        query: &T,
+        params: &[&dyn ToSql],
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> Result<Vec<Row>, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        self.client
+            .query_with_formats(query, params, param_formats, result_formats)
+    }
+
     /// Binds parameters to a statement, creating a "portal".
     ///
     /// Portals can be used with the `query_portal` method to page through the results of a query without being forced
@@ -122,6 +146,31 @@ impl<'a> Transaction<'a> {
         self.client.get_mut().bind(&statement, params).wait()
     }
 
+    /// Like `bind`, but allows the formats of the parameters and of the resulting columns to be specified.
+    ///
+    /// See `Client::query_with_formats` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected, or if the number of formats
+    /// provided is neither 0, 1, nor the number of parameters or columns respectively.
+    pub fn bind_with_formats<T>(
+        &mut self,
+        query: &T,
+        params: &[&dyn ToSql],
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> Result<Portal, Error>
+    where
+        T: ?Sized + ToStatement,
+    {
+        let statement = query.__statement(self.client)?;
+        self.client
+            .get_mut()
+            .bind_with_formats(&statement, params, param_formats, result_formats)
+            .wait()
+    }
+
     /// Continues execution of a portal, returning the next set of rows.
     ///
     /// Unlike `query`, portals can be incrementally evaluated by limiting the number of rows returned in each call to
@@ -178,6 +227,22 @@ impl<'a> Transaction<'a> {
         self.client.simple_query_iter(query)
     }
 
+    /// Like `Client::simple_query_described`.
+    pub fn simple_query_described(
+        &mut self,
+        query: &str,
+    ) -> Result<Vec<SimpleQueryMessage>, Error> {
+        self.client.simple_query_described(query)
+    }
+
+    /// Like `Client::simple_query_described_iter`.
+    pub fn simple_query_described_iter(
+        &mut self,
+        query: &str,
+    ) -> Result<SimpleQueryIter<'_>, Error> {
+        self.client.simple_query_described_iter(query)
+    }
+
     /// Like `Client::transaction`.
     pub fn transaction(&mut self) -> Result<Transaction<'_>, Error> {
         let depth = self.depth + 1;
@@ -0,0 +1,440 @@
+//! Utilities for working with the PostgreSQL binary copy format.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use futures::Future;
+//! use tokio_postgres::binary_copy::BinaryCopyInWriter;
+//! use tokio_postgres::types::Type;
+//! use tokio_postgres::{Client, Error, Statement};
+//!
+//! // `statement` was prepared from "COPY people (id, name) FROM STDIN (FORMAT binary)"
+//! fn copy_people(
+//!     client: &mut Client,
+//!     statement: &Statement,
+//! ) -> impl Future<Item = u64, Error = Error> {
+//!     let (writer, stream) = BinaryCopyInWriter::new(&[Type::INT4, Type::TEXT]);
+//!     let copy = client.copy_in(statement, &[], stream);
+//!     let write = writer
+//!         .write(&[&1i32, &"john"])
+//!         .and_then(|writer| writer.write(&[&2i32, &"jane"]))
+//!         .and_then(|writer| writer.finish());
+//!
+//!     copy.join(write).map(|(rows, ())| rows)
+//! }
+//! ```
+
+use bytes::{Bytes, BytesMut};
+use futures::sync::mpsc;
+use futures::{sink, try_ready, Async, Future, Poll, Sink, Stream};
+use std::io;
+use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::types::{FromSql, IsNull, ToSql, Type, WrongType};
+use crate::Error;
+
+const MAGIC: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const HEADER_LEN: usize = MAGIC.len() + 4 + 4;
+const BUFFER_LEN: usize = 4096;
+
+enum Message {
+    Data(Bytes),
+    Done(Bytes),
+}
+
+/// A writer which serializes rows into the PostgreSQL binary copy format.
+///
+/// The writer is paired with a `BinaryCopyInStream`, which should be passed to `Client::copy_in` along with a
+/// `COPY ... FROM STDIN (FORMAT binary)` statement. Rows are buffered and handed to the stream in chunks. If the writer
+/// is dropped before `finish` is called, the stream returns an error and the copy is aborted.
+pub struct BinaryCopyInWriter {
+    sender: mpsc::Sender<Message>,
+    types: Vec<Type>,
+    buf: Vec<u8>,
+}
+
+impl BinaryCopyInWriter {
+    /// Creates a new writer which will serialize rows with the specified types, along with its stream.
+    pub fn new(types: &[Type]) -> (BinaryCopyInWriter, BinaryCopyInStream) {
+        let (sender, receiver) = mpsc::channel(1);
+
+        let mut buf = Vec::with_capacity(BUFFER_LEN);
+        buf.extend_from_slice(MAGIC);
+        // flags
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        // header extension length
+        buf.extend_from_slice(&0i32.to_be_bytes());
+
+        let writer = BinaryCopyInWriter {
+            sender,
+            types: types.to_vec(),
+            buf,
+        };
+        let stream = BinaryCopyInStream {
+            receiver,
+            done: false,
+        };
+
+        (writer, stream)
+    }
+
+    /// Writes a single row, returning the writer once it is ready to accept another.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values provided does not match the number expected.
+    pub fn write(self, values: &[&dyn ToSql]) -> Write {
+        self.write_iter(values.iter().cloned())
+    }
+
+    /// Like `write`, except that it takes an iterator of values rather than a slice.
+    pub fn write_iter<'a, I>(mut self, values: I) -> Write
+    where
+        I: IntoIterator<Item = &'a dyn ToSql>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let values = values.into_iter();
+        assert!(
+            values.len() == self.types.len(),
+            "expected {} values but got {}",
+            self.types.len(),
+            values.len(),
+        );
+
+        let start = self.buf.len();
+        if let Err(e) = self.encode(values) {
+            self.buf.truncate(start);
+            return Write(WriteState::Failed(Some(e)));
+        }
+
+        if self.buf.len() < BUFFER_LEN {
+            return Write(WriteState::Ready(Some(self)));
+        }
+
+        let buf = mem::replace(&mut self.buf, Vec::with_capacity(BUFFER_LEN));
+        Write(WriteState::Sending {
+            future: self.sender.send(Message::Data(Bytes::from(buf))),
+            types: self.types,
+        })
+    }
+
+    /// Completes the copy, flushing any buffered rows to the stream.
+    pub fn finish(mut self) -> Finish {
+        self.buf.extend_from_slice(&(-1i16).to_be_bytes());
+        Finish(self.sender.send(Message::Done(Bytes::from(self.buf))))
+    }
+
+    fn encode<'a, I>(&mut self, values: I) -> Result<(), Error>
+    where
+        I: Iterator<Item = &'a dyn ToSql>,
+    {
+        self.buf
+            .extend_from_slice(&(self.types.len() as i16).to_be_bytes());
+
+        for (idx, (value, type_)) in values.zip(&self.types).enumerate() {
+            let len_idx = self.buf.len();
+            self.buf.extend_from_slice(&[0; 4]);
+
+            let len = match value
+                .to_sql_checked(type_, &mut self.buf)
+                .map_err(|e| Error::to_sql(e, idx))?
+            {
+                IsNull::Yes => -1,
+                IsNull::No => {
+                    let len = self.buf.len() - len_idx - 4;
+                    if len > i32::MAX as usize {
+                        return Err(Error::to_sql("value too large to transmit".into(), idx));
+                    }
+                    len as i32
+                }
+            };
+            self.buf[len_idx..len_idx + 4].copy_from_slice(&len.to_be_bytes());
+        }
+
+        Ok(())
+    }
+}
+
+enum WriteState {
+    Ready(Option<BinaryCopyInWriter>),
+    Sending {
+        future: sink::Send<mpsc::Sender<Message>>,
+        types: Vec<Type>,
+    },
+    Failed(Option<Error>),
+}
+
+/// The future returned by `BinaryCopyInWriter::write`.
+#[must_use = "futures do nothing unless polled"]
+pub struct Write(WriteState);
+
+impl Future for Write {
+    type Item = BinaryCopyInWriter;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<BinaryCopyInWriter, Error> {
+        match self.0 {
+            WriteState::Ready(ref mut writer) => Ok(Async::Ready(
+                writer.take().expect("future polled after completion"),
+            )),
+            WriteState::Sending {
+                ref mut future,
+                ref mut types,
+            } => {
+                let sender = try_ready!(future.poll().map_err(|_| Error::closed()));
+                Ok(Async::Ready(BinaryCopyInWriter {
+                    sender,
+                    types: mem::take(types),
+                    buf: Vec::with_capacity(BUFFER_LEN),
+                }))
+            }
+            WriteState::Failed(ref mut e) => Err(e.take().expect("future polled after completion")),
+        }
+    }
+}
+
+/// The future returned by `BinaryCopyInWriter::finish`.
+#[must_use = "futures do nothing unless polled"]
+pub struct Finish(sink::Send<mpsc::Sender<Message>>);
+
+impl Future for Finish {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        try_ready!(self.0.poll().map_err(|_| Error::closed()));
+        Ok(Async::Ready(()))
+    }
+}
+
+/// The stream of binary copy data produced by a `BinaryCopyInWriter`.
+///
+/// It should be passed to `Client::copy_in`.
+pub struct BinaryCopyInStream {
+    receiver: mpsc::Receiver<Message>,
+    done: bool,
+}
+
+impl Stream for BinaryCopyInStream {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, io::Error> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        match self.receiver.poll() {
+            Ok(Async::Ready(Some(Message::Data(buf)))) => Ok(Async::Ready(Some(buf))),
+            Ok(Async::Ready(Some(Message::Done(buf)))) => {
+                self.done = true;
+                Ok(Async::Ready(Some(buf)))
+            }
+            Ok(Async::Ready(None)) => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "BinaryCopyInWriter dropped without calling finish",
+            )),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum State {
+    Header,
+    Body,
+    Done,
+}
+
+/// A stream of rows deserialized from the PostgreSQL binary copy format.
+///
+/// It wraps the stream returned by `Client::copy_out` for a `COPY ... TO STDOUT (FORMAT binary)` statement.
+pub struct BinaryCopyOutStream<S> {
+    stream: S,
+    types: Arc<Vec<Type>>,
+    buf: BytesMut,
+    state: State,
+}
+
+impl<S> BinaryCopyOutStream<S>
+where
+    S: Stream<Item = Bytes, Error = Error>,
+{
+    /// Creates a stream which will parse rows with the specified types from the copy data stream.
+    pub fn new(stream: S, types: &[Type]) -> BinaryCopyOutStream<S> {
+        BinaryCopyOutStream {
+            stream,
+            types: Arc::new(types.to_vec()),
+            buf: BytesMut::new(),
+            state: State::Header,
+        }
+    }
+
+    // Returns false if the buffer doesn't yet contain the entire header.
+    fn read_header(&mut self) -> Result<bool, Error> {
+        if self.buf.len() < HEADER_LEN {
+            return Ok(false);
+        }
+
+        if &self.buf[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("invalid binary copy signature"));
+        }
+
+        let extension_len = read_i32(&self.buf[HEADER_LEN - 4..]);
+        if extension_len < 0 {
+            return Err(invalid_data("invalid binary copy header extension length"));
+        }
+
+        let len = HEADER_LEN + extension_len as usize;
+        if self.buf.len() < len {
+            return Ok(false);
+        }
+
+        self.buf.advance(len);
+        Ok(true)
+    }
+
+    // Returns None if the buffer doesn't yet contain the entire tuple, or if it contains the trailer.
+    fn read_row(&mut self) -> Result<Option<BinaryCopyOutRow>, Error> {
+        if self.buf.len() < 2 {
+            return Ok(None);
+        }
+
+        let count = i16::from_be_bytes([self.buf[0], self.buf[1]]);
+        if count == -1 {
+            self.buf.advance(2);
+            self.state = State::Done;
+            return Ok(None);
+        }
+
+        if count as usize != self.types.len() {
+            return Err(invalid_data(&format!(
+                "expected {} values but got {}",
+                self.types.len(),
+                count
+            )));
+        }
+
+        let mut pos = 2;
+        let mut ranges = Vec::with_capacity(self.types.len());
+        for _ in 0..count {
+            if self.buf.len() < pos + 4 {
+                return Ok(None);
+            }
+            let len = read_i32(&self.buf[pos..]);
+            pos += 4;
+
+            if len < 0 {
+                ranges.push(None);
+            } else {
+                let len = len as usize;
+                if self.buf.len() < pos + len {
+                    return Ok(None);
+                }
+                ranges.push(Some(pos..pos + len));
+                pos += len;
+            }
+        }
+
+        Ok(Some(BinaryCopyOutRow {
+            buf: self.buf.split_to(pos).freeze(),
+            ranges,
+            types: self.types.clone(),
+        }))
+    }
+}
+
+impl<S> Stream for BinaryCopyOutStream<S>
+where
+    S: Stream<Item = Bytes, Error = Error>,
+{
+    type Item = BinaryCopyOutRow;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BinaryCopyOutRow>, Error> {
+        loop {
+            match self.state {
+                State::Header => {
+                    if self.read_header()? {
+                        self.state = State::Body;
+                        continue;
+                    }
+                }
+                State::Body => {
+                    if let Some(row) = self.read_row()? {
+                        return Ok(Async::Ready(Some(row)));
+                    }
+                    if self.state == State::Done {
+                        continue;
+                    }
+                }
+                // drain the remainder of the stream so the copy runs to completion
+                State::Done => match try_ready!(self.stream.poll()) {
+                    Some(_) => continue,
+                    None => return Ok(Async::Ready(None)),
+                },
+            }
+
+            match try_ready!(self.stream.poll()) {
+                Some(buf) => self.buf.extend_from_slice(&buf),
+                None => return Err(invalid_data("unexpected EOF in binary copy data")),
+            }
+        }
+    }
+}
+
+/// A row of data parsed from a binary copy.
+pub struct BinaryCopyOutRow {
+    buf: Bytes,
+    ranges: Vec<Option<Range<usize>>>,
+    types: Arc<Vec<Type>>,
+}
+
+impl BinaryCopyOutRow {
+    /// Deserializes a value from the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds or if the value cannot be converted to the specified type.
+    pub fn get<'a, T>(&'a self, idx: usize) -> T
+    where
+        T: FromSql<'a>,
+    {
+        match self.try_get(idx) {
+            Ok(ok) => ok,
+            Err(err) => panic!("error retrieving column {}: {}", idx, err),
+        }
+    }
+
+    /// Like `get`, but returns a `Result` rather than panicking.
+    pub fn try_get<'a, T>(&'a self, idx: usize) -> Result<T, Error>
+    where
+        T: FromSql<'a>,
+    {
+        let type_ = match self.types.get(idx) {
+            Some(type_) => type_,
+            None => return Err(Error::column(idx.to_string())),
+        };
+
+        if !T::accepts(type_) {
+            return Err(Error::from_sql(
+                Box::new(WrongType::new(type_.clone())),
+                idx,
+            ));
+        }
+
+        let buf = self.ranges[idx].clone().map(|r| &self.buf[r]);
+        FromSql::from_sql_nullable(type_, buf).map_err(|e| Error::from_sql(e, idx))
+    }
+}
+
+fn read_i32(buf: &[u8]) -> i32 {
+    i32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]])
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::parse(io::Error::new(io::ErrorKind::InvalidData, message))
+}
@@ -1,15 +1,17 @@
 //! Connection configuration.
 
 use std::borrow::Cow;
+use std::env;
 use std::error;
 #[cfg(unix)]
 use std::ffi::OsStr;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::iter;
 use std::mem;
 #[cfg(unix)]
 use std::os::unix::ffi::OsStrExt;
-#[cfg(unix)]
 use std::path::{Path, PathBuf};
 use std::str::{self, FromStr};
 use std::sync::Arc;
@@ -37,15 +39,43 @@ pub enum TargetSessionAttrs {
     __NonExhaustive,
 }
 
+/// Replication mode configuration.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReplicationMode {
+    /// Physical replication, which allows the session to use replication commands such as `BASE_BACKUP` and
+    /// `START_REPLICATION ... PHYSICAL` but not SQL commands.
+    Physical,
+    /// Logical replication, which allows the session to use replication commands along with SQL commands against the
+    /// database it is connected to.
+    Logical,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
 /// TLS configuration.
+///
+/// Verification of the server's certificate is performed by the `TlsConnect` implementation. The connectors built from
+/// a `Config` by the TLS integration crates verify it as described for each mode.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SslMode {
     /// Do not use TLS.
     Disable,
+    /// Connect without TLS, but retry with TLS if the server rejects the session.
+    ///
+    /// The retry is only made when connecting with `Config::connect`.
+    Allow,
     /// Attempt to connect with TLS but allow sessions without.
+    ///
+    /// A session whose TLS handshake fails is retried without TLS when connecting with `Config::connect`.
     Prefer,
     /// Require the use of TLS.
+    ///
+    /// The server's certificate is only verified if a root certificate is configured, as with `VerifyCa`.
     Require,
+    /// Require the use of TLS, and verify that the server's certificate is signed by a trusted certificate authority.
+    VerifyCa,
+    /// Like `VerifyCa`, and additionally verify that the server's certificate matches the hostname connected to.
+    VerifyFull,
     #[doc(hidden)]
     __NonExhaustive,
 }
@@ -71,6 +101,15 @@ pub(crate) struct Inner {
     pub(crate) keepalives: bool,
     pub(crate) keepalives_idle: Duration,
     pub(crate) target_session_attrs: TargetSessionAttrs,
+    pub(crate) statement_cache_capacity: usize,
+    pub(crate) replication_mode: Option<ReplicationMode>,
+    pub(crate) passfile: Option<PathBuf>,
+    pub(crate) ssl_root_cert: Option<PathBuf>,
+    pub(crate) ssl_cert: Option<PathBuf>,
+    pub(crate) ssl_key: Option<PathBuf>,
+    pub(crate) ssl_password: Option<Vec<u8>>,
+    pub(crate) ssl_crl: Option<PathBuf>,
+    pub(crate) ssl_sni: bool,
 }
 
 /// Connection configuration.
@@ -89,24 +128,35 @@ pub(crate) struct Inner {
 /// * `dbname` - The name of the database to connect to. Defaults to the username.
 /// * `options` - Command line options used to configure the server.
 /// * `application_name` - Sets the `application_name` parameter on the server.
-/// * `sslmode` - Controls usage of TLS. If set to `disable`, TLS will not be used. If set to `prefer`, TLS will be used
-///     if available, but not used otherwise. If set to `require`, TLS will be forced to be used. Defaults to `prefer`.
+/// * `sslmode` - Controls usage of TLS. One of `disable`, `allow`, `prefer`, `require`, `verify-ca` or `verify-full`,
+///   with the same meanings as in libpq (see `SslMode`). Defaults to `prefer`.
+/// * `sslrootcert` - The path of a file containing the certificate authorities trusted to sign the server's certificate.
+/// * `sslcert` - The path of the client certificate file.
+/// * `sslkey` - The path of the private key file of the client certificate.
+/// * `sslpassword` - The password used to decrypt the private key of the client certificate.
+/// * `sslcrl` - The path of a file containing certificate revocation lists checked when verifying the server.
+/// * `sslsni` - Controls the use of TLS Server Name Indication. A value of 0 disables it. Defaults to on.
 /// * `host` - The host to connect to. On Unix platforms, if the host starts with a `/` character it is treated as the
-///     path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
-///     can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
-///     with the `connect` method.
+///   path to the directory containing Unix domain sockets. Otherwise, it is treated as a hostname. Multiple hosts
+///   can be specified, separated by commas. Each host will be tried in turn when connecting. Required if connecting
+///   with the `connect` method.
 /// * `port` - The port to connect to. Multiple ports can be specified, separated by commas. The number of ports must be
-///     either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
-///     omitted or the empty string.
+///   either 1, in which case it will be used for all hosts, or the same as the number of hosts. Defaults to 5432 if
+///   omitted or the empty string.
 /// * `connect_timeout` - The time limit in seconds applied to each socket-level connection attempt. Note that hostnames
-///     can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
+///   can resolve to multiple IP addresses, and this limit is applied to each address. Defaults to no timeout.
 /// * `keepalives` - Controls the use of TCP keepalive. A value of 0 disables keepalive and nonzero integers enable it.
-///     This option is ignored when connecting with Unix sockets. Defaults to on.
+///   This option is ignored when connecting with Unix sockets. Defaults to on.
 /// * `keepalives_idle` - The number of seconds of inactivity after which a keepalive message is sent to the server.
-///     This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
+///   This option is ignored when connecting with Unix sockets. Defaults to 2 hours.
 /// * `target_session_attrs` - Specifies requirements of the session. If set to `read-write`, the client will check that
-///     the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
-///     in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+///   the `transaction_read_write` session parameter is set to `on`. This can be used to connect to the primary server
+///   in a database cluster as opposed to the secondary read-only mirrors. Defaults to `all`.
+/// * `replication` - Starts the session as a walsender. If set to `database`, the session uses the logical replication
+///   protocol, and if set to `true`, the physical replication protocol. Defaults to no replication.
+/// * `service` - The name of a service in the service file (see below) to read any unspecified settings from. Only
+///   used by `Config::parse_with_env`; parsing with `FromStr` does not read the service file and ignores this key.
+/// * `passfile` - The path of the password file used if no password is specified. See `Config::passfile`.
 ///
 /// ## Examples
 ///
@@ -146,6 +196,32 @@ pub(crate) struct Inner {
 /// ```not_rust
 /// postgresql:///mydb?user=user&host=/var/lib/postgresql
 /// ```
+///
+/// # Service File
+///
+/// Connection parameters can be grouped into named services in a libpq-compatible connection service file. Each
+/// service is a section starting with its name in square brackets, followed by `key=value` lines using the keys
+/// described above. Lines starting with `#` are ignored.
+///
+/// ```not_rust
+/// [mydb]
+/// host=somehost
+/// port=5433
+/// user=admin
+/// ```
+///
+/// A service is looked up first in the per-user service file, which is `~/.pg_service.conf` (or
+/// `%APPDATA%\postgresql\.pg_service.conf` on Windows) unless overridden by the `PGSERVICEFILE` environment variable,
+/// and then in `pg_service.conf` in the directory named by the `PGSYSCONFDIR` environment variable. Services are only
+/// resolved by `Config::from_env` and `Config::parse_with_env`.
+///
+/// # Environment
+///
+/// `Config::from_env` and `Config::parse_with_env` additionally read settings from the same environment variables as
+/// libpq: `PGHOST`, `PGPORT`, `PGDATABASE`, `PGUSER`, `PGPASSWORD`, `PGOPTIONS`, `PGAPPNAME`, `PGSSLMODE`,
+/// `PGSSLROOTCERT`, `PGSSLCERT`, `PGSSLKEY`, `PGSSLCRL`, `PGSSLSNI`, `PGCONNECT_TIMEOUT`, `PGTARGETSESSIONATTRS` and
+/// `PGSERVICE`. As in libpq, settings in a connection string take precedence over those of its service, which take
+/// precedence over the environment.
 #[derive(Clone, PartialEq)]
 pub struct Config(pub(crate) Arc<Inner>);
 
@@ -171,9 +247,42 @@ impl Config {
             keepalives: true,
             keepalives_idle: Duration::from_secs(2 * 60 * 60),
             target_session_attrs: TargetSessionAttrs::Any,
+            statement_cache_capacity: 0,
+            replication_mode: None,
+            passfile: None,
+            ssl_root_cert: None,
+            ssl_cert: None,
+            ssl_key: None,
+            ssl_password: None,
+            ssl_crl: None,
+            ssl_sni: true,
         }))
     }
 
+    /// Creates a configuration from the libpq environment variables and the service named by `PGSERVICE`.
+    ///
+    /// This is equivalent to `Config::parse_with_env("")`.
+    pub fn from_env() -> Result<Config, Error> {
+        Config::parse_with_env("")
+    }
+
+    /// Parses a connection string, using the libpq environment variables for any settings it does not specify.
+    ///
+    /// Settings are resolved in the same order as libpq: those in the connection string take precedence over those of
+    /// the service named by its `service` key (or by `PGSERVICE`), which take precedence over the environment
+    /// variables.
+    pub fn parse_with_env(s: &str) -> Result<Config, Error> {
+        let (_, service) = parse(s, Config::new())?;
+
+        let mut config = Config::new();
+        config.params(&env_params())?;
+        if let Some(service) = service.or_else(|| env_var("PGSERVICE")) {
+            config.params(&service_params(&service)?)?;
+        }
+
+        parse(s, config).map(|(config, _)| config)
+    }
+
     /// Sets the user to authenticate with.
     ///
     /// Required.
@@ -219,6 +328,94 @@ impl Config {
         self
     }
 
+    /// Gets the SSL configuration.
+    pub fn get_ssl_mode(&self) -> SslMode {
+        self.0.ssl_mode
+    }
+
+    /// Sets the path of the file containing the certificate authorities trusted to sign the server's certificate.
+    pub fn ssl_root_cert<T>(&mut self, ssl_root_cert: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        Arc::make_mut(&mut self.0).ssl_root_cert = Some(ssl_root_cert.as_ref().to_path_buf());
+        self
+    }
+
+    /// Gets the path of the file containing the trusted certificate authorities, if one has been set.
+    pub fn get_ssl_root_cert(&self) -> Option<&Path> {
+        self.0.ssl_root_cert.as_deref()
+    }
+
+    /// Sets the path of the client certificate file.
+    pub fn ssl_cert<T>(&mut self, ssl_cert: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        Arc::make_mut(&mut self.0).ssl_cert = Some(ssl_cert.as_ref().to_path_buf());
+        self
+    }
+
+    /// Gets the path of the client certificate file, if one has been set.
+    pub fn get_ssl_cert(&self) -> Option<&Path> {
+        self.0.ssl_cert.as_deref()
+    }
+
+    /// Sets the path of the private key file of the client certificate.
+    pub fn ssl_key<T>(&mut self, ssl_key: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        Arc::make_mut(&mut self.0).ssl_key = Some(ssl_key.as_ref().to_path_buf());
+        self
+    }
+
+    /// Gets the path of the private key file of the client certificate, if one has been set.
+    pub fn get_ssl_key(&self) -> Option<&Path> {
+        self.0.ssl_key.as_deref()
+    }
+
+    /// Sets the password used to decrypt the private key of the client certificate.
+    pub fn ssl_password<T>(&mut self, ssl_password: T) -> &mut Config
+    where
+        T: AsRef<[u8]>,
+    {
+        Arc::make_mut(&mut self.0).ssl_password = Some(ssl_password.as_ref().to_vec());
+        self
+    }
+
+    /// Gets the password used to decrypt the private key of the client certificate, if one has been set.
+    pub fn get_ssl_password(&self) -> Option<&[u8]> {
+        self.0.ssl_password.as_deref()
+    }
+
+    /// Sets the path of the file containing certificate revocation lists checked when verifying the server.
+    pub fn ssl_crl<T>(&mut self, ssl_crl: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        Arc::make_mut(&mut self.0).ssl_crl = Some(ssl_crl.as_ref().to_path_buf());
+        self
+    }
+
+    /// Gets the path of the file containing certificate revocation lists, if one has been set.
+    pub fn get_ssl_crl(&self) -> Option<&Path> {
+        self.0.ssl_crl.as_deref()
+    }
+
+    /// Controls the use of TLS Server Name Indication.
+    ///
+    /// Defaults to `true`.
+    pub fn ssl_sni(&mut self, ssl_sni: bool) -> &mut Config {
+        Arc::make_mut(&mut self.0).ssl_sni = ssl_sni;
+        self
+    }
+
+    /// Determines if TLS Server Name Indication is used.
+    pub fn get_ssl_sni(&self) -> bool {
+        self.0.ssl_sni
+    }
+
     /// Adds a host to the configuration.
     ///
     /// Multiple hosts can be specified by calling this method multiple times, and each will be tried in order. On Unix
@@ -298,6 +495,56 @@ impl Config {
         self
     }
 
+    /// Sets the maximum number of prepared statements cached by the client.
+    ///
+    /// When enabled, `Client::prepare` and `Client::prepare_typed` reuse a previously prepared statement with the same
+    /// query and parameter types rather than preparing a new one, and the least recently used statement is closed once
+    /// the capacity is exceeded. Cached statements which fail with `cached plan must not change result type` after a
+    /// schema change are re-prepared and the query retried once. Defaults to 0, which disables the cache.
+    pub fn statement_cache_capacity(&mut self, statement_cache_capacity: usize) -> &mut Config {
+        Arc::make_mut(&mut self.0).statement_cache_capacity = statement_cache_capacity;
+        self
+    }
+
+    /// Sets the replication mode of the session.
+    ///
+    /// A session in replication mode can stream changes from a replication slot with `Client::copy_both_simple` and
+    /// the types in the `replication` module. Only the simple query protocol is supported by the server in this mode.
+    /// Defaults to no replication.
+    pub fn replication_mode(&mut self, replication_mode: ReplicationMode) -> &mut Config {
+        Arc::make_mut(&mut self.0).replication_mode = Some(replication_mode);
+        self
+    }
+
+    /// Sets the path of the password file.
+    ///
+    /// If no password is configured and the server requests one, it is looked up in this file by host, port, database
+    /// and user, as described in the libpq documentation. The file is ignored if it is readable by users other than its
+    /// owner. Defaults to the value of the `PGPASSFILE` environment variable, or `~/.pgpass`
+    /// (`%APPDATA%\postgresql\pgpass.conf` on Windows) if it is unset.
+    pub fn passfile<T>(&mut self, passfile: T) -> &mut Config
+    where
+        T: AsRef<Path>,
+    {
+        Arc::make_mut(&mut self.0).passfile = Some(passfile.as_ref().to_path_buf());
+        self
+    }
+
+    fn params(&mut self, params: &[(String, String)]) -> Result<(), Error> {
+        let mut host = false;
+        let mut port = false;
+        for (key, value) in params {
+            match &**key {
+                "host" if !mem::replace(&mut host, true) => Arc::make_mut(&mut self.0).host.clear(),
+                "port" if !mem::replace(&mut port, true) => Arc::make_mut(&mut self.0).port.clear(),
+                _ => {}
+            }
+            self.param(key, value)?;
+        }
+
+        Ok(())
+    }
+
     fn param(&mut self, key: &str, value: &str) -> Result<(), Error> {
         match key {
             "user" => {
@@ -318,12 +565,36 @@ impl Config {
             "sslmode" => {
                 let mode = match value {
                     "disable" => SslMode::Disable,
+                    "allow" => SslMode::Allow,
                     "prefer" => SslMode::Prefer,
                     "require" => SslMode::Require,
+                    "verify-ca" => SslMode::VerifyCa,
+                    "verify-full" => SslMode::VerifyFull,
                     _ => return Err(Error::config_parse(Box::new(InvalidValue("sslmode")))),
                 };
                 self.ssl_mode(mode);
             }
+            "sslrootcert" => {
+                self.ssl_root_cert(value);
+            }
+            "sslcert" => {
+                self.ssl_cert(value);
+            }
+            "sslkey" => {
+                self.ssl_key(value);
+            }
+            "sslpassword" => {
+                self.ssl_password(value);
+            }
+            "sslcrl" => {
+                self.ssl_crl(value);
+            }
+            "sslsni" => {
+                let sni = value
+                    .parse::<u64>()
+                    .map_err(|_| Error::config_parse(Box::new(InvalidValue("sslsni"))))?;
+                self.ssl_sni(sni != 0);
+            }
             "host" => {
                 for host in value.split(',') {
                     self.host(host);
@@ -374,6 +645,21 @@ impl Config {
                 };
                 self.target_session_attrs(target_session_attrs);
             }
+            "replication" => match value {
+                "database" => {
+                    self.replication_mode(ReplicationMode::Logical);
+                }
+                "true" | "on" | "yes" | "1" => {
+                    self.replication_mode(ReplicationMode::Physical);
+                }
+                "false" | "off" | "no" | "0" => {
+                    Arc::make_mut(&mut self.0).replication_mode = None;
+                }
+                _ => return Err(Error::config_parse(Box::new(InvalidValue("replication")))),
+            },
+            "passfile" => {
+                self.passfile(value);
+            }
             key => {
                 return Err(Error::config_parse(Box::new(UnknownOption(
                     key.to_string(),
@@ -411,11 +697,132 @@ impl FromStr for Config {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Config, Error> {
-        match UrlParser::parse(s)? {
-            Some(config) => Ok(config),
-            None => Parser::parse(s),
+        parse(s, Config::new()).map(|(config, _)| config)
+    }
+}
+
+// Parses a connection string on top of an existing configuration, returning the `service` it names separately.
+fn parse(s: &str, config: Config) -> Result<(Config, Option<String>), Error> {
+    match UrlParser::parse(s, config.clone())? {
+        Some(parsed) => Ok(parsed),
+        None => Parser::parse(s, config),
+    }
+}
+
+const ENV_VARS: &[(&str, &str)] = &[
+    ("PGHOST", "host"),
+    ("PGPORT", "port"),
+    ("PGDATABASE", "dbname"),
+    ("PGUSER", "user"),
+    ("PGPASSWORD", "password"),
+    ("PGOPTIONS", "options"),
+    ("PGAPPNAME", "application_name"),
+    ("PGSSLMODE", "sslmode"),
+    ("PGSSLROOTCERT", "sslrootcert"),
+    ("PGSSLCERT", "sslcert"),
+    ("PGSSLKEY", "sslkey"),
+    ("PGSSLCRL", "sslcrl"),
+    ("PGSSLSNI", "sslsni"),
+    ("PGCONNECT_TIMEOUT", "connect_timeout"),
+    ("PGTARGETSESSIONATTRS", "target_session_attrs"),
+];
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn env_params() -> Vec<(String, String)> {
+    ENV_VARS
+        .iter()
+        .filter_map(|&(name, key)| env_var(name).map(|value| (key.to_string(), value)))
+        .collect()
+}
+
+fn user_service_file() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("PGSERVICEFILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    #[cfg(windows)]
+    let path = env::var_os("APPDATA").map(|p| PathBuf::from(p).join("postgresql"));
+    #[cfg(not(windows))]
+    let path = env::var_os("HOME").map(PathBuf::from);
+    path.map(|p| p.join(".pg_service.conf"))
+}
+
+fn service_params(service: &str) -> Result<Vec<(String, String)>, Error> {
+    let system_file = env::var_os("PGSYSCONFDIR").map(|p| PathBuf::from(p).join("pg_service.conf"));
+
+    for path in user_service_file().into_iter().chain(system_file) {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(Error::config_parse(Box::new(e))),
+        };
+
+        if let Some(params) = parse_service_file(&contents, service)? {
+            return Ok(params);
+        }
+    }
+
+    Err(Error::config_parse(
+        format!("definition of service `{}` not found", service).into(),
+    ))
+}
+
+fn parse_service_file(
+    contents: &str,
+    service: &str,
+) -> Result<Option<Vec<(String, String)>>, Error> {
+    let mut params: Option<Vec<(String, String)>> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            if params.is_some() {
+                break;
+            }
+            let name = match line.find(']') {
+                Some(end) => &line[1..end],
+                None => return Err(Error::config_parse("unterminated service name".into())),
+            };
+            if name == service {
+                params = Some(vec![]);
+            }
+            continue;
+        }
+
+        let params = match &mut params {
+            Some(params) => params,
+            None => continue,
+        };
+
+        let mut it = line.splitn(2, '=');
+        let key = it.next().unwrap().trim();
+        let value = match it.next() {
+            Some(value) => value.trim(),
+            None => {
+                return Err(Error::config_parse(
+                    format!("invalid line in service `{}`: {}", service, line).into(),
+                ))
+            }
+        };
+        if key == "service" {
+            return Err(Error::config_parse(
+                "nested service specifications are not supported".into(),
+            ));
+        }
+        // as in libpq, the first occurrence of a key wins
+        if params.iter().all(|(k, _)| k != key) {
+            params.push((key.to_string(), value.to_string()));
         }
     }
+
+    Ok(params)
 }
 
 // Omit password from debug output
@@ -441,6 +848,18 @@ impl fmt::Debug for Config {
             .field("keepalives", &self.0.keepalives)
             .field("keepalives_idle", &self.0.keepalives_idle)
             .field("target_session_attrs", &self.0.target_session_attrs)
+            .field("statement_cache_capacity", &self.0.statement_cache_capacity)
+            .field("replication_mode", &self.0.replication_mode)
+            .field("passfile", &self.0.passfile)
+            .field("ssl_root_cert", &self.0.ssl_root_cert)
+            .field("ssl_cert", &self.0.ssl_cert)
+            .field("ssl_key", &self.0.ssl_key)
+            .field(
+                "ssl_password",
+                &self.0.ssl_password.as_ref().map(|_| Redaction {}),
+            )
+            .field("ssl_crl", &self.0.ssl_crl)
+            .field("ssl_sni", &self.0.ssl_sni)
             .finish()
     }
 }
@@ -473,19 +892,25 @@ struct Parser<'a> {
 }
 
 impl<'a> Parser<'a> {
-    fn parse(s: &'a str) -> Result<Config, Error> {
+    fn parse(s: &'a str, mut config: Config) -> Result<(Config, Option<String>), Error> {
         let mut parser = Parser {
             s,
             it: s.char_indices().peekable(),
         };
 
-        let mut config = Config::new();
+        let mut service = None;
+        let mut params = vec![];
 
         while let Some((key, value)) = parser.parameter()? {
-            config.param(key, &value)?;
+            if key == "service" {
+                service = Some(value);
+            } else {
+                params.push((key.to_string(), value));
+            }
         }
 
-        Ok(config)
+        config.params(&params)?;
+        Ok((config, service))
     }
 
     fn skip_ws(&mut self) {
@@ -629,10 +1054,14 @@ impl<'a> Parser<'a> {
 struct UrlParser<'a> {
     s: &'a str,
     config: Config,
+    service: Option<String>,
+    host: bool,
+    port: bool,
+    implied_ports: usize,
 }
 
 impl<'a> UrlParser<'a> {
-    fn parse(s: &'a str) -> Result<Option<Config>, Error> {
+    fn parse(s: &'a str, config: Config) -> Result<Option<(Config, Option<String>)>, Error> {
         let s = match Self::remove_url_prefix(s) {
             Some(s) => s,
             None => return Ok(None),
@@ -640,7 +1069,11 @@ impl<'a> UrlParser<'a> {
 
         let mut parser = UrlParser {
             s,
-            config: Config::new(),
+            config,
+            service: None,
+            host: false,
+            port: false,
+            implied_ports: 0,
         };
 
         parser.parse_credentials()?;
@@ -648,7 +1081,14 @@ impl<'a> UrlParser<'a> {
         parser.parse_path()?;
         parser.parse_params()?;
 
-        Ok(Some(parser.config))
+        // hosts without ports use the default only if no port was set here or by a lower layer
+        if parser.config.0.port.is_empty() {
+            for _ in 0..parser.implied_ports {
+                parser.config.port(5432);
+            }
+        }
+
+        Ok(Some((parser.config, parser.service)))
     }
 
     fn remove_url_prefix(s: &str) -> Option<&str> {
@@ -709,6 +1149,7 @@ impl<'a> UrlParser<'a> {
             return Ok(());
         }
 
+        let mut ports = vec![];
         for chunk in host.split(',') {
             let (host, port) = if chunk.starts_with('[') {
                 let idx = match chunk.find(']') {
@@ -733,6 +1174,16 @@ impl<'a> UrlParser<'a> {
             };
 
             self.host_param(host)?;
+            ports.push(port);
+        }
+
+        if ports.iter().all(Option::is_none) {
+            self.implied_ports = ports.len();
+            return Ok(());
+        }
+
+        self.clear_ports();
+        for port in ports {
             let port = self.decode(port.unwrap_or("5432"))?;
             self.config.param("port", &port)?;
         }
@@ -781,7 +1232,12 @@ impl<'a> UrlParser<'a> {
 
             if key == "host" {
                 self.host_param(value)?;
+            } else if key == "service" {
+                self.service = Some(self.decode(value)?.into_owned());
             } else {
+                if key == "port" {
+                    self.clear_ports();
+                }
                 let value = self.decode(value)?;
                 self.config.param(&key, &value)?;
             }
@@ -790,8 +1246,22 @@ impl<'a> UrlParser<'a> {
         Ok(())
     }
 
+    // hosts and ports accumulate, so those in the URL replace rather than extend any set by a lower layer
+    fn clear_hosts(&mut self) {
+        if !mem::replace(&mut self.host, true) {
+            Arc::make_mut(&mut self.config.0).host.clear();
+        }
+    }
+
+    fn clear_ports(&mut self) {
+        if !mem::replace(&mut self.port, true) {
+            Arc::make_mut(&mut self.config.0).port.clear();
+        }
+    }
+
     #[cfg(unix)]
     fn host_param(&mut self, s: &str) -> Result<(), Error> {
+        self.clear_hosts();
         let decoded = Cow::from(percent_encoding::percent_decode(s.as_bytes()));
         if decoded.get(0) == Some(&b'/') {
             self.config.host_path(OsStr::from_bytes(&decoded));
@@ -805,6 +1275,7 @@ impl<'a> UrlParser<'a> {
 
     #[cfg(not(unix))]
     fn host_param(&mut self, s: &str) -> Result<(), Error> {
+        self.clear_hosts();
         let s = self.decode(s)?;
         self.config.param("host", &s)
     }
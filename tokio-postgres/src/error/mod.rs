@@ -336,9 +336,11 @@ enum Kind {
     Tls,
     ToSql(usize),
     FromSql(usize),
-    Column,
+    FromRow(String),
+    Column(String),
     CopyInStream,
     Closed,
+    PipelineAborted,
     Db,
     Parse,
     Encode,
@@ -347,6 +349,8 @@ enum Kind {
     Config,
     #[cfg(feature = "runtime")]
     Connect,
+    #[cfg(feature = "runtime")]
+    Timeout,
 }
 
 struct ErrorInner {
@@ -374,9 +378,13 @@ impl fmt::Display for Error {
             Kind::Tls => fmt.write_str("error performing TLS handshake")?,
             Kind::ToSql(idx) => write!(fmt, "error serializing parameter {}", idx)?,
             Kind::FromSql(idx) => write!(fmt, "error deserializing column {}", idx)?,
-            Kind::Column => fmt.write_str("invalid column")?,
+            Kind::FromRow(ref name) => write!(fmt, "error deserializing column `{}`", name)?,
+            Kind::Column(ref name) => write!(fmt, "invalid column `{}`", name)?,
             Kind::CopyInStream => fmt.write_str("error from a copy_in stream")?,
             Kind::Closed => fmt.write_str("connection closed")?,
+            Kind::PipelineAborted => {
+                fmt.write_str("statement skipped after an earlier error in the pipeline")?
+            }
             Kind::Db => fmt.write_str("db error")?,
             Kind::Parse => fmt.write_str("error parsing response from server")?,
             Kind::Encode => fmt.write_str("error encoding message to server")?,
@@ -385,6 +393,8 @@ impl fmt::Display for Error {
             Kind::Config => fmt.write_str("invalid configuration")?,
             #[cfg(feature = "runtime")]
             Kind::Connect => fmt.write_str("error connecting to server")?,
+            #[cfg(feature = "runtime")]
+            Kind::Timeout => fmt.write_str("timed out waiting for a connection")?,
         };
         if let Some(ref cause) = self.0.cause {
             write!(fmt, ": {}", cause)?;
@@ -415,6 +425,17 @@ impl Error {
             .map(DbError::code)
     }
 
+    /// Determines if the error was returned for a statement in a pipeline which was skipped because an earlier
+    /// statement failed.
+    pub fn is_pipeline_aborted(&self) -> bool {
+        matches!(self.0.kind, Kind::PipelineAborted)
+    }
+
+    #[cfg(feature = "runtime")]
+    pub(crate) fn is_tls(&self) -> bool {
+        matches!(self.0.kind, Kind::Tls)
+    }
+
     fn new(kind: Kind, cause: Option<Box<dyn error::Error + Sync + Send>>) -> Error {
         Error(Box::new(ErrorInner { kind, cause }))
     }
@@ -423,6 +444,10 @@ impl Error {
         Error::new(Kind::Closed, None)
     }
 
+    pub(crate) fn pipeline_aborted() -> Error {
+        Error::new(Kind::PipelineAborted, None)
+    }
+
     pub(crate) fn unexpected_message() -> Error {
         Error::new(Kind::UnexpectedMessage, None)
     }
@@ -452,8 +477,12 @@ impl Error {
         Error::new(Kind::FromSql(idx), Some(e))
     }
 
-    pub(crate) fn column() -> Error {
-        Error::new(Kind::Column, None)
+    pub(crate) fn from_row(e: Box<dyn error::Error + Sync + Send>, name: &str) -> Error {
+        Error::new(Kind::FromRow(name.to_string()), Some(e))
+    }
+
+    pub(crate) fn column(name: String) -> Error {
+        Error::new(Kind::Column(name), None)
     }
 
     pub(crate) fn copy_in_stream<E>(e: E) -> Error
@@ -487,4 +516,9 @@ impl Error {
     pub(crate) fn connect(e: io::Error) -> Error {
         Error::new(Kind::Connect, Some(Box::new(e)))
     }
+
+    #[cfg(feature = "runtime")]
+    pub(crate) fn timeout() -> Error {
+        Error::new(Kind::Timeout, None)
+    }
 }
@@ -1,11 +1,15 @@
 //! Futures and stream types used in the crate.
 use bytes::{Bytes, IntoBuf};
-use futures::{try_ready, Async, Future, Poll, Stream};
+use futures::{try_ready, Async, Future, Poll, Sink, StartSend, Stream};
 use std::error;
+use std::mem;
 use tokio_io::{AsyncRead, AsyncWrite};
 
 use crate::proto;
-use crate::{Client, Connection, Error, Portal, Row, SimpleQueryMessage, Statement, TlsConnect};
+use crate::row::RowRef;
+use crate::{
+    Client, Connection, Error, FromRow, Portal, Row, SimpleQueryMessage, Statement, TlsConnect,
+};
 #[cfg(feature = "runtime")]
 use crate::{MakeTlsConnect, Socket};
 
@@ -95,7 +99,7 @@ where
 
 /// The future returned by `Client::prepare`.
 #[must_use = "futures do nothing unless polled"]
-pub struct Prepare(pub(crate) proto::PrepareFuture);
+pub struct Prepare(pub(crate) proto::CachedPrepareFuture);
 
 impl Future for Prepare {
     type Item = Statement;
@@ -121,6 +125,54 @@ impl Stream for Query {
     }
 }
 
+impl Query {
+    /// Like `poll`, but passes a borrowed view of the next row to `f` rather than returning an owned `Row`.
+    ///
+    /// The view borrows directly from the buffer the row was read into, and `&str` and `&[u8]` values can be borrowed
+    /// from it. Unlike `poll`, no allocation is performed per row.
+    pub fn poll_row_ref<F, R>(&mut self, f: F) -> Poll<Option<R>, Error>
+    where
+        F: FnOnce(RowRef<'_>) -> Result<R, Error>,
+    {
+        self.0.poll_row_ref(f)
+    }
+
+    /// Returns a future which calls `f` with a borrowed view of each of the resulting rows.
+    ///
+    /// See [`poll_row_ref`] for details. The future fails with the first error returned by `f`.
+    ///
+    /// [`poll_row_ref`]: #method.poll_row_ref
+    pub fn for_each_row<F>(self, f: F) -> ForEachRow<F>
+    where
+        F: FnMut(RowRef<'_>) -> Result<(), Error>,
+    {
+        ForEachRow(RowStream::Statement(self.0), f)
+    }
+}
+
+/// The future returned by `Client::query_as`.
+#[must_use = "futures do nothing unless polled"]
+pub struct QueryAs<T>(
+    pub(crate) proto::QueryStream<proto::Statement>,
+    pub(crate) Vec<T>,
+);
+
+impl<T> Future for QueryAs<T>
+where
+    T: FromRow,
+{
+    type Item = Vec<T>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Vec<T>, Error> {
+        while let Some(row) = try_ready!(self.0.poll()) {
+            self.1.push(T::from_row(&row)?);
+        }
+
+        Ok(Async::Ready(mem::take(&mut self.1)))
+    }
+}
+
 /// The future returned by `Client::execute`.
 #[must_use = "futures do nothing unless polled"]
 pub struct Execute(pub(crate) proto::ExecuteFuture);
@@ -164,6 +216,54 @@ impl Stream for QueryPortal {
     }
 }
 
+impl QueryPortal {
+    /// Like `Query::poll_row_ref`.
+    pub fn poll_row_ref<F, R>(&mut self, f: F) -> Poll<Option<R>, Error>
+    where
+        F: FnOnce(RowRef<'_>) -> Result<R, Error>,
+    {
+        self.0.poll_row_ref(f)
+    }
+
+    /// Like `Query::for_each_row`.
+    pub fn for_each_row<F>(self, f: F) -> ForEachRow<F>
+    where
+        F: FnMut(RowRef<'_>) -> Result<(), Error>,
+    {
+        ForEachRow(RowStream::Portal(self.0), f)
+    }
+}
+
+enum RowStream {
+    Statement(proto::QueryStream<proto::Statement>),
+    Portal(proto::QueryStream<proto::Portal>),
+}
+
+/// The future returned by `Query::for_each_row` and `QueryPortal::for_each_row`.
+#[must_use = "futures do nothing unless polled"]
+pub struct ForEachRow<F>(RowStream, F);
+
+impl<F> Future for ForEachRow<F>
+where
+    F: FnMut(RowRef<'_>) -> Result<(), Error>,
+{
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        let f = &mut self.1;
+        loop {
+            let r = match self.0 {
+                RowStream::Statement(ref mut s) => s.poll_row_ref(&mut *f),
+                RowStream::Portal(ref mut s) => s.poll_row_ref(&mut *f),
+            };
+            if try_ready!(r).is_none() {
+                return Ok(Async::Ready(()));
+            }
+        }
+    }
+}
+
 /// The future returned by `Client::copy_in`.
 #[must_use = "futures do nothing unless polled"]
 pub struct CopyIn<S>(pub(crate) proto::CopyInFuture<S>)
@@ -201,6 +301,36 @@ impl Stream for CopyOut {
     }
 }
 
+/// The stream and sink returned by `Client::copy_both_simple`.
+#[must_use = "streams do nothing unless polled"]
+pub struct CopyBoth(pub(crate) proto::CopyBothDuplex);
+
+impl Stream for CopyBoth {
+    type Item = Bytes;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, Error> {
+        self.0.poll()
+    }
+}
+
+impl Sink for CopyBoth {
+    type SinkItem = Bytes;
+    type SinkError = Error;
+
+    fn start_send(&mut self, item: Bytes) -> StartSend<Bytes, Error> {
+        self.0.start_send(item)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Error> {
+        self.0.poll_complete()
+    }
+
+    fn close(&mut self) -> Poll<(), Error> {
+        self.0.close()
+    }
+}
+
 /// The stream returned by `Client::simple_query`.
 #[must_use = "streams do nothing unless polled"]
 pub struct SimpleQuery(pub(crate) proto::SimpleQueryStream);
@@ -115,25 +115,31 @@ use bytes::IntoBuf;
 use futures::{Future, Poll, Stream};
 use std::error::Error as StdError;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use tokio_io::{AsyncRead, AsyncWrite};
 
 pub use crate::config::Config;
 use crate::error::DbError;
 pub use crate::error::Error;
-pub use crate::row::{Row, SimpleQueryRow};
+pub use crate::row::{FromRow, Row, RowRef, SimpleQueryRow};
 #[cfg(feature = "runtime")]
 pub use crate::socket::Socket;
-pub use crate::stmt::Column;
+pub use crate::stmt::{Column, SimpleColumn};
 #[cfg(feature = "runtime")]
 use crate::tls::MakeTlsConnect;
 pub use crate::tls::NoTls;
 use crate::tls::TlsConnect;
-use crate::types::{ToSql, Type};
+use crate::types::{Format, ToSql, Type};
 
+pub mod binary_copy;
 pub mod config;
 pub mod error;
 pub mod impls;
+pub mod pipeline;
+#[cfg(feature = "runtime")]
+pub mod pool;
 mod proto;
+pub mod replication;
 pub mod row;
 #[cfg(feature = "runtime")]
 mod socket;
@@ -177,6 +183,9 @@ impl Client {
     ///
     /// Prepared statements can be executed repeatedly, and may contain query parameters (indicated by `$1`, `$2`, etc),
     /// which are set when executed. Prepared statements can only be used with the connection that created them.
+    ///
+    /// If the statement cache is enabled with `Config::statement_cache_capacity`, a statement previously prepared
+    /// with the same query and parameter types is reused if it is still cached.
     pub fn prepare(&mut self, query: &str) -> impls::Prepare {
         self.prepare_typed(query, &[])
     }
@@ -186,7 +195,16 @@ impl Client {
     /// The list of types may be smaller than the number of parameters - the types of the remaining parameters will be
     /// inferred. For example, `client.prepare_typed(query, &[])` is equivalent to `client.prepare(query)`.
     pub fn prepare_typed(&mut self, query: &str, param_types: &[Type]) -> impls::Prepare {
-        impls::Prepare(self.0.prepare(next_statement(), query, param_types))
+        impls::Prepare(self.0.prepare_cached(query, param_types))
+    }
+
+    /// Creates a new pipeline, which sends a sequence of statements to the server as a single unit.
+    ///
+    /// See the [`pipeline`] module for details.
+    ///
+    /// [`pipeline`]: pipeline/index.html
+    pub fn pipeline(&mut self) -> pipeline::Pipeline {
+        pipeline::Pipeline::new(self.0.clone())
     }
 
     /// Executes a statement, returning the number of rows modified.
@@ -231,6 +249,48 @@ impl Client {
         impls::Query(self.0.query(&statement.0, params))
     }
 
+    /// Like [`query`], but allows the formats of the parameters and of the resulting columns to be specified.
+    ///
+    /// Each list of formats may be empty, in which case every value uses the binary format, contain a single format
+    /// which applies to every value, or contain one format per value. Parameters in the text format must be strings,
+    /// which the server parses with the input function of the parameter's type. Columns in the text format can be
+    /// read as strings with `Row::get` or parsed with `Row::get_text`, which allows values of types without a
+    /// `FromSql` implementation to be retrieved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected, or if the number of formats
+    /// provided is neither 0, 1, nor the number of parameters or columns respectively.
+    ///
+    /// [`query`]: #method.query
+    pub fn query_with_formats(
+        &mut self,
+        statement: &Statement,
+        params: &[&dyn ToSql],
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> impls::Query {
+        impls::Query(self.0.query_with_formats(
+            &statement.0,
+            params.iter().cloned(),
+            param_formats,
+            result_formats,
+        ))
+    }
+
+    /// Executes a statement, converting each of the resulting rows into a value of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub fn query_as<T>(&mut self, statement: &Statement, params: &[&dyn ToSql]) -> impls::QueryAs<T>
+    where
+        T: FromRow,
+    {
+        let stream = self.0.query(&statement.0, params.iter().cloned());
+        impls::QueryAs(stream, vec![])
+    }
+
     /// Binds a statement to a set of parameters, creating a `Portal` which can be incrementally queried.
     ///
     /// Portals only last for the duration of the transaction in which they are created - in particular, a portal
@@ -255,6 +315,33 @@ impl Client {
         impls::Bind(self.0.bind(&statement.0, next_portal(), params))
     }
 
+    /// Like [`bind`], but allows the formats of the parameters and of the resulting columns to be specified.
+    ///
+    /// See [`query_with_formats`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected, or if the number of formats
+    /// provided is neither 0, 1, nor the number of parameters or columns respectively.
+    ///
+    /// [`bind`]: #method.bind
+    /// [`query_with_formats`]: #method.query_with_formats
+    pub fn bind_with_formats(
+        &mut self,
+        statement: &Statement,
+        params: &[&dyn ToSql],
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> impls::Bind {
+        impls::Bind(self.0.bind_with_formats(
+            &statement.0,
+            next_portal(),
+            params.iter().cloned(),
+            param_formats,
+            result_formats,
+        ))
+    }
+
     /// Continues execution of a portal, returning a stream of the resulting rows.
     ///
     /// Unlike `query`, portals can be incrementally evaluated by limiting the number of rows returned in each call to
@@ -328,6 +415,18 @@ impl Client {
         impls::CopyOut(self.0.copy_out(&statement.0, params))
     }
 
+    /// Executes a command which enters copy both mode, such as `START_REPLICATION`, using the simple query protocol.
+    ///
+    /// The returned value is both a stream of the data sent by the server and a sink of the data sent to it, each
+    /// item being the contents of a single `CopyData` message. Closing the sink ends the copy; the stream then finishes
+    /// once the server has completed the command. If the server ends the copy first, the client's end of the copy is
+    /// sent automatically, the stream finishes once the command completes, and no more data can be sent to the sink.
+    ///
+    /// This is a low level API; see the `replication` module for replication specific wrappers.
+    pub fn copy_both_simple(&mut self, query: &str) -> impls::CopyBoth {
+        impls::CopyBoth(self.0.copy_both_simple(query))
+    }
+
     /// Executes a sequence of SQL statements using the simple query protocol.
     ///
     /// Statements should be separated by semicolons. If an error occurs, execution of the sequence will stop at that
@@ -345,6 +444,21 @@ impl Client {
         impls::SimpleQuery(self.0.simple_query(query))
     }
 
+    /// Like `simple_query`, but additionally returns a `SimpleQueryMessage::RowDescription` at the start of the
+    /// result set of each statement which returns rows.
+    ///
+    /// The description is returned even if the statement returns no rows, so the result sets of a sequence of
+    /// statements can be presented separately along with their column names and types.
+    ///
+    /// # Warning
+    ///
+    /// As with [`simple_query`], prepared statements should be used for any query which contains user-specified data.
+    ///
+    /// [`simple_query`]: #method.simple_query
+    pub fn simple_query_described(&mut self, query: &str) -> impls::SimpleQuery {
+        impls::SimpleQuery(self.0.simple_query_described(query))
+    }
+
     /// A utility method to wrap a future in a database transaction.
     ///
     /// The returned future will start a transaction and then run the provided future. If the future returns `Ok`, it
@@ -509,6 +623,11 @@ impl TransactionBuilder {
 pub enum SimpleQueryMessage {
     /// A row of data.
     Row(SimpleQueryRow),
+    /// A statement in the query is about to return rows with the described columns.
+    ///
+    /// This is only returned by `Client::simple_query_described`. Statements which do not return rows, such as
+    /// `INSERT` without a `RETURNING` clause, are not described.
+    RowDescription(Arc<[SimpleColumn]>),
     /// A statement in the query has completed.
     ///
     /// The number of rows modified or selected is returned.
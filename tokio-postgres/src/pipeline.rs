@@ -0,0 +1,216 @@
+//! Explicit pipelining of prepared statements.
+//!
+//! Requests made with methods like `Client::query` are already pipelined when their futures are polled concurrently,
+//! but each is sent with its own Sync message, so the server treats each as an independent unit. A `Pipeline` instead
+//! sends a sequence of statements followed by a single Sync, in the same way as libpq's pipeline mode, and returns the
+//! result of each statement. If a statement fails, the server skips the remaining statements, which are reported as
+//! aborted. Since the statements run in a single implicit transaction, the effects of those before the failure are
+//! rolled back as well, even though their results are successful.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use futures::Future;
+//! use tokio_postgres::{Client, Error, Statement};
+//!
+//! // `insert` was prepared from "INSERT INTO people (name) VALUES ($1)"
+//! fn insert_people(
+//!     client: &mut Client,
+//!     insert: &Statement,
+//! ) -> impl Future<Item = u64, Error = Error> {
+//!     client
+//!         .pipeline()
+//!         .execute(insert, &[&"alice"])
+//!         .execute(insert, &[&"bob"])
+//!         .send()
+//!         .and_then(|results| {
+//!             let mut rows = 0;
+//!             for result in results {
+//!                 rows += result?.rows_affected();
+//!             }
+//!             Ok(rows)
+//!         })
+//! }
+//! ```
+use futures::{Future, Poll};
+use postgres_protocol::message::frontend;
+use std::collections::VecDeque;
+
+use crate::proto;
+use crate::types::ToSql;
+use crate::{Error, Row, Statement};
+
+/// A builder for a pipeline of statements.
+///
+/// Statements are serialized as they are added, and sent to the server by `send`.
+pub struct Pipeline {
+    client: proto::Client,
+    messages: Vec<Vec<u8>>,
+    items: VecDeque<proto::PipelineItem>,
+    error: Option<Error>,
+    abort_on_error: bool,
+}
+
+impl Pipeline {
+    pub(crate) fn new(client: proto::Client) -> Pipeline {
+        Pipeline {
+            client,
+            messages: vec![],
+            items: VecDeque::new(),
+            error: None,
+            abort_on_error: true,
+        }
+    }
+
+    /// Determines if an error in one statement causes the remaining statements to be skipped.
+    ///
+    /// If enabled, the pipeline is sent with a single Sync message, so unless it is sent within an explicit
+    /// transaction its statements run in one implicit transaction. An error then rolls back the statements before it
+    /// as well, even though their results are still `Ok` and report the rows they affected.
+    ///
+    /// If disabled, a Sync message is sent after each statement rather than once at the end of the pipeline, so each
+    /// statement runs in its own implicit transaction unless the pipeline is sent within an explicit transaction.
+    ///
+    /// Defaults to `true`.
+    pub fn abort_on_error(&mut self, abort_on_error: bool) -> &mut Pipeline {
+        self.abort_on_error = abort_on_error;
+        self
+    }
+
+    /// Adds a statement to the pipeline, keeping the resulting rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub fn query(&mut self, statement: &Statement, params: &[&dyn ToSql]) -> &mut Pipeline {
+        self.push(statement, params, true)
+    }
+
+    /// Adds a statement to the pipeline, discarding any resulting rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number expected.
+    pub fn execute(&mut self, statement: &Statement, params: &[&dyn ToSql]) -> &mut Pipeline {
+        self.push(statement, params, false)
+    }
+
+    /// Returns the number of statements in the pipeline.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Determines if the pipeline contains no statements.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Sends the statements in the pipeline to the server, returning a future which resolves to the result of each
+    /// statement in order.
+    ///
+    /// The future fails if a statement's parameters could not be serialized or if the connection fails. Errors
+    /// reported by the server are returned in the results of the individual statements. The pipeline is left empty.
+    pub fn send(&mut self) -> SendPipeline {
+        let messages = self.messages.split_off(0);
+        let items = self.items.split_off(0);
+        let syncs = if self.abort_on_error { 1 } else { items.len() };
+
+        let message = match self.error.take() {
+            Some(e) => Err(e),
+            None => {
+                let mut buf = vec![];
+                for message in &messages {
+                    buf.extend_from_slice(message);
+                    if !self.abort_on_error {
+                        frontend::sync(&mut buf);
+                    }
+                }
+                if self.abort_on_error {
+                    frontend::sync(&mut buf);
+                }
+                Ok(buf)
+            }
+        };
+
+        SendPipeline(
+            self.client
+                .pipeline(message, items, syncs, self.abort_on_error),
+        )
+    }
+
+    fn push(
+        &mut self,
+        statement: &Statement,
+        params: &[&dyn ToSql],
+        keep_rows: bool,
+    ) -> &mut Pipeline {
+        let message = self
+            .client
+            .bind_message(&statement.0, "", params.iter().cloned(), &[], &[])
+            .and_then(|mut buf| {
+                frontend::execute("", 0, &mut buf).map_err(Error::parse)?;
+                Ok(buf)
+            });
+        match message {
+            Ok(message) => self.messages.push(message),
+            Err(e) => {
+                if self.error.is_none() {
+                    self.error = Some(e);
+                }
+            }
+        }
+
+        self.items.push_back(proto::PipelineItem {
+            statement: statement.0.clone(),
+            keep_rows,
+        });
+        self
+    }
+}
+
+/// The result of a statement executed in a pipeline.
+pub struct PipelineResult {
+    rows: Vec<Row>,
+    rows_affected: u64,
+}
+
+impl PipelineResult {
+    pub(crate) fn new(rows: Vec<Row>, rows_affected: u64) -> PipelineResult {
+        PipelineResult {
+            rows,
+            rows_affected,
+        }
+    }
+
+    /// Returns the rows returned by the statement.
+    ///
+    /// This is always empty for statements added with `Pipeline::execute`.
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Consumes the result, returning the rows returned by the statement.
+    pub fn into_rows(self) -> Vec<Row> {
+        self.rows
+    }
+
+    /// Returns the number of rows modified by the statement.
+    ///
+    /// If the statement does not modify any rows (e.g. `SELECT`), 0 is returned.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
+/// The future returned by `Pipeline::send`.
+#[must_use = "futures do nothing unless polled"]
+pub struct SendPipeline(proto::PipelineFuture);
+
+impl Future for SendPipeline {
+    type Item = Vec<Result<PipelineResult, Error>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Vec<Result<PipelineResult, Error>>, Error> {
+        self.0.poll()
+    }
+}
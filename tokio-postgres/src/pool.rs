@@ -0,0 +1,645 @@
+//! Connection pooling.
+//!
+//! A `Pool` opens connections on demand from a `Config` and a `MakeTlsConnect` implementation, and hands them out as
+//! `PooledClient`s which return their connection to the pool when dropped. Connection futures are spawned onto the
+//! default tokio executor, so the pool must be used from within a tokio runtime.
+//!
+//! Requires the `runtime` Cargo feature (enabled by default).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures::{Future, Stream};
+//! use std::time::Duration;
+//! use tokio_postgres::pool::{Builder, Recycle};
+//! use tokio_postgres::NoTls;
+//!
+//! let config = "host=localhost user=postgres".parse().unwrap();
+//! let pool = Builder::new()
+//!     .max_size(16)
+//!     .checkout_timeout(Some(Duration::from_secs(5)))
+//!     .recycle(Recycle::DiscardAll)
+//!     .build(config, NoTls);
+//!
+//! let fut = pool
+//!     .get()
+//!     .and_then(|mut client| {
+//!         client
+//!             .simple_query("SELECT 1")
+//!             .for_each(|_| Ok(()))
+//!     })
+//!     .map_err(|e| eprintln!("error: {}", e));
+//!
+//! tokio::run(fut);
+//! ```
+use antidote::Mutex;
+use futures::sync::oneshot;
+use futures::{try_ready, Async, Future, Poll, Stream};
+use log::{debug, error};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_timer::Delay;
+
+use crate::tls::{MakeTlsConnect, TlsConnect};
+use crate::{impls, Client, Config, Error, Socket};
+
+/// The action taken on a previously used connection before it is handed out again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recycle {
+    /// Reuse the connection as-is.
+    Never,
+    /// Run `DISCARD ALL` to reset all session state.
+    DiscardAll,
+    /// Run a custom statement.
+    Statement(String),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+impl Recycle {
+    fn statement(&self) -> Option<&str> {
+        match self {
+            Recycle::DiscardAll => Some("DISCARD ALL"),
+            Recycle::Statement(s) => Some(s),
+            Recycle::Never | Recycle::__NonExhaustive => None,
+        }
+    }
+}
+
+/// A builder for `Pool`s.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    max_size: usize,
+    min_size: usize,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+    checkout_timeout: Option<Duration>,
+    test_on_checkout: bool,
+    recycle: Recycle,
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+impl Builder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Builder {
+        Builder {
+            max_size: 10,
+            min_size: 0,
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            max_lifetime: Some(Duration::from_secs(30 * 60)),
+            checkout_timeout: Some(Duration::from_secs(30)),
+            test_on_checkout: true,
+            recycle: Recycle::Never,
+        }
+    }
+
+    /// Sets the maximum number of connections managed by the pool.
+    ///
+    /// Defaults to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is 0.
+    pub fn max_size(&mut self, max_size: usize) -> &mut Builder {
+        assert!(max_size > 0, "max_size must be positive");
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the number of connections the pool will try to keep open.
+    ///
+    /// Connections are opened in the background as the pool is used. Defaults to 0.
+    pub fn min_size(&mut self, min_size: usize) -> &mut Builder {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the amount of time a connection may sit idle in the pool before it is closed.
+    ///
+    /// Idle connections are evicted when a connection is checked out of the pool rather than by a background task, so
+    /// connections in a pool that is not being used remain open past the timeout. Defaults to 10 minutes.
+    pub fn idle_timeout(&mut self, idle_timeout: Option<Duration>) -> &mut Builder {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Sets the maximum age of a connection, after which it is closed rather than being returned to the pool.
+    ///
+    /// Defaults to 30 minutes.
+    pub fn max_lifetime(&mut self, max_lifetime: Option<Duration>) -> &mut Builder {
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    /// Sets the time limit for checking a connection out of the pool, including any time spent opening a new
+    /// connection.
+    ///
+    /// Defaults to 30 seconds.
+    pub fn checkout_timeout(&mut self, checkout_timeout: Option<Duration>) -> &mut Builder {
+        self.checkout_timeout = checkout_timeout;
+        self
+    }
+
+    /// Determines if connections are checked for health before being handed out.
+    ///
+    /// A connection which has closed is always discarded. If this is enabled, an empty query is additionally sent to
+    /// the server when no recycling statement was run. Defaults to `true`.
+    pub fn test_on_checkout(&mut self, test_on_checkout: bool) -> &mut Builder {
+        self.test_on_checkout = test_on_checkout;
+        self
+    }
+
+    /// Sets the action taken on a previously used connection before it is handed out again.
+    ///
+    /// A connection whose recycling statement fails is discarded. Defaults to `Recycle::Never`.
+    pub fn recycle(&mut self, recycle: Recycle) -> &mut Builder {
+        self.recycle = recycle;
+        self
+    }
+
+    /// Creates a pool which opens connections with the specified configuration and TLS implementation.
+    ///
+    /// No connections are opened until the pool is first used.
+    pub fn build<T>(&self, config: Config, tls: T) -> Pool<T>
+    where
+        T: MakeTlsConnect<Socket>,
+    {
+        Pool(Arc::new(Inner {
+            config,
+            tls,
+            builder: self.clone(),
+            state: Mutex::new(State {
+                idle: VecDeque::new(),
+                size: 0,
+                waiters: VecDeque::new(),
+            }),
+        }))
+    }
+}
+
+struct Conn {
+    client: Client,
+    created: Instant,
+    dirty: bool,
+}
+
+struct IdleConn {
+    conn: Conn,
+    since: Instant,
+}
+
+enum Slot {
+    Conn(Conn),
+    Permit,
+}
+
+struct State {
+    idle: VecDeque<IdleConn>,
+    // the number of open or opening connections, whether idle or checked out
+    size: usize,
+    waiters: VecDeque<oneshot::Sender<Slot>>,
+}
+
+impl State {
+    fn release(&mut self) {
+        while let Some(waiter) = self.waiters.pop_front() {
+            if waiter.send(Slot::Permit).is_ok() {
+                return;
+            }
+        }
+
+        self.size -= 1;
+    }
+}
+
+struct Inner<T> {
+    config: Config,
+    tls: T,
+    builder: Builder,
+    state: Mutex<State>,
+}
+
+impl<T> Inner<T> {
+    fn expired(&self, conn: &Conn, now: Instant) -> bool {
+        match self.builder.max_lifetime {
+            Some(max_lifetime) => now - conn.created >= max_lifetime,
+            None => false,
+        }
+    }
+
+    fn put(&self, mut conn: Conn) {
+        let mut state = self.state.lock();
+
+        if conn.client.is_closed() || self.expired(&conn, Instant::now()) {
+            state.release();
+            return;
+        }
+
+        while let Some(waiter) = state.waiters.pop_front() {
+            match waiter.send(Slot::Conn(conn)) {
+                Ok(()) => return,
+                Err(Slot::Conn(c)) => conn = c,
+                Err(Slot::Permit) => unreachable!(),
+            }
+        }
+
+        state.idle.push_back(IdleConn {
+            conn,
+            since: Instant::now(),
+        });
+    }
+
+    fn release(&self) {
+        self.state.lock().release();
+    }
+
+    fn prune(&self, state: &mut State) {
+        let now = Instant::now();
+        let idle_timeout = self.builder.idle_timeout;
+
+        let mut i = 0;
+        while i < state.idle.len() {
+            let idle = &state.idle[i];
+            let stale = match idle_timeout {
+                Some(idle_timeout) => now - idle.since >= idle_timeout,
+                None => false,
+            };
+
+            if stale || idle.conn.client.is_closed() || self.expired(&idle.conn, now) {
+                state.idle.remove(i);
+                state.release();
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+impl<T> Inner<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    T::TlsConnect: Send,
+    T::Stream: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    fn connect(&self) -> impls::Connect<T> {
+        self.config.connect(self.tls.clone())
+    }
+
+    fn replenish(self: &Arc<Self>) {
+        loop {
+            {
+                let mut state = self.state.lock();
+                if state.size >= self.builder.min_size || state.size >= self.builder.max_size {
+                    return;
+                }
+                state.size += 1;
+            }
+
+            let inner = self.clone();
+            let connect = self.connect().then(move |r| {
+                match r {
+                    Ok((client, connection)) => {
+                        spawn_connection(connection);
+                        inner.put(Conn {
+                            client,
+                            created: Instant::now(),
+                            dirty: false,
+                        });
+                    }
+                    Err(e) => {
+                        debug!("error opening pooled connection: {}", e);
+                        inner.release();
+                    }
+                }
+                Ok(())
+            });
+            tokio_executor::spawn(connect);
+        }
+    }
+}
+
+fn spawn_connection<C>(connection: C)
+where
+    C: Future<Item = (), Error = Error> + 'static + Send,
+{
+    let connection = connection.map_err(|e| error!("postgres connection error: {}", e));
+    tokio_executor::spawn(connection);
+}
+
+/// A pool of connections.
+pub struct Pool<T>(Arc<Inner<T>>);
+
+impl<T> Clone for Pool<T> {
+    fn clone(&self) -> Pool<T> {
+        Pool(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        fmt.debug_struct("Pool")
+            .field("connections", &state.connections)
+            .field("idle_connections", &state.idle_connections)
+            .field("builder", &self.0.builder)
+            .finish()
+    }
+}
+
+impl<T> Pool<T> {
+    /// Returns information about the current state of the pool.
+    pub fn state(&self) -> PoolState {
+        let state = self.0.state.lock();
+        PoolState {
+            connections: state.size,
+            idle_connections: state.idle.len(),
+        }
+    }
+}
+
+impl<T> Pool<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    T::TlsConnect: Send,
+    T::Stream: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    /// Checks a connection out of the pool.
+    ///
+    /// An idle connection is reused if one is available. Otherwise, a new connection is opened if the pool is below
+    /// its maximum size, and the checkout waits for a connection to be returned if it is not.
+    pub fn get(&self) -> Checkout<T> {
+        Checkout {
+            pool: self.clone(),
+            state: CheckoutState::Start,
+            timeout: self
+                .0
+                .builder
+                .checkout_timeout
+                .map(|d| Delay::new(Instant::now() + d)),
+        }
+    }
+}
+
+/// Information about the state of a `Pool`.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolState {
+    /// The number of connections managed by the pool, including those which are being opened or are checked out.
+    pub connections: usize,
+    /// The number of idle connections in the pool.
+    pub idle_connections: usize,
+}
+
+enum CheckoutState<T>
+where
+    T: MakeTlsConnect<Socket>,
+{
+    Start,
+    Connecting(Box<impls::Connect<T>>),
+    Waiting(oneshot::Receiver<Slot>),
+    Checking {
+        conn: Conn,
+        query: Option<impls::SimpleQuery>,
+    },
+    Done,
+}
+
+/// The future returned by `Pool::get`.
+#[must_use = "futures do nothing unless polled"]
+pub struct Checkout<T>
+where
+    T: MakeTlsConnect<Socket>,
+{
+    pool: Pool<T>,
+    state: CheckoutState<T>,
+    timeout: Option<Delay>,
+}
+
+impl<T> Checkout<T>
+where
+    T: MakeTlsConnect<Socket>,
+{
+    fn poll_timeout(&mut self) -> Result<(), Error> {
+        if let Some(timeout) = &mut self.timeout {
+            match timeout.poll() {
+                Ok(Async::Ready(())) => return Err(Error::timeout()),
+                Ok(Async::NotReady) => {}
+                // io::Error::other requires Rust 1.74, past the supported minimum version
+                #[allow(clippy::io_other_error)]
+                Err(e) => return Err(Error::io(io::Error::new(io::ErrorKind::Other, e))),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn poll_check(
+        &mut self,
+        conn: &mut Conn,
+        query: &mut Option<impls::SimpleQuery>,
+    ) -> Poll<(), Error> {
+        loop {
+            if let Some(query) = query {
+                while try_ready!(query.poll()).is_some() {}
+                return Ok(Async::Ready(()));
+            }
+
+            if conn.client.is_closed() {
+                return Err(Error::closed());
+            }
+            try_ready!(conn.client.poll_idle());
+
+            let builder = &self.pool.0.builder;
+            let statement = match builder.recycle.statement() {
+                Some(statement) if conn.dirty => statement,
+                _ if builder.test_on_checkout => "",
+                _ => return Ok(Async::Ready(())),
+            };
+            *query = Some(conn.client.simple_query(statement));
+        }
+    }
+
+    fn finish(&self, conn: Conn) -> PooledClient<T> {
+        PooledClient {
+            pool: self.pool.clone(),
+            client: Some(conn.client),
+            created: conn.created,
+        }
+    }
+}
+
+impl<T> Future for Checkout<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    T::TlsConnect: Send,
+    T::Stream: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    type Item = PooledClient<T>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<PooledClient<T>, Error> {
+        loop {
+            // any connection or reservation held by the current state is released when the checkout is dropped
+            self.poll_timeout()?;
+
+            match mem::replace(&mut self.state, CheckoutState::Done) {
+                CheckoutState::Start => {
+                    let inner = &self.pool.0;
+                    let mut state = inner.state.lock();
+                    inner.prune(&mut state);
+
+                    self.state = if let Some(idle) = state.idle.pop_back() {
+                        CheckoutState::Checking {
+                            conn: idle.conn,
+                            query: None,
+                        }
+                    } else if state.size < inner.builder.max_size {
+                        state.size += 1;
+                        CheckoutState::Connecting(Box::new(inner.connect()))
+                    } else {
+                        let (tx, rx) = oneshot::channel();
+                        state.waiters.push_back(tx);
+                        CheckoutState::Waiting(rx)
+                    };
+                    drop(state);
+
+                    inner.replenish();
+                }
+                CheckoutState::Connecting(mut connect) => match connect.poll() {
+                    Ok(Async::Ready((client, connection))) => {
+                        spawn_connection(connection);
+                        let conn = Conn {
+                            client,
+                            created: Instant::now(),
+                            dirty: false,
+                        };
+                        return Ok(Async::Ready(self.finish(conn)));
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = CheckoutState::Connecting(connect);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => {
+                        self.pool.0.release();
+                        return Err(e);
+                    }
+                },
+                CheckoutState::Waiting(mut rx) => match rx.poll() {
+                    Ok(Async::Ready(Slot::Conn(conn))) => {
+                        self.state = CheckoutState::Checking { conn, query: None };
+                    }
+                    Ok(Async::Ready(Slot::Permit)) => {
+                        self.state = CheckoutState::Connecting(Box::new(self.pool.0.connect()));
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = CheckoutState::Waiting(rx);
+                        return Ok(Async::NotReady);
+                    }
+                    Err(_) => self.state = CheckoutState::Start,
+                },
+                CheckoutState::Checking {
+                    mut conn,
+                    mut query,
+                } => match self.poll_check(&mut conn, &mut query) {
+                    Ok(Async::Ready(())) => return Ok(Async::Ready(self.finish(conn))),
+                    Ok(Async::NotReady) => {
+                        self.state = CheckoutState::Checking { conn, query };
+                        return Ok(Async::NotReady);
+                    }
+                    Err(e) => {
+                        debug!("discarding unhealthy pooled connection: {}", e);
+                        drop(conn);
+                        self.pool.0.release();
+                        self.state = CheckoutState::Start;
+                    }
+                },
+                CheckoutState::Done => panic!("Checkout polled after completion"),
+            }
+        }
+    }
+}
+
+impl<T> Drop for Checkout<T>
+where
+    T: MakeTlsConnect<Socket>,
+{
+    fn drop(&mut self) {
+        match mem::replace(&mut self.state, CheckoutState::Done) {
+            CheckoutState::Connecting(_) => self.pool.0.release(),
+            CheckoutState::Waiting(mut rx) => {
+                rx.close();
+                match rx.try_recv() {
+                    Ok(Some(Slot::Conn(conn))) => self.pool.0.put(conn),
+                    Ok(Some(Slot::Permit)) => self.pool.0.release(),
+                    Ok(None) | Err(_) => {}
+                }
+            }
+            CheckoutState::Checking { conn, .. } => self.pool.0.put(conn),
+            CheckoutState::Start | CheckoutState::Done => {}
+        }
+    }
+}
+
+/// A client checked out of a `Pool`.
+///
+/// The connection is returned to the pool when this is dropped.
+pub struct PooledClient<T> {
+    pool: Pool<T>,
+    client: Option<Client>,
+    created: Instant,
+}
+
+impl<T> PooledClient<T> {
+    // Used by the blocking pool in `postgres`, which wraps the client for the duration of the checkout and hands it
+    // back before the `PooledClient` is dropped.
+    #[doc(hidden)]
+    pub fn __take_client(&mut self) -> Client {
+        self.client.take().unwrap()
+    }
+
+    #[doc(hidden)]
+    pub fn __restore_client(&mut self, client: Client) {
+        self.client = Some(client);
+    }
+}
+
+impl<T> Drop for PooledClient<T> {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            self.pool.0.put(Conn {
+                client,
+                created: self.created,
+                dirty: true,
+            });
+        } else {
+            // the client was never handed back, so its slot is free for a new connection
+            self.pool.0.release();
+        }
+    }
+}
+
+impl<T> Deref for PooledClient<T> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        self.client.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for PooledClient<T> {
+    fn deref_mut(&mut self) -> &mut Client {
+        self.client.as_mut().unwrap()
+    }
+}
@@ -0,0 +1,211 @@
+use bytes::{Buf, Bytes, IntoBuf};
+use futures::{Async, Poll, Stream};
+use postgres_protocol::message::backend::Message;
+use std::io;
+use std::mem;
+use std::str;
+use std::sync::Arc;
+
+use crate::proto::client::{Client, PendingRequest};
+use crate::proto::responses::Responses;
+use crate::proto::simple_query;
+use crate::replication::{self, Archive, BaseBackupMessage, Tablespace, WalPosition};
+use crate::stmt::SimpleColumn;
+use crate::{Error, SimpleQueryRow};
+
+const NEW_ARCHIVE_TAG: u8 = b'n';
+const MANIFEST_TAG: u8 = b'm';
+const DATA_TAG: u8 = b'd';
+const PROGRESS_TAG: u8 = b'p';
+
+// The server reports the backup's start position and tablespaces as two result sets before the copy, and its end
+// position as a result set after it.
+#[derive(Copy, Clone)]
+enum Phase {
+    Start,
+    Tablespaces,
+    Copy,
+    End,
+}
+
+enum State {
+    Start {
+        client: Client,
+        request: PendingRequest,
+    },
+    ReadResponse {
+        phase: Phase,
+        columns: Option<Arc<[SimpleColumn]>>,
+        receiver: Responses,
+    },
+    Done,
+}
+
+pub struct BaseBackupStream(State);
+
+impl Stream for BaseBackupStream {
+    type Item = BaseBackupMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BaseBackupMessage>, Error> {
+        loop {
+            match mem::replace(&mut self.0, State::Done) {
+                State::Start { client, request } => {
+                    let receiver = client.send(request)?;
+                    self.0 = State::ReadResponse {
+                        phase: Phase::Start,
+                        columns: None,
+                        receiver,
+                    };
+                }
+                State::ReadResponse {
+                    mut phase,
+                    mut columns,
+                    mut receiver,
+                } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.0 = State::ReadResponse {
+                                phase,
+                                columns,
+                                receiver,
+                            };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    let item = match message {
+                        Some(Message::RowDescription(body)) => {
+                            columns = Some(simple_query::columns_from_description(&body)?);
+                            None
+                        }
+                        Some(Message::DataRow(body)) => {
+                            let row = match &columns {
+                                Some(columns) => SimpleQueryRow::new(columns.clone(), body)?,
+                                None => return Err(Error::unexpected_message()),
+                            };
+                            match phase {
+                                Phase::Start | Phase::End => Some(wal_position(&row, phase)?),
+                                Phase::Tablespaces => {
+                                    Some(BaseBackupMessage::Tablespace(tablespace(&row)?))
+                                }
+                                Phase::Copy => return Err(Error::unexpected_message()),
+                            }
+                        }
+                        Some(Message::CommandComplete(_)) => {
+                            columns = None;
+                            phase = match phase {
+                                Phase::Start => Phase::Tablespaces,
+                                phase => phase,
+                            };
+                            None
+                        }
+                        Some(Message::CopyOutResponse(_)) => {
+                            phase = Phase::Copy;
+                            None
+                        }
+                        Some(Message::CopyData(body)) => Some(copy_data(body.into_bytes())?),
+                        Some(Message::CopyDone) => {
+                            phase = Phase::End;
+                            None
+                        }
+                        Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                        Some(Message::ReadyForQuery(_)) => return Ok(Async::Ready(None)),
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    };
+
+                    self.0 = State::ReadResponse {
+                        phase,
+                        columns,
+                        receiver,
+                    };
+                    if let Some(item) = item {
+                        return Ok(Async::Ready(Some(item)));
+                    }
+                }
+                State::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+impl BaseBackupStream {
+    pub fn new(client: Client, request: PendingRequest) -> BaseBackupStream {
+        BaseBackupStream(State::Start { client, request })
+    }
+}
+
+fn wal_position(row: &SimpleQueryRow, phase: Phase) -> Result<BaseBackupMessage, Error> {
+    let lsn = replication::parse_lsn_column(row, 0)?;
+    let timeline = replication::parse_column(row, 1)?;
+    let position = WalPosition { lsn, timeline };
+
+    match phase {
+        Phase::Start => Ok(BaseBackupMessage::Start(position)),
+        _ => Ok(BaseBackupMessage::End(position)),
+    }
+}
+
+fn tablespace(row: &SimpleQueryRow) -> Result<Tablespace, Error> {
+    Ok(Tablespace {
+        oid: replication::parse_nullable_column(row, 0)?,
+        location: row.try_get(1)?.map(|s| s.to_string()),
+        size: replication::parse_nullable_column(row, 2)?,
+    })
+}
+
+fn copy_data(buf: Bytes) -> Result<BaseBackupMessage, Error> {
+    let tag = match buf.first() {
+        Some(&tag) => tag,
+        None => return Err(Error::parse(invalid("empty base backup message"))),
+    };
+
+    match tag {
+        NEW_ARCHIVE_TAG => {
+            let mut buf = buf.slice_from(1);
+            let name = read_cstr(&mut buf)?;
+            let location = read_cstr(&mut buf)?;
+            Ok(BaseBackupMessage::Archive(Archive {
+                name,
+                tablespace_location: if location.is_empty() {
+                    None
+                } else {
+                    Some(location)
+                },
+            }))
+        }
+        MANIFEST_TAG => Ok(BaseBackupMessage::Manifest),
+        DATA_TAG => Ok(BaseBackupMessage::Data(buf.slice_from(1))),
+        PROGRESS_TAG => {
+            if buf.len() != 9 {
+                return Err(Error::parse(invalid("invalid message length")));
+            }
+            Ok(BaseBackupMessage::Progress(
+                buf.slice_from(1).into_buf().get_u64_be(),
+            ))
+        }
+        tag => Err(Error::parse(invalid(&format!(
+            "unknown base backup message tag `{}`",
+            tag
+        )))),
+    }
+}
+
+fn read_cstr(buf: &mut Bytes) -> Result<String, Error> {
+    let end = match buf.iter().position(|&b| b == 0) {
+        Some(end) => end,
+        None => return Err(Error::parse(invalid("unexpected EOF"))),
+    };
+    let s = str::from_utf8(&buf[..end])
+        .map_err(|e| Error::parse(io::Error::new(io::ErrorKind::InvalidInput, e)))?
+        .to_string();
+    buf.advance(end + 1);
+    Ok(s)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
@@ -1,11 +1,13 @@
 use futures::{try_ready, Poll, Stream};
 use postgres_protocol::message::backend::Message;
 use state_machine_future::{transition, RentToOwn, StateMachineFuture};
+use std::sync::Arc;
 
 use crate::proto::client::{Client, PendingRequest};
 use crate::proto::portal::Portal;
 use crate::proto::responses::Responses;
 use crate::proto::statement::Statement;
+use crate::types::Format;
 use crate::Error;
 
 #[derive(StateMachineFuture)]
@@ -16,6 +18,7 @@ pub enum Bind {
         request: PendingRequest,
         name: String,
         statement: Statement,
+        result_formats: Arc<[Format]>,
     },
     #[state_machine_future(transitions(Finished))]
     ReadBindComplete {
@@ -23,6 +26,7 @@ pub enum Bind {
         client: Client,
         name: String,
         statement: Statement,
+        result_formats: Arc<[Format]>,
     },
     #[state_machine_future(ready)]
     Finished(Portal),
@@ -40,6 +44,7 @@ impl PollBind for Bind {
             client: state.client,
             name: state.name,
             statement: state.statement,
+            result_formats: state.result_formats,
         })
     }
 
@@ -54,6 +59,7 @@ impl PollBind for Bind {
                 state.client.downgrade(),
                 state.name,
                 state.statement,
+                state.result_formats,
             ))),
             Some(_) => Err(Error::unexpected_message()),
             None => Err(Error::closed()),
@@ -67,7 +73,8 @@ impl BindFuture {
         request: PendingRequest,
         name: String,
         statement: Statement,
+        result_formats: Arc<[Format]>,
     ) -> BindFuture {
-        Bind::start(client, request, name, statement)
+        Bind::start(client, request, name, statement, result_formats)
     }
 }
@@ -4,28 +4,32 @@ use futures::sync::mpsc;
 use futures::{AsyncSink, Poll, Sink, Stream};
 use postgres_protocol;
 use postgres_protocol::message::frontend;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::error::Error as StdError;
 use std::sync::{Arc, Weak};
 use tokio_io::{AsyncRead, AsyncWrite};
 
+use crate::proto::base_backup::BaseBackupStream;
 use crate::proto::bind::BindFuture;
 use crate::proto::codec::FrontendMessage;
 use crate::proto::connection::{Request, RequestMessages};
+use crate::proto::copy_both::{CopyBothDuplex, CopyBothReceiver};
 use crate::proto::copy_in::{CopyInFuture, CopyInReceiver, CopyMessage};
 use crate::proto::copy_out::CopyOutStream;
 use crate::proto::execute::ExecuteFuture;
 use crate::proto::idle::{IdleGuard, IdleState};
+use crate::proto::pipeline::{PipelineFuture, PipelineItem};
 use crate::proto::portal::Portal;
 use crate::proto::prepare::PrepareFuture;
 use crate::proto::query::QueryStream;
 use crate::proto::responses::{self, Responses};
 use crate::proto::simple_query::SimpleQueryStream;
 use crate::proto::statement::Statement;
+use crate::proto::statement_cache::{CachedPrepareFuture, Retry, StatementCache, StatementKey};
 #[cfg(feature = "runtime")]
 use crate::proto::CancelQueryFuture;
 use crate::proto::CancelQueryRawFuture;
-use crate::types::{IsNull, Oid, ToSql, Type};
+use crate::types::{Format, IsNull, Oid, ToSql, Type};
 use crate::{Config, Error, TlsConnect};
 #[cfg(feature = "runtime")]
 use crate::{MakeTlsConnect, Socket};
@@ -45,6 +49,7 @@ struct State {
     typeinfo_query: Option<Statement>,
     typeinfo_enum_query: Option<Statement>,
     typeinfo_composite_query: Option<Statement>,
+    statements: StatementCache,
 }
 
 struct Inner {
@@ -76,6 +81,7 @@ impl Client {
                 typeinfo_query: None,
                 typeinfo_enum_query: None,
                 typeinfo_composite_query: None,
+                statements: StatementCache::new(config.0.statement_cache_capacity),
             }),
             idle: IdleState::new(),
             sender,
@@ -130,7 +136,34 @@ impl Client {
         self.0.state.lock().typeinfo_composite_query = Some(statement.clone());
     }
 
+    pub fn cache_statement(&self, statement: &Statement) {
+        let key = statement
+            .cache_key()
+            .expect("statement not cacheable")
+            .clone();
+        let evicted = self
+            .0
+            .state
+            .lock()
+            .statements
+            .insert(key, statement.clone());
+        drop(evicted);
+    }
+
+    pub fn invalidate_statement(&self, statement: &Statement) {
+        let removed = self.0.state.lock().statements.remove(statement);
+        drop(removed);
+    }
+
     pub fn send(&self, request: PendingRequest) -> Result<Responses, Error> {
+        self.send_with_syncs(request, 1)
+    }
+
+    pub fn send_with_syncs(
+        &self,
+        request: PendingRequest,
+        syncs: usize,
+    ) -> Result<Responses, Error> {
         let (messages, idle) = request.0?;
         let (sender, receiver) = responses::channel();
         self.0
@@ -139,30 +172,82 @@ impl Client {
                 messages,
                 sender,
                 idle: Some(idle),
+                syncs,
             })
             .map(|_| receiver)
             .map_err(|_| Error::closed())
     }
 
+    pub fn send_raw(&self, message: Vec<u8>) -> Result<Responses, Error> {
+        self.send(self.pending(|buf| {
+            *buf = message;
+            Ok(())
+        }))
+    }
+
     pub fn simple_query(&self, query: &str) -> SimpleQueryStream {
+        self.simple_query_inner(query, false)
+    }
+
+    pub fn simple_query_described(&self, query: &str) -> SimpleQueryStream {
+        self.simple_query_inner(query, true)
+    }
+
+    fn simple_query_inner(&self, query: &str, describe: bool) -> SimpleQueryStream {
         let pending = self.pending(|buf| {
             frontend::query(query, buf).map_err(Error::parse)?;
             Ok(())
         });
 
-        SimpleQueryStream::new(self.clone(), pending)
+        SimpleQueryStream::new(self.clone(), pending, describe)
     }
 
     pub fn prepare(&self, name: String, query: &str, param_types: &[Type]) -> PrepareFuture {
+        let param_types = param_types.iter().map(Type::oid);
+        self.prepare_inner(name, query, param_types, None)
+    }
+
+    pub fn prepare_cached(&self, query: &str, param_types: &[Type]) -> CachedPrepareFuture {
+        if self.0.state.lock().statements.is_enabled() {
+            return self.prepare_key(StatementKey::new(query, param_types));
+        }
+
+        let future = self.prepare(crate::next_statement(), query, param_types);
+        CachedPrepareFuture::Preparing(Box::new(future), self.clone())
+    }
+
+    pub fn prepare_key(&self, key: StatementKey) -> CachedPrepareFuture {
+        if let Some(statement) = self.0.state.lock().statements.get(&key) {
+            return CachedPrepareFuture::Cached(Some(statement));
+        }
+
+        let future = self.prepare_inner(
+            crate::next_statement(),
+            key.query(),
+            key.types().iter().cloned(),
+            Some(key.clone()),
+        );
+        CachedPrepareFuture::Preparing(Box::new(future), self.clone())
+    }
+
+    fn prepare_inner<I>(
+        &self,
+        name: String,
+        query: &str,
+        param_types: I,
+        cache_key: Option<StatementKey>,
+    ) -> PrepareFuture
+    where
+        I: IntoIterator<Item = Oid>,
+    {
         let pending = self.pending(|buf| {
-            frontend::parse(&name, query, param_types.iter().map(Type::oid), buf)
-                .map_err(Error::parse)?;
+            frontend::parse(&name, query, param_types, buf).map_err(Error::parse)?;
             frontend::describe(b'S', &name, buf).map_err(Error::parse)?;
             frontend::sync(buf);
             Ok(())
         });
 
-        PrepareFuture::new(self.clone(), pending, name)
+        PrepareFuture::new(self.clone(), pending, name, cache_key)
     }
 
     pub fn execute<'a, I>(&self, statement: &Statement, params: I) -> ExecuteFuture
@@ -170,11 +255,8 @@ impl Client {
         I: IntoIterator<Item = &'a dyn ToSql>,
         I::IntoIter: ExactSizeIterator,
     {
-        let pending = PendingRequest(
-            self.excecute_message(statement, params)
-                .map(|m| (RequestMessages::Single(m), self.0.idle.guard())),
-        );
-        ExecuteFuture::new(self.clone(), pending, statement.clone())
+        let (pending, retry) = self.retryable_execute_message(statement, params, &[], &[]);
+        ExecuteFuture::new(self.clone(), pending, statement.clone(), retry)
     }
 
     pub fn query<'a, I>(&self, statement: &Statement, params: I) -> QueryStream<Statement>
@@ -182,11 +264,29 @@ impl Client {
         I: IntoIterator<Item = &'a dyn ToSql>,
         I::IntoIter: ExactSizeIterator,
     {
-        let pending = PendingRequest(
-            self.excecute_message(statement, params)
-                .map(|m| (RequestMessages::Single(m), self.0.idle.guard())),
-        );
-        QueryStream::new(self.clone(), pending, statement.clone())
+        self.query_with_formats(statement, params, &[], &[])
+    }
+
+    pub fn query_with_formats<'a, I>(
+        &self,
+        statement: &Statement,
+        params: I,
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> QueryStream<Statement>
+    where
+        I: IntoIterator<Item = &'a dyn ToSql>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let (pending, retry) =
+            self.retryable_execute_message(statement, params, param_formats, result_formats);
+        QueryStream::new(
+            self.clone(),
+            pending,
+            statement.clone(),
+            retry,
+            result_formats.into(),
+        )
     }
 
     pub fn bind<'a, I>(&self, statement: &Statement, name: String, params: I) -> BindFuture
@@ -194,7 +294,22 @@ impl Client {
         I: IntoIterator<Item = &'a dyn ToSql>,
         I::IntoIter: ExactSizeIterator,
     {
-        let mut buf = self.bind_message(statement, &name, params);
+        self.bind_with_formats(statement, name, params, &[], &[])
+    }
+
+    pub fn bind_with_formats<'a, I>(
+        &self,
+        statement: &Statement,
+        name: String,
+        params: I,
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> BindFuture
+    where
+        I: IntoIterator<Item = &'a dyn ToSql>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut buf = self.bind_message(statement, &name, params, param_formats, result_formats);
         if let Ok(ref mut buf) = buf {
             frontend::sync(buf);
         }
@@ -204,7 +319,29 @@ impl Client {
                 self.0.idle.guard(),
             )
         }));
-        BindFuture::new(self.clone(), pending, name, statement.clone())
+        BindFuture::new(
+            self.clone(),
+            pending,
+            name,
+            statement.clone(),
+            result_formats.into(),
+        )
+    }
+
+    pub fn pipeline(
+        &self,
+        message: Result<Vec<u8>, Error>,
+        items: VecDeque<PipelineItem>,
+        syncs: usize,
+        abort_on_error: bool,
+    ) -> PipelineFuture {
+        let pending = PendingRequest(message.map(|m| {
+            (
+                RequestMessages::Single(FrontendMessage::Raw(m)),
+                self.0.idle.guard(),
+            )
+        }));
+        PipelineFuture::new(self.clone(), pending, items, syncs, abort_on_error)
     }
 
     pub fn query_portal(&self, portal: &Portal, rows: i32) -> QueryStream<Portal> {
@@ -213,7 +350,13 @@ impl Client {
             frontend::sync(buf);
             Ok(())
         });
-        QueryStream::new(self.clone(), pending, portal.clone())
+        QueryStream::new(
+            self.clone(),
+            pending,
+            portal.clone(),
+            None,
+            portal.result_formats().clone(),
+        )
     }
 
     pub fn copy_in<'a, S, I>(&self, statement: &Statement, params: I, stream: S) -> CopyInFuture<S>
@@ -226,19 +369,21 @@ impl Client {
         I::IntoIter: ExactSizeIterator,
     {
         let (mut sender, receiver) = mpsc::channel(1);
-        let pending = PendingRequest(self.excecute_message(statement, params).map(|data| {
-            match sender.start_send(CopyMessage::Message(data)) {
-                Ok(AsyncSink::Ready) => {}
-                _ => unreachable!("channel should have capacity"),
-            }
-            (
-                RequestMessages::CopyIn {
-                    receiver: CopyInReceiver::new(receiver),
-                    pending_message: None,
-                },
-                self.0.idle.guard(),
-            )
-        }));
+        let pending = PendingRequest(self.excecute_message(statement, params, &[], &[]).map(
+            |data| {
+                match sender.start_send(CopyMessage::Message(data)) {
+                    Ok(AsyncSink::Ready) => {}
+                    _ => unreachable!("channel should have capacity"),
+                }
+                (
+                    RequestMessages::Stream {
+                        receiver: Box::new(CopyInReceiver::new(receiver)),
+                        pending_message: None,
+                    },
+                    self.0.idle.guard(),
+                )
+            },
+        ));
         CopyInFuture::new(self.clone(), pending, statement.clone(), stream, sender)
     }
 
@@ -248,12 +393,42 @@ impl Client {
         I::IntoIter: ExactSizeIterator,
     {
         let pending = PendingRequest(
-            self.excecute_message(statement, params)
+            self.excecute_message(statement, params, &[], &[])
                 .map(|m| (RequestMessages::Single(m), self.0.idle.guard())),
         );
         CopyOutStream::new(self.clone(), pending, statement.clone())
     }
 
+    pub fn base_backup(&self, query: &str) -> BaseBackupStream {
+        let pending = self.pending(|buf| {
+            frontend::query(query, buf).map_err(Error::parse)?;
+            Ok(())
+        });
+
+        BaseBackupStream::new(self.clone(), pending)
+    }
+
+    pub fn copy_both_simple(&self, query: &str) -> CopyBothDuplex {
+        let (mut sender, receiver) = mpsc::channel(1);
+        let mut buf = vec![];
+        let pending = PendingRequest(frontend::query(query, &mut buf).map_err(Error::encode).map(
+            |()| {
+                match sender.start_send(CopyMessage::Message(FrontendMessage::Raw(buf))) {
+                    Ok(AsyncSink::Ready) => {}
+                    _ => unreachable!("channel should have capacity"),
+                }
+                (
+                    RequestMessages::Stream {
+                        receiver: Box::new(CopyBothReceiver::new(receiver)),
+                        pending_message: None,
+                    },
+                    self.0.idle.guard(),
+                )
+            },
+        ));
+        CopyBothDuplex::new(self.clone(), pending, sender)
+    }
+
     pub fn close_statement(&self, name: &str) {
         self.close(b'S', name)
     }
@@ -299,44 +474,40 @@ impl Client {
             messages: RequestMessages::Single(FrontendMessage::Raw(buf)),
             sender,
             idle: None,
+            syncs: 1,
         });
     }
 
-    fn bind_message<'a, I>(
+    pub fn bind_message<'a, I>(
         &self,
         statement: &Statement,
         name: &str,
         params: I,
+        param_formats: &[Format],
+        result_formats: &[Format],
     ) -> Result<Vec<u8>, Error>
     where
         I: IntoIterator<Item = &'a dyn ToSql>,
         I::IntoIter: ExactSizeIterator,
     {
         let params = params.into_iter();
-
-        assert!(
-            statement.params().len() == params.len(),
-            "expected {} parameters but got {}",
-            statement.params().len(),
-            params.len()
-        );
+        check_formats(statement, params.len(), param_formats, result_formats);
 
         let mut buf = vec![];
         let mut error_idx = 0;
         let r = frontend::bind(
             name,
             statement.name(),
-            Some(1),
+            format_codes(param_formats),
             params.zip(statement.params()).enumerate(),
-            |(idx, (param, ty)), buf| match param.to_sql_checked(ty, buf) {
-                Ok(IsNull::No) => Ok(postgres_protocol::IsNull::No),
-                Ok(IsNull::Yes) => Ok(postgres_protocol::IsNull::Yes),
-                Err(e) => {
+            |(idx, (param, ty)), buf| {
+                let r = encode_param(param, ty, Format::at(param_formats, idx), buf);
+                if r.is_err() {
                     error_idx = idx;
-                    Err(e)
                 }
+                r
             },
-            Some(1),
+            format_codes(result_formats),
             &mut buf,
         );
         match r {
@@ -350,17 +521,59 @@ impl Client {
         &self,
         statement: &Statement,
         params: I,
+        param_formats: &[Format],
+        result_formats: &[Format],
     ) -> Result<FrontendMessage, Error>
     where
         I: IntoIterator<Item = &'a dyn ToSql>,
         I::IntoIter: ExactSizeIterator,
     {
-        let mut buf = self.bind_message(statement, "", params)?;
+        let mut buf = self.bind_message(statement, "", params, param_formats, result_formats)?;
         frontend::execute("", 0, &mut buf).map_err(Error::parse)?;
         frontend::sync(&mut buf);
         Ok(FrontendMessage::Raw(buf))
     }
 
+    fn retryable_execute_message<'a, I>(
+        &self,
+        statement: &Statement,
+        params: I,
+        param_formats: &[Format],
+        result_formats: &[Format],
+    ) -> (PendingRequest, Option<Retry>)
+    where
+        I: IntoIterator<Item = &'a dyn ToSql>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        if statement.cache_key().is_none() {
+            let message = self.excecute_message(statement, params, param_formats, result_formats);
+            let pending =
+                PendingRequest(message.map(|m| (RequestMessages::Single(m), self.0.idle.guard())));
+            return (pending, None);
+        }
+
+        // the parameters are encoded up front so that the request can be encoded again for a re-prepared statement
+        let values = match encode_params(statement, params, param_formats, result_formats) {
+            Ok(values) => values,
+            Err(e) => return (PendingRequest(Err(e)), None),
+        };
+        let retry = Retry::new(
+            self.clone(),
+            statement.clone(),
+            values,
+            format_codes(param_formats),
+            format_codes(result_formats),
+        );
+        let pending = PendingRequest(retry.message(statement.name()).map(|m| {
+            (
+                RequestMessages::Single(FrontendMessage::Raw(m)),
+                self.0.idle.guard(),
+            )
+        }));
+
+        (pending, Some(retry))
+    }
+
     fn pending<F>(&self, messages: F) -> PendingRequest
     where
         F: FnOnce(&mut Vec<u8>) -> Result<(), Error>,
@@ -374,3 +587,81 @@ impl Client {
         }))
     }
 }
+
+fn check_formats(
+    statement: &Statement,
+    params: usize,
+    param_formats: &[Format],
+    result_formats: &[Format],
+) {
+    assert!(
+        statement.params().len() == params,
+        "expected {} parameters but got {}",
+        statement.params().len(),
+        params
+    );
+    assert!(
+        param_formats.len() <= 1 || param_formats.len() == params,
+        "expected {} parameter formats but got {}",
+        params,
+        param_formats.len()
+    );
+    assert!(
+        result_formats.len() <= 1 || result_formats.len() == statement.columns().len(),
+        "expected {} result formats but got {}",
+        statement.columns().len(),
+        result_formats.len()
+    );
+}
+
+fn encode_param(
+    param: &dyn ToSql,
+    ty: &Type,
+    format: Format,
+    buf: &mut Vec<u8>,
+) -> Result<postgres_protocol::IsNull, Box<dyn StdError + Sync + Send>> {
+    // text format parameters are sent as strings and parsed by the server
+    let r = match format {
+        Format::Text => param.to_sql_checked(&Type::TEXT, buf),
+        Format::Binary => param.to_sql_checked(ty, buf),
+    };
+    match r? {
+        IsNull::No => Ok(postgres_protocol::IsNull::No),
+        IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+    }
+}
+
+fn encode_params<'a, I>(
+    statement: &Statement,
+    params: I,
+    param_formats: &[Format],
+    result_formats: &[Format],
+) -> Result<Vec<Option<Vec<u8>>>, Error>
+where
+    I: IntoIterator<Item = &'a dyn ToSql>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let params = params.into_iter();
+    check_formats(statement, params.len(), param_formats, result_formats);
+
+    params
+        .zip(statement.params())
+        .enumerate()
+        .map(|(idx, (param, ty))| {
+            let mut buf = vec![];
+            match encode_param(param, ty, Format::at(param_formats, idx), &mut buf) {
+                Ok(postgres_protocol::IsNull::No) => Ok(Some(buf)),
+                Ok(postgres_protocol::IsNull::Yes) => Ok(None),
+                Err(e) => Err(Error::to_sql(e, idx)),
+            }
+        })
+        .collect()
+}
+
+fn format_codes(formats: &[Format]) -> Vec<i16> {
+    if formats.is_empty() {
+        vec![Format::Binary.code()]
+    } else {
+        formats.iter().map(|f| f.code()).collect()
+    }
+}
@@ -1,7 +1,7 @@
 use futures::{Async, Future, Poll};
 use state_machine_future::{transition, RentToOwn, StateMachineFuture};
 
-use crate::config::Host;
+use crate::config::{Host, SslMode};
 use crate::proto::{Client, ConnectOnceFuture, Connection, MaybeTlsStream};
 use crate::{Config, Error, MakeTlsConnect, Socket};
 
@@ -19,6 +19,7 @@ where
     Connecting {
         future: ConnectOnceFuture<T::TlsConnect>,
         idx: usize,
+        tls_retry: bool,
         tls: T,
         config: Config,
     },
@@ -45,20 +46,12 @@ where
             return Err(Error::config("invalid number of ports".into()));
         }
 
-        let hostname = match &config.0.host[0] {
-            Host::Tcp(host) => &**host,
-            // postgres doesn't support TLS over unix sockets, so the choice here doesn't matter
-            #[cfg(unix)]
-            Host::Unix(_) => "",
-        };
-        let tls = state
-            .tls
-            .make_tls_connect(hostname)
-            .map_err(|e| Error::tls(e.into()))?;
+        let tls = make_tls_connect(&mut state.tls, &config.0.host[0])?;
 
         transition!(Connecting {
             future: ConnectOnceFuture::new(0, tls, config.clone()),
             idx: 0,
+            tls_retry: false,
             tls: state.tls,
             config,
         })
@@ -73,23 +66,42 @@ where
                 Ok(Async::NotReady) => return Ok(Async::NotReady),
                 Err(e) => {
                     let state = &mut **state;
+
+                    // with `allow`, a session the server rejected without TLS is retried with it
+                    if state.config.0.ssl_mode == SslMode::Allow
+                        && !state.tls_retry
+                        && e.code().is_some()
+                    {
+                        let tls =
+                            make_tls_connect(&mut state.tls, &state.config.0.host[state.idx])?;
+                        let mut config = state.config.clone();
+                        config.ssl_mode(SslMode::Require);
+                        state.tls_retry = true;
+                        state.future = ConnectOnceFuture::new(state.idx, tls, config);
+                        continue;
+                    }
+
+                    // with `prefer`, a session whose TLS handshake failed is retried without it
+                    if state.config.0.ssl_mode == SslMode::Prefer && !state.tls_retry && e.is_tls()
+                    {
+                        let tls =
+                            make_tls_connect(&mut state.tls, &state.config.0.host[state.idx])?;
+                        let mut config = state.config.clone();
+                        config.ssl_mode(SslMode::Disable);
+                        state.tls_retry = true;
+                        state.future = ConnectOnceFuture::new(state.idx, tls, config);
+                        continue;
+                    }
+
                     state.idx += 1;
+                    state.tls_retry = false;
 
                     let host = match state.config.0.host.get(state.idx) {
                         Some(host) => host,
                         None => return Err(e),
                     };
 
-                    let hostname = match host {
-                        Host::Tcp(host) => &**host,
-                        #[cfg(unix)]
-                        Host::Unix(_) => "",
-                    };
-                    let tls = state
-                        .tls
-                        .make_tls_connect(hostname)
-                        .map_err(|e| Error::tls(e.into()))?;
-
+                    let tls = make_tls_connect(&mut state.tls, host)?;
                     state.future = ConnectOnceFuture::new(state.idx, tls, state.config.clone());
                 }
             }
@@ -97,6 +109,20 @@ where
     }
 }
 
+fn make_tls_connect<T>(tls: &mut T, host: &Host) -> Result<T::TlsConnect, Error>
+where
+    T: MakeTlsConnect<Socket>,
+{
+    let hostname = match host {
+        Host::Tcp(host) => &**host,
+        // postgres doesn't support TLS over unix sockets, so the choice here doesn't matter
+        #[cfg(unix)]
+        Host::Unix(_) => "",
+    };
+    tls.make_tls_connect(hostname)
+        .map_err(|e| Error::tls(e.into()))
+}
+
 impl<T> ConnectFuture<T>
 where
     T: MakeTlsConnect<Socket>,
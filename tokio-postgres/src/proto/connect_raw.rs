@@ -7,12 +7,15 @@ use postgres_protocol::authentication::sasl::{self, ScramSha256};
 use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
 use state_machine_future::{transition, RentToOwn, StateMachineFuture};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io;
 use tokio_codec::Framed;
 use tokio_io::{AsyncRead, AsyncWrite};
 
+use crate::config::ReplicationMode;
 use crate::proto::codec::{BackendMessage, BackendMessages};
+use crate::proto::passfile;
 use crate::proto::{Client, Connection, FrontendMessage, MaybeTlsStream, PostgresCodec, TlsFuture};
 use crate::tls::ChannelBinding;
 use crate::{Config, Error, TlsConnect};
@@ -155,6 +158,13 @@ where
         if let Some(application_name) = &state.config.0.application_name {
             params.push(("application_name", &**application_name));
         }
+        if let Some(replication_mode) = &state.config.0.replication_mode {
+            match replication_mode {
+                ReplicationMode::Physical => params.push(("replication", "true")),
+                ReplicationMode::Logical => params.push(("replication", "database")),
+                ReplicationMode::__NonExhaustive => unreachable!(),
+            }
+        }
 
         let mut buf = vec![];
         frontend::startup_message(params, &mut buf).map_err(Error::encode)?;
@@ -202,14 +212,9 @@ where
                 idx: state.idx,
             }),
             Some(Message::AuthenticationCleartextPassword) => {
-                let pass = state
-                    .config
-                    .0
-                    .password
-                    .as_ref()
-                    .ok_or_else(|| Error::config("password missing".into()))?;
+                let pass = password(&state.config, state.idx)?;
                 let mut buf = vec![];
-                frontend::password_message(pass, &mut buf).map_err(Error::encode)?;
+                frontend::password_message(&pass, &mut buf).map_err(Error::encode)?;
                 transition!(SendingPassword {
                     future: state.stream.send(FrontendMessage::Raw(buf)),
                     config: state.config,
@@ -223,13 +228,8 @@ where
                     .user
                     .as_ref()
                     .ok_or_else(|| Error::config("user missing".into()))?;
-                let pass = state
-                    .config
-                    .0
-                    .password
-                    .as_ref()
-                    .ok_or_else(|| Error::config("password missing".into()))?;
-                let output = authentication::md5_hash(user.as_bytes(), pass, body.salt());
+                let pass = password(&state.config, state.idx)?;
+                let output = authentication::md5_hash(user.as_bytes(), &pass, body.salt());
                 let mut buf = vec![];
                 frontend::password_message(output.as_bytes(), &mut buf).map_err(Error::encode)?;
                 transition!(SendingPassword {
@@ -239,12 +239,7 @@ where
                 })
             }
             Some(Message::AuthenticationSasl(body)) => {
-                let pass = state
-                    .config
-                    .0
-                    .password
-                    .as_ref()
-                    .ok_or_else(|| Error::config("password missing".into()))?;
+                let pass = password(&state.config, state.idx)?;
 
                 let mut has_scram = false;
                 let mut has_scram_plus = false;
@@ -283,7 +278,7 @@ where
                     ));
                 };
 
-                let scram = ScramSha256::new(pass, channel_binding);
+                let scram = ScramSha256::new(&pass, channel_binding);
 
                 let mut buf = vec![];
                 frontend::sasl_initial_response(mechanism, scram.message(), &mut buf)
@@ -440,3 +435,14 @@ where
         ConnectRaw::start(TlsFuture::new(stream, config.0.ssl_mode, tls), config, idx)
     }
 }
+
+// The password is only looked up in the password file once the server asks for one, as in libpq.
+fn password(config: &Config, idx: Option<usize>) -> Result<Cow<'_, [u8]>, Error> {
+    if let Some(password) = &config.0.password {
+        return Ok(Cow::Borrowed(password));
+    }
+
+    passfile::find(config, idx)
+        .map(Cow::Owned)
+        .ok_or_else(|| Error::config("password missing".into()))
+}
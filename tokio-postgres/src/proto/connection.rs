@@ -10,15 +10,15 @@ use tokio_codec::Framed;
 use tokio_io::{AsyncRead, AsyncWrite};
 
 use crate::proto::codec::{BackendMessage, BackendMessages, FrontendMessage, PostgresCodec};
-use crate::proto::copy_in::CopyInReceiver;
 use crate::proto::idle::IdleGuard;
 use crate::{AsyncMessage, Notification};
 use crate::{DbError, Error};
 
 pub enum RequestMessages {
     Single(FrontendMessage),
-    CopyIn {
-        receiver: CopyInReceiver,
+    // used by copy_in and copy_both to stream messages to the server until the receiver is exhausted
+    Stream {
+        receiver: Box<dyn Stream<Item = FrontendMessage, Error = ()> + Send>,
         pending_message: Option<FrontendMessage>,
     },
 }
@@ -27,10 +27,13 @@ pub struct Request {
     pub messages: RequestMessages,
     pub sender: mpsc::Sender<BackendMessages>,
     pub idle: Option<IdleGuard>,
+    // the number of Sync messages in the request, each of which is answered by a ReadyForQuery
+    pub syncs: usize,
 }
 
 struct Response {
     sender: mpsc::Sender<BackendMessages>,
+    syncs: usize,
     _idle: Option<IdleGuard>,
 }
 
@@ -141,7 +144,10 @@ where
                 // if the receiver's hung up we still need to page through the rest of the messages
                 // designated to it
                 Ok(AsyncSink::Ready) | Err(_) => {
-                    if !request_complete {
+                    if request_complete {
+                        response.syncs -= 1;
+                    }
+                    if response.syncs > 0 {
                         self.responses.push_front(response);
                     }
                 }
@@ -169,6 +175,7 @@ where
                 trace!("polled new request");
                 self.responses.push_back(Response {
                     sender: request.sender,
+                    syncs: request.syncs,
                     _idle: request.idle,
                 });
                 Ok(Async::Ready(Some(request.messages)))
@@ -224,7 +231,7 @@ where
                         }
                     }
                 }
-                RequestMessages::CopyIn {
+                RequestMessages::Stream {
                     mut receiver,
                     mut pending_message,
                 } => {
@@ -233,31 +240,31 @@ where
                         None => match receiver.poll() {
                             Ok(Async::Ready(Some(message))) => message,
                             Ok(Async::Ready(None)) => {
-                                trace!("poll_write: finished copy_in request");
+                                trace!("poll_write: finished stream request");
                                 continue;
                             }
                             Ok(Async::NotReady) => {
-                                trace!("poll_write: waiting on copy_in stream");
-                                self.pending_request = Some(RequestMessages::CopyIn {
+                                trace!("poll_write: waiting on request stream");
+                                self.pending_request = Some(RequestMessages::Stream {
                                     receiver,
                                     pending_message,
                                 });
                                 return Ok(true);
                             }
-                            Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+                            Err(()) => unreachable!("request streams don't return errors"),
                         },
                     };
 
                     match self.stream.start_send(message).map_err(Error::io)? {
                         AsyncSink::Ready => {
-                            self.pending_request = Some(RequestMessages::CopyIn {
+                            self.pending_request = Some(RequestMessages::Stream {
                                 receiver,
                                 pending_message: None,
                             });
                         }
                         AsyncSink::NotReady(message) => {
                             trace!("poll_write: waiting on socket");
-                            self.pending_request = Some(RequestMessages::CopyIn {
+                            self.pending_request = Some(RequestMessages::Stream {
                                 receiver,
                                 pending_message: Some(message),
                             });
@@ -0,0 +1,291 @@
+use bytes::{Buf, Bytes, IntoBuf};
+use futures::sync::mpsc;
+use futures::{try_ready, Async, AsyncSink, Poll, Sink, StartSend, Stream};
+use postgres_protocol::message::backend::Message;
+use postgres_protocol::message::frontend::{self, CopyData};
+use std::mem;
+use std::sync::Arc;
+
+use crate::proto::client::{Client, PendingRequest};
+use crate::proto::codec::FrontendMessage;
+use crate::proto::copy_in::CopyMessage;
+use crate::proto::responses::Responses;
+use crate::proto::simple_query::columns_from_description;
+use crate::{Error, SimpleColumn, SimpleQueryRow};
+
+pub struct CopyBothReceiver {
+    receiver: mpsc::Receiver<CopyMessage>,
+    done: bool,
+}
+
+impl CopyBothReceiver {
+    pub fn new(receiver: mpsc::Receiver<CopyMessage>) -> CopyBothReceiver {
+        CopyBothReceiver {
+            receiver,
+            done: false,
+        }
+    }
+}
+
+impl Stream for CopyBothReceiver {
+    type Item = FrontendMessage;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<FrontendMessage>, ()> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        match self.receiver.poll()? {
+            Async::Ready(Some(CopyMessage::Message(message))) => Ok(Async::Ready(Some(message))),
+            // copy both mode is only entered via the simple query protocol, so there's no Sync to send. The server
+            // terminates replication connections on CopyFail, so an abandoned copy is ended with CopyDone as well.
+            Async::Ready(Some(CopyMessage::Done)) | Async::Ready(None) => {
+                self.done = true;
+                let mut buf = vec![];
+                frontend::copy_done(&mut buf);
+                Ok(Async::Ready(Some(FrontendMessage::Raw(buf))))
+            }
+            Async::NotReady => Ok(Async::NotReady),
+        }
+    }
+}
+
+enum State {
+    Start {
+        client: Client,
+        request: PendingRequest,
+    },
+    ReadingCopyBothResponse {
+        receiver: Responses,
+    },
+    ReadingCopyData {
+        receiver: Responses,
+    },
+    EndingCopy {
+        receiver: Responses,
+    },
+    ReadingResults {
+        columns: Option<Arc<[SimpleColumn]>>,
+        receiver: Responses,
+    },
+    Done,
+}
+
+pub struct CopyBothDuplex {
+    state: State,
+    sender: mpsc::Sender<CopyMessage>,
+    closing: bool,
+    rows: Vec<SimpleQueryRow>,
+}
+
+impl CopyBothDuplex {
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        sender: mpsc::Sender<CopyMessage>,
+    ) -> CopyBothDuplex {
+        CopyBothDuplex {
+            state: State::Start { client, request },
+            sender,
+            closing: false,
+            rows: vec![],
+        }
+    }
+
+    // The rows of any result set sent by the server after the copy, such as the next timeline at the end of a physical
+    // replication stream.
+    pub fn rows(&self) -> &[SimpleQueryRow] {
+        &self.rows
+    }
+
+    // The request is sent on first use of either half so that data written before the stream is polled isn't stuck
+    // behind it.
+    fn start(&mut self) -> Result<(), Error> {
+        if let State::Start { .. } = self.state {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Start { client, request } => {
+                    let receiver = client.send(request)?;
+                    self.state = State::ReadingCopyBothResponse { receiver };
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Stream for CopyBothDuplex {
+    type Item = Bytes;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Bytes>, Error> {
+        self.start()?;
+
+        loop {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Start { .. } => unreachable!(),
+                State::ReadingCopyBothResponse { mut receiver } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::ReadingCopyBothResponse { receiver };
+                            break Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    match message {
+                        Some(Message::CopyBothResponse(_)) => {
+                            self.state = State::ReadingCopyData { receiver };
+                        }
+                        Some(Message::ErrorResponse(body)) => break Err(Error::db(body)),
+                        Some(_) => break Err(Error::unexpected_message()),
+                        None => break Err(Error::closed()),
+                    }
+                }
+                State::ReadingCopyData { mut receiver } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::ReadingCopyData { receiver };
+                            break Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    match message {
+                        Some(Message::CopyData(body)) => {
+                            self.state = State::ReadingCopyData { receiver };
+                            break Ok(Async::Ready(Some(body.into_bytes())));
+                        }
+                        Some(Message::CopyDone) => {
+                            self.state = State::EndingCopy { receiver };
+                        }
+                        Some(Message::ReadyForQuery(_)) => break Ok(Async::Ready(None)),
+                        Some(Message::ErrorResponse(body)) => break Err(Error::db(body)),
+                        Some(_) => break Err(Error::unexpected_message()),
+                        None => break Err(Error::closed()),
+                    }
+                }
+                // the server won't complete the command until the client's half of the copy has ended as well
+                State::EndingCopy { receiver } => {
+                    if !self.closing {
+                        match self.sender.poll_ready() {
+                            Ok(Async::Ready(())) => {}
+                            Ok(Async::NotReady) => {
+                                self.state = State::EndingCopy { receiver };
+                                break Ok(Async::NotReady);
+                            }
+                            Err(_) => break Err(Error::closed()),
+                        }
+                        match self.sender.start_send(CopyMessage::Done) {
+                            Ok(AsyncSink::Ready) => self.closing = true,
+                            Ok(AsyncSink::NotReady(_)) => {
+                                unreachable!("channel should have capacity")
+                            }
+                            Err(_) => break Err(Error::closed()),
+                        }
+                    }
+                    if self.sender.poll_complete().is_err() {
+                        break Err(Error::closed());
+                    }
+
+                    self.state = State::ReadingResults {
+                        columns: None,
+                        receiver,
+                    };
+                }
+                State::ReadingResults {
+                    columns,
+                    mut receiver,
+                } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::ReadingResults { columns, receiver };
+                            break Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    match message {
+                        Some(Message::RowDescription(body)) => {
+                            let columns = Some(columns_from_description(&body)?);
+                            self.state = State::ReadingResults { columns, receiver };
+                        }
+                        Some(Message::DataRow(body)) => {
+                            let row = match &columns {
+                                Some(columns) => SimpleQueryRow::new(columns.clone(), body)?,
+                                None => break Err(Error::unexpected_message()),
+                            };
+                            self.rows.push(row);
+                            self.state = State::ReadingResults { columns, receiver };
+                        }
+                        Some(Message::CommandComplete(_)) => {
+                            self.state = State::ReadingResults { columns, receiver };
+                        }
+                        Some(Message::ReadyForQuery(_)) => break Ok(Async::Ready(None)),
+                        Some(Message::ErrorResponse(body)) => break Err(Error::db(body)),
+                        Some(_) => break Err(Error::unexpected_message()),
+                        None => break Err(Error::closed()),
+                    }
+                }
+                State::Done => break Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+impl Sink for CopyBothDuplex {
+    type SinkItem = Bytes;
+    type SinkError = Error;
+
+    fn start_send(&mut self, item: Bytes) -> StartSend<Bytes, Error> {
+        self.start()?;
+
+        if self.closing {
+            return Err(Error::closed());
+        }
+
+        if self
+            .sender
+            .poll_ready()
+            .map_err(|_| Error::closed())?
+            .is_not_ready()
+        {
+            return Ok(AsyncSink::NotReady(item));
+        }
+
+        let buf: Box<dyn Buf + Send> = Box::new(item.into_buf());
+        let data = CopyData::new(buf).map_err(Error::encode)?;
+        match self
+            .sender
+            .start_send(CopyMessage::Message(FrontendMessage::CopyData(data)))
+        {
+            Ok(AsyncSink::Ready) => Ok(AsyncSink::Ready),
+            Ok(AsyncSink::NotReady(_)) => unreachable!("channel should have capacity"),
+            Err(_) => Err(Error::closed()),
+        }
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), Error> {
+        self.sender.poll_complete().map_err(|_| Error::closed())
+    }
+
+    fn close(&mut self) -> Poll<(), Error> {
+        self.start()?;
+
+        if !self.closing {
+            try_ready!(self.sender.poll_ready().map_err(|_| Error::closed()));
+            match self.sender.start_send(CopyMessage::Done) {
+                Ok(AsyncSink::Ready) => self.closing = true,
+                Ok(AsyncSink::NotReady(_)) => unreachable!("channel should have capacity"),
+                Err(_) => return Err(Error::closed()),
+            }
+        }
+
+        self.poll_complete()
+    }
+}
@@ -1,10 +1,11 @@
-use futures::{try_ready, Poll, Stream};
+use futures::{try_ready, Future, Poll, Stream};
 use postgres_protocol::message::backend::Message;
 use state_machine_future::{transition, RentToOwn, StateMachineFuture};
 
 use crate::proto::client::{Client, PendingRequest};
 use crate::proto::responses::Responses;
 use crate::proto::statement::Statement;
+use crate::proto::statement_cache::{Retry, RetryFuture};
 use crate::Error;
 
 #[derive(StateMachineFuture)]
@@ -14,9 +15,15 @@ pub enum Execute {
         client: Client,
         request: PendingRequest,
         statement: Statement,
+        retry: Option<Retry>,
     },
-    #[state_machine_future(transitions(Finished))]
-    ReadResponse { receiver: Responses },
+    #[state_machine_future(transitions(Retrying, Finished))]
+    ReadResponse {
+        receiver: Responses,
+        retry: Option<Retry>,
+    },
+    #[state_machine_future(transitions(ReadResponse))]
+    Retrying { future: RetryFuture },
     #[state_machine_future(ready)]
     Finished(u64),
     #[state_machine_future(error)]
@@ -29,7 +36,10 @@ impl PollExecute for Execute {
         let receiver = state.client.send(state.request)?;
 
         // the statement can drop after this point, since its close will queue up after the execution
-        transition!(ReadResponse { receiver })
+        transition!(ReadResponse {
+            receiver,
+            retry: state.retry,
+        })
     }
 
     fn poll_read_response<'a>(
@@ -40,8 +50,17 @@ impl PollExecute for Execute {
 
             match message {
                 Some(Message::BindComplete) => {}
-                Some(Message::DataRow(_)) => {}
-                Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                Some(Message::DataRow(_)) => state.retry = None,
+                Some(Message::ErrorResponse(body)) => {
+                    let error = Error::db(body);
+                    let state = state.take();
+                    match state.retry {
+                        Some(retry) => transition!(Retrying {
+                            future: retry.start(error, state.receiver)?,
+                        }),
+                        None => return Err(error),
+                    }
+                }
                 Some(Message::CommandComplete(body)) => {
                     let rows = body
                         .tag()
@@ -59,10 +78,24 @@ impl PollExecute for Execute {
             }
         }
     }
+
+    fn poll_retrying<'a>(state: &'a mut RentToOwn<'a, Retrying>) -> Poll<AfterRetrying, Error> {
+        let (receiver, _) = try_ready!(state.future.poll());
+
+        transition!(ReadResponse {
+            receiver,
+            retry: None,
+        })
+    }
 }
 
 impl ExecuteFuture {
-    pub fn new(client: Client, request: PendingRequest, statement: Statement) -> ExecuteFuture {
-        Execute::start(client, request, statement)
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        statement: Statement,
+        retry: Option<Retry>,
+    ) -> ExecuteFuture {
+        Execute::start(client, request, statement, retry)
     }
 }
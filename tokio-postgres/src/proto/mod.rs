@@ -8,6 +8,7 @@ macro_rules! try_ready_closed {
     };
 }
 
+mod base_backup;
 mod bind;
 #[cfg(feature = "runtime")]
 mod cancel_query;
@@ -22,23 +23,28 @@ mod connect_raw;
 #[cfg(feature = "runtime")]
 mod connect_socket;
 mod connection;
+mod copy_both;
 mod copy_in;
 mod copy_out;
 mod execute;
 mod idle;
 mod maybe_tls_stream;
+mod passfile;
+mod pipeline;
 mod portal;
 mod prepare;
 mod query;
 mod responses;
 mod simple_query;
 mod statement;
+mod statement_cache;
 mod tls;
 mod transaction;
 mod typeinfo;
 mod typeinfo_composite;
 mod typeinfo_enum;
 
+pub use crate::proto::base_backup::BaseBackupStream;
 pub use crate::proto::bind::BindFuture;
 #[cfg(feature = "runtime")]
 pub use crate::proto::cancel_query::CancelQueryFuture;
@@ -53,14 +59,16 @@ pub use crate::proto::connect_raw::ConnectRawFuture;
 #[cfg(feature = "runtime")]
 pub use crate::proto::connect_socket::ConnectSocketFuture;
 pub use crate::proto::connection::Connection;
+pub use crate::proto::copy_both::CopyBothDuplex;
 pub use crate::proto::copy_in::CopyInFuture;
 pub use crate::proto::copy_out::CopyOutStream;
 pub use crate::proto::execute::ExecuteFuture;
 pub use crate::proto::maybe_tls_stream::MaybeTlsStream;
+pub use crate::proto::pipeline::{PipelineFuture, PipelineItem};
 pub use crate::proto::portal::Portal;
-pub use crate::proto::prepare::PrepareFuture;
 pub use crate::proto::query::QueryStream;
 pub use crate::proto::simple_query::SimpleQueryStream;
 pub use crate::proto::statement::Statement;
+pub use crate::proto::statement_cache::CachedPrepareFuture;
 pub use crate::proto::tls::TlsFuture;
 pub use crate::proto::transaction::TransactionFuture;
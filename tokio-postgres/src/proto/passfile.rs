@@ -0,0 +1,147 @@
+use log::warn;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::config::Host;
+use crate::Config;
+
+/// Looks up the password for a connection to the host at `idx` in the password file.
+pub fn find(config: &Config, idx: Option<usize>) -> Option<Vec<u8>> {
+    let user = config.0.user.as_ref()?;
+    let path = path(config)?;
+
+    let idx = idx.unwrap_or(0);
+    let host = match config.0.host.get(idx) {
+        Some(Host::Tcp(host)) => host,
+        // libpq matches Unix socket connections against `localhost`
+        _ => "localhost",
+    };
+    let port = config
+        .0
+        .port
+        .get(idx)
+        .or_else(|| config.0.port.first())
+        .cloned()
+        .unwrap_or(5432)
+        .to_string();
+    let dbname = config.0.dbname.as_ref().unwrap_or(user);
+
+    let file = match open(&path) {
+        Ok(Some(file)) => file,
+        Ok(None) => return None,
+        Err(e) => {
+            if e.kind() != io::ErrorKind::NotFound {
+                warn!("could not open password file {}: {}", path.display(), e);
+            }
+            return None;
+        }
+    };
+
+    for line in BufReader::new(file).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("could not read password file {}: {}", path.display(), e);
+                return None;
+            }
+        };
+
+        if let Some(password) = matches(&line, &[host, &port, dbname, user]) {
+            return Some(password.into_bytes());
+        }
+    }
+
+    None
+}
+
+fn path(config: &Config) -> Option<PathBuf> {
+    if let Some(path) = &config.0.passfile {
+        return Some(path.clone());
+    }
+    if let Some(path) = env::var_os("PGPASSFILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    #[cfg(windows)]
+    let path =
+        env::var_os("APPDATA").map(|p| PathBuf::from(p).join("postgresql").join("pgpass.conf"));
+    #[cfg(not(windows))]
+    let path = env::var_os("HOME").map(|p| PathBuf::from(p).join(".pgpass"));
+    path
+}
+
+#[cfg(unix)]
+fn open(path: &Path) -> io::Result<Option<File>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = fs::metadata(path)?;
+    if !metadata.is_file() {
+        warn!("password file {} is not a plain file", path.display());
+        return Ok(None);
+    }
+    if metadata.permissions().mode() & 0o077 != 0 {
+        warn!(
+            "password file {} has group or world access; permissions should be u=rw (0600) or less",
+            path.display()
+        );
+        return Ok(None);
+    }
+
+    File::open(path).map(Some)
+}
+
+#[cfg(not(unix))]
+fn open(path: &Path) -> io::Result<Option<File>> {
+    if !fs::metadata(path)?.is_file() {
+        warn!("password file {} is not a plain file", path.display());
+        return Ok(None);
+    }
+
+    File::open(path).map(Some)
+}
+
+// Matches a `hostname:port:database:username:password` line, returning the password. Each of the first four fields
+// can be `*` to match anything, and `:` and `\` characters are escaped with a `\`.
+fn matches(line: &str, values: &[&str]) -> Option<String> {
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let mut chars = line.chars();
+    for value in values {
+        let mut raw = String::new();
+        let mut field = String::new();
+        loop {
+            match chars.next()? {
+                ':' => break,
+                '\\' => {
+                    raw.push('\\');
+                    if let Some(c) = chars.next() {
+                        raw.push(c);
+                        field.push(c);
+                    }
+                }
+                c => {
+                    raw.push(c);
+                    field.push(c);
+                }
+            }
+        }
+
+        if raw != "*" && field != *value {
+            return None;
+        }
+    }
+
+    let mut password = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => password.extend(chars.next()),
+            c => password.push(c),
+        }
+    }
+
+    Some(password)
+}
@@ -0,0 +1,152 @@
+use futures::{Async, Future, Poll, Stream};
+use postgres_protocol::message::backend::Message;
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+
+use crate::pipeline::PipelineResult;
+use crate::proto::client::{Client, PendingRequest};
+use crate::proto::responses::Responses;
+use crate::proto::statement::Statement;
+use crate::types::Format;
+use crate::{Error, Row};
+
+pub struct PipelineItem {
+    pub statement: Statement,
+    pub keep_rows: bool,
+}
+
+enum State {
+    Start {
+        client: Client,
+        request: PendingRequest,
+        syncs: usize,
+    },
+    ReadingResponse {
+        receiver: Responses,
+    },
+    Done,
+}
+
+pub struct PipelineFuture {
+    state: State,
+    items: VecDeque<PipelineItem>,
+    abort_on_error: bool,
+    formats: Arc<[Format]>,
+    rows: Vec<Row>,
+    results: Vec<Result<PipelineResult, Error>>,
+}
+
+impl Future for PipelineFuture {
+    type Item = Vec<Result<PipelineResult, Error>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Vec<Result<PipelineResult, Error>>, Error> {
+        loop {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Start {
+                    client,
+                    request,
+                    syncs,
+                } => {
+                    if self.items.is_empty() {
+                        return Ok(Async::Ready(vec![]));
+                    }
+
+                    let receiver = client.send_with_syncs(request, syncs)?;
+                    self.state = State::ReadingResponse { receiver };
+                }
+                State::ReadingResponse { mut receiver } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::ReadingResponse { receiver };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(e) => return Err(e),
+                    };
+
+                    match message {
+                        Some(Message::BindComplete) => {}
+                        Some(Message::DataRow(body)) => {
+                            let item = match self.items.front() {
+                                Some(item) => item,
+                                None => return Err(Error::unexpected_message()),
+                            };
+                            if item.keep_rows {
+                                let row =
+                                    Row::new(item.statement.clone(), body, self.formats.clone())?;
+                                self.rows.push(row);
+                            }
+                        }
+                        Some(Message::CommandComplete(body)) => {
+                            let rows_affected = body
+                                .tag()
+                                .map_err(Error::parse)?
+                                .rsplit(' ')
+                                .next()
+                                .unwrap()
+                                .parse()
+                                .unwrap_or(0);
+                            self.finish_item(Ok(rows_affected))?;
+                        }
+                        Some(Message::EmptyQueryResponse) => self.finish_item(Ok(0))?,
+                        Some(Message::ErrorResponse(body)) => {
+                            self.finish_item(Err(Error::db(body)))?;
+                            // the server skips everything up to the next Sync after an error
+                            if self.abort_on_error {
+                                while self.items.pop_front().is_some() {
+                                    self.results.push(Err(Error::pipeline_aborted()));
+                                }
+                            }
+                        }
+                        Some(Message::ReadyForQuery(_)) => {
+                            if self.items.is_empty() {
+                                return Ok(Async::Ready(mem::take(&mut self.results)));
+                            }
+                        }
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    }
+
+                    self.state = State::ReadingResponse { receiver };
+                }
+                State::Done => panic!("future polled after completion"),
+            }
+        }
+    }
+}
+
+impl PipelineFuture {
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        items: VecDeque<PipelineItem>,
+        syncs: usize,
+        abort_on_error: bool,
+    ) -> PipelineFuture {
+        PipelineFuture {
+            state: State::Start {
+                client,
+                request,
+                syncs,
+            },
+            items,
+            abort_on_error,
+            formats: Arc::new([]),
+            rows: vec![],
+            results: vec![],
+        }
+    }
+
+    fn finish_item(&mut self, result: Result<u64, Error>) -> Result<(), Error> {
+        if self.items.pop_front().is_none() {
+            return Err(Error::unexpected_message());
+        }
+
+        let rows = mem::take(&mut self.rows);
+        let result = result.map(|rows_affected| PipelineResult::new(rows, rows_affected));
+        self.results.push(result);
+        Ok(())
+    }
+}
@@ -2,11 +2,13 @@ use std::sync::Arc;
 
 use crate::proto::client::WeakClient;
 use crate::proto::statement::Statement;
+use crate::types::Format;
 
 struct Inner {
     client: WeakClient,
     name: String,
     statement: Statement,
+    result_formats: Arc<[Format]>,
 }
 
 impl Drop for Inner {
@@ -21,11 +23,17 @@ impl Drop for Inner {
 pub struct Portal(Arc<Inner>);
 
 impl Portal {
-    pub fn new(client: WeakClient, name: String, statement: Statement) -> Portal {
+    pub fn new(
+        client: WeakClient,
+        name: String,
+        statement: Statement,
+        result_formats: Arc<[Format]>,
+    ) -> Portal {
         Portal(Arc::new(Inner {
             client,
             name,
             statement,
+            result_formats,
         }))
     }
 
@@ -36,4 +44,8 @@ impl Portal {
     pub fn statement(&self) -> &Statement {
         &self.0.statement
     }
+
+    pub fn result_formats(&self) -> &Arc<[Format]> {
+        &self.0.result_formats
+    }
 }
@@ -10,6 +10,7 @@ use std::vec;
 use crate::proto::client::{Client, PendingRequest};
 use crate::proto::responses::Responses;
 use crate::proto::statement::Statement;
+use crate::proto::statement_cache::StatementKey;
 use crate::proto::typeinfo::TypeinfoFuture;
 use crate::types::{Oid, Type};
 use crate::{Column, Error};
@@ -21,24 +22,28 @@ pub enum Prepare {
         client: Client,
         request: PendingRequest,
         name: String,
+        cache_key: Option<StatementKey>,
     },
     #[state_machine_future(transitions(ReadParameterDescription))]
     ReadParseComplete {
         client: Client,
         receiver: Responses,
         name: String,
+        cache_key: Option<StatementKey>,
     },
     #[state_machine_future(transitions(ReadRowDescription))]
     ReadParameterDescription {
         client: Client,
         receiver: Responses,
         name: String,
+        cache_key: Option<StatementKey>,
     },
     #[state_machine_future(transitions(GetParameterTypes, GetColumnTypes, Finished))]
     ReadRowDescription {
         client: Client,
         receiver: Responses,
         name: String,
+        cache_key: Option<StatementKey>,
         parameters: Vec<Oid>,
     },
     #[state_machine_future(transitions(GetColumnTypes, Finished))]
@@ -46,6 +51,7 @@ pub enum Prepare {
         future: TypeinfoFuture,
         remaining_parameters: vec::IntoIter<Oid>,
         name: String,
+        cache_key: Option<StatementKey>,
         parameters: Vec<Type>,
         columns: Vec<(String, Oid)>,
     },
@@ -55,6 +61,7 @@ pub enum Prepare {
         cur_column_name: String,
         remaining_columns: vec::IntoIter<(String, Oid)>,
         name: String,
+        cache_key: Option<StatementKey>,
         parameters: Vec<Type>,
         columns: Vec<Column>,
     },
@@ -72,6 +79,7 @@ impl PollPrepare for Prepare {
         transition!(ReadParseComplete {
             receiver,
             name: state.name,
+            cache_key: state.cache_key,
             client: state.client,
         })
     }
@@ -86,6 +94,7 @@ impl PollPrepare for Prepare {
             Some(Message::ParseComplete) => transition!(ReadParameterDescription {
                 receiver: state.receiver,
                 name: state.name,
+                cache_key: state.cache_key,
                 client: state.client,
             }),
             Some(Message::ErrorResponse(body)) => Err(Error::db(body)),
@@ -104,6 +113,7 @@ impl PollPrepare for Prepare {
             Some(Message::ParameterDescription(body)) => transition!(ReadRowDescription {
                 receiver: state.receiver,
                 name: state.name,
+                cache_key: state.cache_key,
                 parameters: body.parameters().collect().map_err(Error::parse)?,
                 client: state.client,
             }),
@@ -135,6 +145,7 @@ impl PollPrepare for Prepare {
                 future: TypeinfoFuture::new(oid, state.client),
                 remaining_parameters: parameters,
                 name: state.name,
+                cache_key: state.cache_key,
                 parameters: vec![],
                 columns: columns,
             });
@@ -147,6 +158,7 @@ impl PollPrepare for Prepare {
                 cur_column_name: name,
                 remaining_columns: columns,
                 name: state.name,
+                cache_key: state.cache_key,
                 parameters: vec![],
                 columns: vec![],
             });
@@ -156,7 +168,8 @@ impl PollPrepare for Prepare {
             state.client.downgrade(),
             state.name,
             vec![],
-            vec![]
+            vec![],
+            state.cache_key,
         )))
     }
 
@@ -181,6 +194,7 @@ impl PollPrepare for Prepare {
                 cur_column_name: name,
                 remaining_columns: columns,
                 name: state.name,
+                cache_key: state.cache_key,
                 parameters: state.parameters,
                 columns: vec![],
             })
@@ -191,6 +205,7 @@ impl PollPrepare for Prepare {
             state.name,
             state.parameters,
             vec![],
+            state.cache_key,
         )))
     }
 
@@ -217,12 +232,18 @@ impl PollPrepare for Prepare {
             state.name,
             state.parameters,
             state.columns,
+            state.cache_key,
         )))
     }
 }
 
 impl PrepareFuture {
-    pub fn new(client: Client, request: PendingRequest, name: String) -> PrepareFuture {
-        Prepare::start(client, request, name)
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        name: String,
+        cache_key: Option<StatementKey>,
+    ) -> PrepareFuture {
+        Prepare::start(client, request, name, cache_key)
     }
 }
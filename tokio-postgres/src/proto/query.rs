@@ -1,11 +1,17 @@
-use futures::{Async, Poll, Stream};
-use postgres_protocol::message::backend::Message;
+use fallible_iterator::FallibleIterator;
+use futures::{try_ready, Async, Future, Poll, Stream};
+use postgres_protocol::message::backend::{DataRowBody, Message};
 use std::mem;
+use std::ops::Range;
+use std::sync::Arc;
 
 use crate::proto::client::{Client, PendingRequest};
 use crate::proto::portal::Portal;
 use crate::proto::responses::Responses;
 use crate::proto::statement::Statement;
+use crate::proto::statement_cache::{Retry, RetryFuture};
+use crate::row::RowRef;
+use crate::types::Format;
 use crate::{Error, Row};
 
 pub trait StatementHolder {
@@ -29,15 +35,26 @@ enum State<T> {
         client: Client,
         request: PendingRequest,
         statement: T,
+        retry: Option<Retry>,
     },
     ReadingResponse {
         receiver: Responses,
         statement: T,
+        retry: Option<Retry>,
+        retried: Option<Statement>,
+    },
+    Retrying {
+        future: RetryFuture,
+        statement: T,
     },
     Done,
 }
 
-pub struct QueryStream<T>(State<T>);
+pub struct QueryStream<T> {
+    state: State<T>,
+    result_formats: Arc<[Format]>,
+    ranges: Vec<Option<Range<usize>>>,
+}
 
 impl<T> Stream for QueryStream<T>
 where
@@ -47,29 +64,106 @@ where
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Option<Row>, Error> {
+        match try_ready!(self.poll_body()) {
+            Some(body) => {
+                let row = Row::new(self.statement().clone(), body, self.result_formats.clone())?;
+                Ok(Async::Ready(Some(row)))
+            }
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
+
+impl<T> QueryStream<T>
+where
+    T: StatementHolder,
+{
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        statement: T,
+        retry: Option<Retry>,
+        result_formats: Arc<[Format]>,
+    ) -> QueryStream<T> {
+        QueryStream {
+            state: State::Start {
+                client,
+                request,
+                statement,
+                retry,
+            },
+            result_formats,
+            ranges: vec![],
+        }
+    }
+
+    pub fn poll_row_ref<F, R>(&mut self, f: F) -> Poll<Option<R>, Error>
+    where
+        F: FnOnce(RowRef<'_>) -> Result<R, Error>,
+    {
+        let body = match try_ready!(self.poll_body()) {
+            Some(body) => body,
+            None => return Ok(Async::Ready(None)),
+        };
+
+        // the range buffer is reused across rows so that no allocation is required per row
+        self.ranges.clear();
+        let mut ranges = body.ranges();
+        while let Some(range) = ranges.next().map_err(Error::parse)? {
+            self.ranges.push(range);
+        }
+
+        let row = RowRef::new(
+            self.statement().columns(),
+            body.buffer(),
+            &self.ranges,
+            &self.result_formats,
+        );
+        f(row).map(|r| Async::Ready(Some(r)))
+    }
+
+    fn statement(&self) -> &Statement {
+        match self.state {
+            State::ReadingResponse {
+                retried: Some(ref retried),
+                ..
+            } => retried,
+            State::ReadingResponse { ref statement, .. } => statement.statement(),
+            _ => unreachable!("no row has been read"),
+        }
+    }
+
+    fn poll_body(&mut self) -> Poll<Option<DataRowBody>, Error> {
         loop {
-            match mem::replace(&mut self.0, State::Done) {
+            match mem::replace(&mut self.state, State::Done) {
                 State::Start {
                     client,
                     request,
                     statement,
+                    retry,
                 } => {
                     let receiver = client.send(request)?;
-                    self.0 = State::ReadingResponse {
+                    self.state = State::ReadingResponse {
                         receiver,
                         statement,
+                        retry,
+                        retried: None,
                     };
                 }
                 State::ReadingResponse {
                     mut receiver,
                     statement,
+                    retry,
+                    retried,
                 } => {
                     let message = match receiver.poll() {
                         Ok(Async::Ready(message)) => message,
                         Ok(Async::NotReady) => {
-                            self.0 = State::ReadingResponse {
+                            self.state = State::ReadingResponse {
                                 receiver,
                                 statement,
+                                retry,
+                                retried,
                             };
                             break Ok(Async::NotReady);
                         }
@@ -78,19 +172,33 @@ where
 
                     match message {
                         Some(Message::BindComplete) => {
-                            self.0 = State::ReadingResponse {
+                            self.state = State::ReadingResponse {
                                 receiver,
                                 statement,
+                                retry,
+                                retried,
                             };
                         }
-                        Some(Message::ErrorResponse(body)) => break Err(Error::db(body)),
+                        Some(Message::ErrorResponse(body)) => {
+                            let error = Error::db(body);
+                            match retry {
+                                Some(retry) => {
+                                    self.state = State::Retrying {
+                                        future: retry.start(error, receiver)?,
+                                        statement,
+                                    };
+                                }
+                                None => break Err(error),
+                            }
+                        }
                         Some(Message::DataRow(body)) => {
-                            let row = Row::new(statement.statement().clone(), body)?;
-                            self.0 = State::ReadingResponse {
+                            self.state = State::ReadingResponse {
                                 receiver,
                                 statement,
+                                retry: None,
+                                retried,
                             };
-                            break Ok(Async::Ready(Some(row)));
+                            break Ok(Async::Ready(Some(body)));
                         }
                         Some(Message::EmptyQueryResponse)
                         | Some(Message::PortalSuspended)
@@ -101,21 +209,25 @@ where
                         None => break Err(Error::closed()),
                     }
                 }
+                State::Retrying {
+                    mut future,
+                    statement,
+                } => match future.poll()? {
+                    Async::Ready((receiver, retried)) => {
+                        self.state = State::ReadingResponse {
+                            receiver,
+                            statement,
+                            retry: None,
+                            retried: Some(retried),
+                        };
+                    }
+                    Async::NotReady => {
+                        self.state = State::Retrying { future, statement };
+                        break Ok(Async::NotReady);
+                    }
+                },
                 State::Done => break Ok(Async::Ready(None)),
             }
         }
     }
 }
-
-impl<T> QueryStream<T>
-where
-    T: StatementHolder,
-{
-    pub fn new(client: Client, request: PendingRequest, statement: T) -> QueryStream<T> {
-        QueryStream(State::Start {
-            client,
-            request,
-            statement,
-        })
-    }
-}
@@ -1,11 +1,12 @@
 use fallible_iterator::FallibleIterator;
 use futures::{Async, Poll, Stream};
-use postgres_protocol::message::backend::Message;
+use postgres_protocol::message::backend::{Message, RowDescriptionBody};
 use std::mem;
 use std::sync::Arc;
 
 use crate::proto::client::{Client, PendingRequest};
 use crate::proto::responses::Responses;
+use crate::stmt::SimpleColumn;
 use crate::{Error, SimpleQueryMessage, SimpleQueryRow};
 
 pub enum State {
@@ -14,13 +15,16 @@ pub enum State {
         request: PendingRequest,
     },
     ReadResponse {
-        columns: Option<Arc<[String]>>,
+        columns: Option<Arc<[SimpleColumn]>>,
         receiver: Responses,
     },
     Done,
 }
 
-pub struct SimpleQueryStream(State);
+pub struct SimpleQueryStream {
+    state: State,
+    describe: bool,
+}
 
 impl Stream for SimpleQueryStream {
     type Item = SimpleQueryMessage;
@@ -28,10 +32,10 @@ impl Stream for SimpleQueryStream {
 
     fn poll(&mut self) -> Poll<Option<SimpleQueryMessage>, Error> {
         loop {
-            match mem::replace(&mut self.0, State::Done) {
+            match mem::replace(&mut self.state, State::Done) {
                 State::Start { client, request } => {
                     let receiver = client.send(request)?;
-                    self.0 = State::ReadResponse {
+                    self.state = State::ReadResponse {
                         columns: None,
                         receiver,
                     };
@@ -43,7 +47,7 @@ impl Stream for SimpleQueryStream {
                     let message = match receiver.poll() {
                         Ok(Async::Ready(message)) => message,
                         Ok(Async::NotReady) => {
-                            self.0 = State::ReadResponse { columns, receiver };
+                            self.state = State::ReadResponse { columns, receiver };
                             return Ok(Async::NotReady);
                         }
                         Err(e) => return Err(e),
@@ -59,7 +63,7 @@ impl Stream for SimpleQueryStream {
                                 .unwrap()
                                 .parse()
                                 .unwrap_or(0);
-                            self.0 = State::ReadResponse {
+                            self.state = State::ReadResponse {
                                 columns: None,
                                 receiver,
                             };
@@ -68,30 +72,30 @@ impl Stream for SimpleQueryStream {
                             ))));
                         }
                         Some(Message::EmptyQueryResponse) => {
-                            self.0 = State::ReadResponse {
+                            self.state = State::ReadResponse {
                                 columns: None,
                                 receiver,
                             };
                             return Ok(Async::Ready(Some(SimpleQueryMessage::CommandComplete(0))));
                         }
                         Some(Message::RowDescription(body)) => {
-                            let columns = body
-                                .fields()
-                                .map(|f| Ok(f.name().to_string()))
-                                .collect::<Vec<_>>()
-                                .map_err(Error::parse)?
-                                .into();
-                            self.0 = State::ReadResponse {
-                                columns: Some(columns),
+                            let columns = columns_from_description(&body)?;
+                            self.state = State::ReadResponse {
+                                columns: Some(columns.clone()),
                                 receiver,
                             };
+                            if self.describe {
+                                return Ok(Async::Ready(Some(SimpleQueryMessage::RowDescription(
+                                    columns,
+                                ))));
+                            }
                         }
                         Some(Message::DataRow(body)) => {
                             let row = match &columns {
                                 Some(columns) => SimpleQueryRow::new(columns.clone(), body)?,
                                 None => return Err(Error::unexpected_message()),
                             };
-                            self.0 = State::ReadResponse { columns, receiver };
+                            self.state = State::ReadResponse { columns, receiver };
                             return Ok(Async::Ready(Some(SimpleQueryMessage::Row(row))));
                         }
                         Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
@@ -107,7 +111,19 @@ impl Stream for SimpleQueryStream {
 }
 
 impl SimpleQueryStream {
-    pub fn new(client: Client, request: PendingRequest) -> SimpleQueryStream {
-        SimpleQueryStream(State::Start { client, request })
+    pub fn new(client: Client, request: PendingRequest, describe: bool) -> SimpleQueryStream {
+        SimpleQueryStream {
+            state: State::Start { client, request },
+            describe,
+        }
     }
 }
+
+pub fn columns_from_description(body: &RowDescriptionBody) -> Result<Arc<[SimpleColumn]>, Error> {
+    let columns = body
+        .fields()
+        .map(|f| Ok(SimpleColumn::new(f.name().to_string(), f.type_oid())))
+        .collect::<Vec<_>>()
+        .map_err(Error::parse)?;
+    Ok(columns.into())
+}
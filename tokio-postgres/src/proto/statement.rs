@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::proto::client::WeakClient;
+use crate::proto::statement_cache::StatementKey;
 use crate::types::Type;
 use crate::Column;
 
@@ -9,6 +10,7 @@ pub struct StatementInner {
     name: String,
     params: Vec<Type>,
     columns: Vec<Column>,
+    cache_key: Option<StatementKey>,
 }
 
 impl Drop for StatementInner {
@@ -28,12 +30,14 @@ impl Statement {
         name: String,
         params: Vec<Type>,
         columns: Vec<Column>,
+        cache_key: Option<StatementKey>,
     ) -> Statement {
         Statement(Arc::new(StatementInner {
             client,
             name,
             params,
             columns,
+            cache_key,
         }))
     }
 
@@ -48,4 +52,8 @@ impl Statement {
     pub fn columns(&self) -> &[Column] {
         &self.0.columns
     }
+
+    pub fn cache_key(&self) -> Option<&StatementKey> {
+        self.0.cache_key.as_ref()
+    }
 }
@@ -0,0 +1,269 @@
+use futures::{try_ready, Async, Future, Poll, Stream};
+use postgres_protocol::message::backend::Message;
+use postgres_protocol::message::frontend;
+use postgres_protocol::IsNull;
+use std::collections::HashMap;
+use std::error::Error as _;
+
+use crate::error::{DbError, SqlState};
+use crate::proto::client::Client;
+use crate::proto::prepare::PrepareFuture;
+use crate::proto::responses::Responses;
+use crate::proto::statement::Statement;
+use crate::types::{Oid, Type};
+use crate::Error;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct StatementKey {
+    query: String,
+    types: Vec<Oid>,
+}
+
+impl StatementKey {
+    pub fn new(query: &str, types: &[Type]) -> StatementKey {
+        StatementKey {
+            query: query.to_string(),
+            types: types.iter().map(Type::oid).collect(),
+        }
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn types(&self) -> &[Oid] {
+        &self.types
+    }
+}
+
+struct Entry {
+    statement: Statement,
+    last_used: u64,
+}
+
+pub struct StatementCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<StatementKey, Entry>,
+}
+
+impl StatementCache {
+    pub fn new(capacity: usize) -> StatementCache {
+        StatementCache {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn get(&mut self, key: &StatementKey) -> Option<Statement> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(key).map(|entry| {
+            entry.last_used = tick;
+            entry.statement.clone()
+        })
+    }
+
+    // Statements closed by the cache are returned rather than dropped so that the caller can drop them after
+    // releasing the client's state lock, since dropping the last reference to a statement closes it.
+    pub fn insert(&mut self, key: StatementKey, statement: Statement) -> Option<Statement> {
+        self.tick += 1;
+        let entry = Entry {
+            statement,
+            last_used: self.tick,
+        };
+
+        if let Some(old) = self.entries.insert(key, entry) {
+            return Some(old.statement);
+        }
+
+        if self.entries.len() <= self.capacity {
+            return None;
+        }
+
+        let lru = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+            .unwrap();
+        self.entries.remove(&lru).map(|entry| entry.statement)
+    }
+
+    pub fn remove(&mut self, statement: &Statement) -> Option<Statement> {
+        let key = statement.cache_key()?;
+        match self.entries.get(key) {
+            Some(entry) if entry.statement.name() == statement.name() => {}
+            _ => return None,
+        }
+        self.entries.remove(key).map(|entry| entry.statement)
+    }
+}
+
+pub enum CachedPrepareFuture {
+    Cached(Option<Statement>),
+    Preparing(Box<PrepareFuture>, Client),
+}
+
+impl Future for CachedPrepareFuture {
+    type Item = Statement;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Statement, Error> {
+        match self {
+            CachedPrepareFuture::Cached(statement) => Ok(Async::Ready(
+                statement.take().expect("future polled after completion"),
+            )),
+            CachedPrepareFuture::Preparing(future, client) => {
+                let statement = try_ready!(future.poll());
+                if statement.cache_key().is_some() {
+                    client.cache_statement(&statement);
+                }
+                Ok(Async::Ready(statement))
+            }
+        }
+    }
+}
+
+pub struct Retry {
+    client: Client,
+    statement: Statement,
+    params: Vec<Option<Vec<u8>>>,
+    param_formats: Vec<i16>,
+    result_formats: Vec<i16>,
+}
+
+impl Retry {
+    pub fn new(
+        client: Client,
+        statement: Statement,
+        params: Vec<Option<Vec<u8>>>,
+        param_formats: Vec<i16>,
+        result_formats: Vec<i16>,
+    ) -> Retry {
+        Retry {
+            client,
+            statement,
+            params,
+            param_formats,
+            result_formats,
+        }
+    }
+
+    // Encodes the Bind, Execute and Sync messages of the request for the named statement.
+    pub fn message(&self, statement: &str) -> Result<Vec<u8>, Error> {
+        let mut buf = vec![];
+        let r = frontend::bind(
+            "",
+            statement,
+            self.param_formats.iter().cloned(),
+            &self.params,
+            |param, buf| match param {
+                Some(param) => {
+                    buf.extend_from_slice(param);
+                    Ok(IsNull::No)
+                }
+                None => Ok(IsNull::Yes),
+            },
+            self.result_formats.iter().cloned(),
+            &mut buf,
+        );
+        match r {
+            Ok(()) => {}
+            Err(frontend::BindError::Conversion(_)) => unreachable!(),
+            Err(frontend::BindError::Serialization(e)) => return Err(Error::encode(e)),
+        }
+        frontend::execute("", 0, &mut buf).map_err(Error::parse)?;
+        frontend::sync(&mut buf);
+        Ok(buf)
+    }
+
+    // The server rejects cached plans whose result type changed with a FEATURE_NOT_SUPPORTED error, which is recovered
+    // from by re-preparing the statement once the failed request has finished. The original error is returned if that
+    // is not possible.
+    pub fn start(self, error: Error, receiver: Responses) -> Result<RetryFuture, Error> {
+        if !is_cached_plan_error(&error) {
+            return Err(error);
+        }
+
+        if self.statement.cache_key().is_none() {
+            return Err(error);
+        }
+        self.client.invalidate_statement(&self.statement);
+
+        Ok(RetryFuture {
+            state: RetryState::ReadingReady(receiver),
+            retry: self,
+            error: Some(error),
+        })
+    }
+}
+
+fn is_cached_plan_error(error: &Error) -> bool {
+    match error.source().and_then(|e| e.downcast_ref::<DbError>()) {
+        Some(e) => {
+            *e.code() == SqlState::FEATURE_NOT_SUPPORTED
+                && e.routine() == Some("RevalidateCachedQuery")
+        }
+        None => false,
+    }
+}
+
+enum RetryState {
+    ReadingReady(Responses),
+    Preparing(Box<CachedPrepareFuture>),
+}
+
+pub struct RetryFuture {
+    state: RetryState,
+    retry: Retry,
+    error: Option<Error>,
+}
+
+impl RetryFuture {
+    fn error(&mut self) -> Error {
+        self.error.take().expect("future polled after completion")
+    }
+}
+
+impl Future for RetryFuture {
+    type Item = (Responses, Statement);
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(Responses, Statement), Error> {
+        loop {
+            let future = match &mut self.state {
+                RetryState::ReadingReady(receiver) => match try_ready!(receiver.poll()) {
+                    // an error inside of a transaction block aborts it, so the statement can only be retried outside
+                    // of one
+                    Some(Message::ReadyForQuery(body)) if body.status() == b'I' => {
+                        let key = self.retry.statement.cache_key().unwrap().clone();
+                        self.retry.client.prepare_key(key)
+                    }
+                    Some(Message::ReadyForQuery(_)) => return Err(self.error()),
+                    Some(_) => continue,
+                    None => return Err(self.error()),
+                },
+                RetryState::Preparing(future) => {
+                    let statement = try_ready!(future.poll());
+
+                    // the parameters have already been encoded for the old statement's types
+                    if statement.params() != self.retry.statement.params() {
+                        return Err(self.error());
+                    }
+
+                    let message = self.retry.message(statement.name())?;
+                    let receiver = self.retry.client.send_raw(message)?;
+
+                    return Ok(Async::Ready((receiver, statement)));
+                }
+            };
+            self.state = RetryState::Preparing(Box::new(future));
+        }
+    }
+}
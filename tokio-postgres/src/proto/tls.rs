@@ -47,7 +47,8 @@ where
         let state = state.take();
 
         match state.mode {
-            SslMode::Disable => transition!(Ready((
+            // `Allow` only uses TLS when the connection is retried with `Require`
+            SslMode::Disable | SslMode::Allow => transition!(Ready((
                 MaybeTlsStream::Raw(state.stream),
                 ChannelBinding::none()
             ))),
@@ -55,7 +56,7 @@ where
                 MaybeTlsStream::Raw(state.stream),
                 ChannelBinding::none()
             ))),
-            SslMode::Prefer | SslMode::Require => {
+            SslMode::Prefer | SslMode::Require | SslMode::VerifyCa | SslMode::VerifyFull => {
                 let mut buf = vec![];
                 frontend::ssl_request(&mut buf);
 
@@ -91,7 +92,7 @@ where
             transition!(ConnectingTls {
                 future: state.tls.connect(stream),
             })
-        } else if state.mode == SslMode::Require {
+        } else if state.mode != SslMode::Prefer {
             Err(Error::tls("server does not support TLS".into()))
         } else {
             transition!(Ready((MaybeTlsStream::Raw(stream), ChannelBinding::none())))
@@ -13,7 +13,19 @@ use crate::proto::typeinfo_enum::TypeinfoEnumFuture;
 use crate::types::{Kind, Oid, ToSql, Type};
 
 const TYPEINFO_QUERY: &str = "
-SELECT t.typname, t.typtype, t.typelem, r.rngsubtype, t.typbasetype, n.nspname, t.typrelid
+SELECT t.typname, t.typtype, t.typelem, r.rngsubtype, t.typbasetype, n.nspname, t.typrelid,
+    m.rngtypid
+FROM pg_catalog.pg_type t
+LEFT OUTER JOIN pg_catalog.pg_range r ON r.rngtypid = t.oid
+LEFT OUTER JOIN pg_catalog.pg_range m ON m.rngmultitypid = t.oid
+INNER JOIN pg_catalog.pg_namespace n ON t.typnamespace = n.oid
+WHERE t.oid = $1
+";
+
+// Multirange types weren't added until Postgres 14, so pg_range.rngmultitypid may not exist
+const TYPEINFO_RANGE_QUERY: &str = "
+SELECT t.typname, t.typtype, t.typelem, r.rngsubtype, t.typbasetype, n.nspname, t.typrelid,
+    NULL::OID
 FROM pg_catalog.pg_type t
 LEFT OUTER JOIN pg_catalog.pg_range r ON r.rngtypid = t.oid
 INNER JOIN pg_catalog.pg_namespace n ON t.typnamespace = n.oid
@@ -22,7 +34,8 @@ WHERE t.oid = $1
 
 // Range types weren't added until Postgres 9.2, so pg_range may not exist
 const TYPEINFO_FALLBACK_QUERY: &str = "
-SELECT t.typname, t.typtype, t.typelem, NULL::OID, t.typbasetype, n.nspname, t.typrelid
+SELECT t.typname, t.typtype, t.typelem, NULL::OID, t.typbasetype, n.nspname, t.typrelid,
+    NULL::OID
 FROM pg_catalog.pg_type t
 INNER JOIN pg_catalog.pg_namespace n ON t.typnamespace = n.oid
 WHERE t.oid = $1
@@ -32,13 +45,23 @@ WHERE t.oid = $1
 pub enum Typeinfo {
     #[state_machine_future(start, transitions(PreparingTypeinfo, QueryingTypeinfo, Finished))]
     Start { oid: Oid, client: Client },
-    #[state_machine_future(transitions(PreparingTypeinfoFallback, QueryingTypeinfo))]
+    #[state_machine_future(transitions(
+        PreparingTypeinfoRange,
+        PreparingTypeinfoFallback,
+        QueryingTypeinfo
+    ))]
     PreparingTypeinfo {
         future: Box<PrepareFuture>,
         oid: Oid,
         client: Client,
     },
     #[state_machine_future(transitions(QueryingTypeinfo))]
+    PreparingTypeinfoRange {
+        future: Box<PrepareFuture>,
+        oid: Oid,
+        client: Client,
+    },
+    #[state_machine_future(transitions(QueryingTypeinfo))]
     PreparingTypeinfoFallback {
         future: Box<PrepareFuture>,
         oid: Oid,
@@ -50,7 +73,8 @@ pub enum Typeinfo {
         QueryingDomainBasetype,
         QueryingArrayElem,
         QueryingCompositeFields,
-        QueryingRangeSubtype
+        QueryingRangeSubtype,
+        QueryingMultirangeRange
     ))]
     QueryingTypeinfo {
         future: stream::Collect<QueryStream<Statement>>,
@@ -92,6 +116,13 @@ pub enum Typeinfo {
         oid: Oid,
         schema: String,
     },
+    #[state_machine_future(transitions(CachingType))]
+    QueryingMultirangeRange {
+        future: Box<TypeinfoFuture>,
+        name: String,
+        oid: Oid,
+        schema: String,
+    },
     #[state_machine_future(transitions(Finished))]
     CachingType { ty: Type, oid: Oid, client: Client },
     #[state_machine_future(ready)]
@@ -135,6 +166,19 @@ impl PollTypeinfo for Typeinfo {
         let statement = match state.future.poll() {
             Ok(Async::Ready(statement)) => statement,
             Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(ref e) if e.code() == Some(&SqlState::UNDEFINED_COLUMN) => {
+                let state = state.take();
+
+                transition!(PreparingTypeinfoRange {
+                    future: Box::new(state.client.prepare(
+                        next_statement(),
+                        TYPEINFO_RANGE_QUERY,
+                        &[]
+                    )),
+                    oid: state.oid,
+                    client: state.client,
+                })
+            }
             Err(ref e) if e.code() == Some(&SqlState::UNDEFINED_TABLE) => {
                 let state = state.take();
 
@@ -164,6 +208,24 @@ impl PollTypeinfo for Typeinfo {
         })
     }
 
+    fn poll_preparing_typeinfo_range<'a>(
+        state: &'a mut RentToOwn<'a, PreparingTypeinfoRange>,
+    ) -> Poll<AfterPreparingTypeinfoRange, Error> {
+        let statement = try_ready!(state.future.poll());
+        let state = state.take();
+
+        let future = state
+            .client
+            .query(&statement, [&state.oid as &dyn ToSql].iter().cloned())
+            .collect();
+        state.client.set_typeinfo_query(&statement);
+        transition!(QueryingTypeinfo {
+            future,
+            oid: state.oid,
+            client: state.client
+        })
+    }
+
     fn poll_preparing_typeinfo_fallback<'a>(
         state: &'a mut RentToOwn<'a, PreparingTypeinfoFallback>,
     ) -> Poll<AfterPreparingTypeinfoFallback, Error> {
@@ -200,6 +262,7 @@ impl PollTypeinfo for Typeinfo {
         let basetype = row.try_get::<_, Oid>(4)?;
         let schema = row.try_get::<_, String>(5)?;
         let relid = row.try_get::<_, Oid>(6)?;
+        let rngtypid = row.try_get::<_, Option<Oid>>(7)?;
 
         let kind = if type_ == b'e' as i8 {
             transition!(QueryingEnumVariants {
@@ -238,6 +301,13 @@ impl PollTypeinfo for Typeinfo {
                 oid: state.oid,
                 schema,
             })
+        } else if let Some(rngtypid) = rngtypid {
+            transition!(QueryingMultirangeRange {
+                future: Box::new(TypeinfoFuture::new(rngtypid, state.client)),
+                name,
+                oid: state.oid,
+                schema,
+            })
         } else {
             Kind::Simple
         };
@@ -320,6 +390,20 @@ impl PollTypeinfo for Typeinfo {
         })
     }
 
+    fn poll_querying_multirange_range<'a>(
+        state: &'a mut RentToOwn<'a, QueryingMultirangeRange>,
+    ) -> Poll<AfterQueryingMultirangeRange, Error> {
+        let (range, client) = try_ready!(state.future.poll());
+        let state = state.take();
+
+        let ty = Type::_new(state.name, state.oid, Kind::Multirange(range), state.schema);
+        transition!(CachingType {
+            ty,
+            oid: state.oid,
+            client,
+        })
+    }
+
     fn poll_caching_type<'a>(
         state: &'a mut RentToOwn<'a, CachingType>,
     ) -> Poll<AfterCachingType, Error> {
@@ -0,0 +1,648 @@
+//! Utilities for working with the PostgreSQL streaming replication protocol.
+//!
+//! Replication requires a connection started in replication mode (see `Config::replication_mode`). A
+//! `ReplicationClient` wraps such a connection, providing methods for the replication commands which inspect the
+//! server, manage replication slots, stream WAL, and take base backups.
+//!
+//! Logical replication streams are decoded as the messages produced by the `pgoutput` plugin, while physical
+//! replication streams yield the raw WAL.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use futures::{Future, Stream};
+//! use tokio_postgres::replication::{LogicalReplicationMessage, ReplicationClient, ReplicationMessage};
+//! use tokio_postgres::Error;
+//!
+//! fn stream_changes(client: &mut ReplicationClient) -> impl Future<Item = (), Error = Error> {
+//!     let stream = client.start_logical_replication(
+//!         "my_slot",
+//!         0,
+//!         &[("proto_version", "1"), ("publication_names", "my_publication")],
+//!     );
+//!
+//!     stream.for_each(|message| {
+//!         if let ReplicationMessage::XLogData(body) = message {
+//!             if let LogicalReplicationMessage::Insert(insert) = body.data() {
+//!                 println!("row inserted into relation {}", insert.rel_id());
+//!             }
+//!         }
+//!         Ok(())
+//!     })
+//! }
+//! ```
+use bytes::Bytes;
+use futures::{try_ready, Async, AsyncSink, Future, Poll, Sink, Stream};
+use postgres_protocol::message::frontend;
+use std::error;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::impls::CopyBoth;
+use crate::proto;
+use crate::{Client, Error, SimpleQueryMessage, SimpleQueryRow};
+
+pub use postgres_protocol::message::backend::{
+    BeginBody, Column, CommitBody, DeleteBody, InsertBody, LogicalReplicationMessage, OriginBody,
+    PrimaryKeepAliveBody, RelationBody, ReplicaIdentity, ReplicationMessage, TruncateBody, Tuple,
+    TupleData, TypeBody, UpdateBody, XLogDataBody,
+};
+
+const POSTGRES_EPOCH_SECS: u64 = 946_684_800;
+
+/// A client for a connection in replication mode.
+pub struct ReplicationClient(Client);
+
+impl ReplicationClient {
+    /// Creates a new `ReplicationClient` from a client connected in replication mode.
+    pub fn new(client: Client) -> ReplicationClient {
+        ReplicationClient(client)
+    }
+
+    /// Returns a mutable reference to the underlying client.
+    pub fn get_mut(&mut self) -> &mut Client {
+        &mut self.0
+    }
+
+    /// Consumes the `ReplicationClient`, returning the underlying client.
+    pub fn into_inner(self) -> Client {
+        self.0
+    }
+
+    /// Requests the server to identify itself with `IDENTIFY_SYSTEM`.
+    pub fn identify_system(&mut self) -> impl Future<Item = IdentifySystem, Error = Error> {
+        query_one(&mut self.0, "IDENTIFY_SYSTEM").and_then(|row| {
+            Ok(IdentifySystem {
+                systemid: parse_column(&row, 0)?,
+                timeline: parse_column(&row, 1)?,
+                xlogpos: parse_lsn_column(&row, 2)?,
+                dbname: row.try_get(3)?.map(|s| s.to_string()),
+            })
+        })
+    }
+
+    /// Requests the history file of a timeline with `TIMELINE_HISTORY`.
+    pub fn timeline_history(
+        &mut self,
+        timeline: u32,
+    ) -> impl Future<Item = TimelineHistory, Error = Error> {
+        let query = format!("TIMELINE_HISTORY {}", timeline);
+        query_one(&mut self.0, &query).and_then(|row| {
+            Ok(TimelineHistory {
+                filename: parse_column(&row, 0)?,
+                content: parse_column(&row, 1)?,
+            })
+        })
+    }
+
+    /// Creates a physical replication slot with `CREATE_REPLICATION_SLOT`.
+    ///
+    /// A temporary slot is dropped when the connection closes. If `reserve_wal` is set, the slot reserves WAL
+    /// immediately rather than when a client first streams from it.
+    pub fn create_physical_replication_slot(
+        &mut self,
+        name: &str,
+        temporary: bool,
+        reserve_wal: bool,
+    ) -> impl Future<Item = ReplicationSlot, Error = Error> {
+        let mut query = format!("CREATE_REPLICATION_SLOT {}", quote_identifier(name));
+        if temporary {
+            query.push_str(" TEMPORARY");
+        }
+        query.push_str(" PHYSICAL");
+        if reserve_wal {
+            query.push_str(" RESERVE_WAL");
+        }
+        self.create_replication_slot(&query)
+    }
+
+    /// Creates a logical replication slot using the specified output plugin with `CREATE_REPLICATION_SLOT`.
+    ///
+    /// A temporary slot is dropped when the connection closes.
+    pub fn create_logical_replication_slot(
+        &mut self,
+        name: &str,
+        temporary: bool,
+        plugin: &str,
+    ) -> impl Future<Item = ReplicationSlot, Error = Error> {
+        let mut query = format!("CREATE_REPLICATION_SLOT {}", quote_identifier(name));
+        if temporary {
+            query.push_str(" TEMPORARY");
+        }
+        write!(query, " LOGICAL {}", quote_identifier(plugin)).unwrap();
+        self.create_replication_slot(&query)
+    }
+
+    fn create_replication_slot(
+        &mut self,
+        query: &str,
+    ) -> impl Future<Item = ReplicationSlot, Error = Error> {
+        query_one(&mut self.0, query).and_then(|row| {
+            Ok(ReplicationSlot {
+                name: parse_column(&row, 0)?,
+                consistent_point: match row.try_get(1)? {
+                    Some(_) => Some(parse_lsn_column(&row, 1)?),
+                    None => None,
+                },
+                snapshot_name: row.try_get(2)?.map(|s| s.to_string()),
+                output_plugin: row.try_get(3)?.map(|s| s.to_string()),
+            })
+        })
+    }
+
+    /// Drops a replication slot with `DROP_REPLICATION_SLOT`.
+    ///
+    /// If `wait` is set and the slot is in use, the server waits for it to become inactive rather than returning an
+    /// error.
+    pub fn drop_replication_slot(
+        &mut self,
+        name: &str,
+        wait: bool,
+    ) -> impl Future<Item = (), Error = Error> {
+        let mut query = format!("DROP_REPLICATION_SLOT {}", quote_identifier(name));
+        if wait {
+            query.push_str(" WAIT");
+        }
+        self.0.simple_query(&query).for_each(|_| Ok(()))
+    }
+
+    /// Starts streaming WAL from the specified position with `START_REPLICATION ... PHYSICAL`.
+    ///
+    /// If a slot is provided, the server retains the WAL needed by the client. The timeline defaults to the server's
+    /// current timeline.
+    pub fn start_physical_replication(
+        &mut self,
+        slot: Option<&str>,
+        start_lsn: u64,
+        timeline: Option<u32>,
+    ) -> ReplicationStream {
+        let mut query = "START_REPLICATION".to_string();
+        if let Some(slot) = slot {
+            write!(query, " SLOT {}", quote_identifier(slot)).unwrap();
+        }
+        write!(query, " PHYSICAL {}", format_lsn(start_lsn)).unwrap();
+        if let Some(timeline) = timeline {
+            write!(query, " TIMELINE {}", timeline).unwrap();
+        }
+        ReplicationStream::new(self.0.copy_both_simple(&query))
+    }
+
+    /// Starts streaming changes from a logical replication slot using the `pgoutput` plugin with
+    /// `START_REPLICATION ... LOGICAL`.
+    ///
+    /// The options are passed to the output plugin, for example `proto_version` and `publication_names`.
+    pub fn start_logical_replication(
+        &mut self,
+        slot: &str,
+        start_lsn: u64,
+        options: &[(&str, &str)],
+    ) -> LogicalReplicationStream {
+        let mut query = format!(
+            "START_REPLICATION SLOT {} LOGICAL {}",
+            quote_identifier(slot),
+            format_lsn(start_lsn)
+        );
+        write_options(&mut query, options);
+        LogicalReplicationStream::new(self.0.copy_both_simple(&query))
+    }
+
+    /// Takes a base backup of the server with `BASE_BACKUP`.
+    ///
+    /// The options are passed to the command, for example `label`, `progress`, or `checkpoint`. The server sends the
+    /// backup as a sequence of tar archives, one per tablespace, followed by the backup manifest. Requires PostgreSQL
+    /// 15 or newer.
+    pub fn base_backup(&mut self, options: &[(&str, &str)]) -> BaseBackupStream {
+        let mut query = "BASE_BACKUP".to_string();
+        write_options(&mut query, options);
+        BaseBackupStream((self.0).0.base_backup(&query))
+    }
+}
+
+/// The response to an `IDENTIFY_SYSTEM` command.
+#[derive(Debug, Clone)]
+pub struct IdentifySystem {
+    systemid: String,
+    timeline: u32,
+    xlogpos: u64,
+    dbname: Option<String>,
+}
+
+impl IdentifySystem {
+    /// The unique system identifier of the database cluster.
+    pub fn systemid(&self) -> &str {
+        &self.systemid
+    }
+
+    /// The server's current timeline.
+    pub fn timeline(&self) -> u32 {
+        self.timeline
+    }
+
+    /// The server's current WAL flush position.
+    pub fn xlogpos(&self) -> u64 {
+        self.xlogpos
+    }
+
+    /// The database the session is connected to, if in logical replication mode.
+    pub fn dbname(&self) -> Option<&str> {
+        self.dbname.as_deref()
+    }
+}
+
+/// The response to a `TIMELINE_HISTORY` command.
+#[derive(Debug, Clone)]
+pub struct TimelineHistory {
+    filename: String,
+    content: String,
+}
+
+impl TimelineHistory {
+    /// The file name of the timeline history file.
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// The contents of the timeline history file.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// The response to a `CREATE_REPLICATION_SLOT` command.
+#[derive(Debug, Clone)]
+pub struct ReplicationSlot {
+    name: String,
+    consistent_point: Option<u64>,
+    snapshot_name: Option<String>,
+    output_plugin: Option<String>,
+}
+
+impl ReplicationSlot {
+    /// The name of the slot.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The WAL position at which the slot became consistent, if it has reserved WAL.
+    pub fn consistent_point(&self) -> Option<u64> {
+        self.consistent_point
+    }
+
+    /// The identifier of the snapshot exported by a logical slot.
+    pub fn snapshot_name(&self) -> Option<&str> {
+        self.snapshot_name.as_deref()
+    }
+
+    /// The output plugin of a logical slot.
+    pub fn output_plugin(&self) -> Option<&str> {
+        self.output_plugin.as_deref()
+    }
+}
+
+/// A message sent by the server during a base backup.
+#[derive(Debug, Clone)]
+pub enum BaseBackupMessage {
+    /// The WAL position at which the backup started.
+    Start(WalPosition),
+    /// A tablespace included in the backup.
+    Tablespace(Tablespace),
+    /// The start of a new tar archive. Subsequent data belongs to this archive.
+    Archive(Archive),
+    /// A chunk of the current archive or manifest.
+    Data(Bytes),
+    /// The number of bytes of the backup sent so far, if progress reporting was requested.
+    Progress(u64),
+    /// The start of the backup manifest. Subsequent data belongs to the manifest.
+    Manifest,
+    /// The WAL position at which the backup ended.
+    End(WalPosition),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// A position in the WAL.
+#[derive(Debug, Copy, Clone)]
+pub struct WalPosition {
+    pub(crate) lsn: u64,
+    pub(crate) timeline: u32,
+}
+
+impl WalPosition {
+    /// The WAL location.
+    pub fn lsn(&self) -> u64 {
+        self.lsn
+    }
+
+    /// The timeline of the location.
+    pub fn timeline(&self) -> u32 {
+        self.timeline
+    }
+}
+
+/// A tablespace included in a base backup.
+#[derive(Debug, Clone)]
+pub struct Tablespace {
+    pub(crate) oid: Option<u32>,
+    pub(crate) location: Option<String>,
+    pub(crate) size: Option<u64>,
+}
+
+impl Tablespace {
+    /// The OID of the tablespace, or `None` for the main data directory.
+    pub fn oid(&self) -> Option<u32> {
+        self.oid
+    }
+
+    /// The directory of the tablespace, or `None` for the main data directory.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The approximate size of the tablespace in kilobytes, if progress reporting was requested.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+}
+
+/// An archive of a base backup.
+#[derive(Debug, Clone)]
+pub struct Archive {
+    pub(crate) name: String,
+    pub(crate) tablespace_location: Option<String>,
+}
+
+impl Archive {
+    /// The file name of the archive, for example `base.tar`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The directory of the tablespace contained in the archive, or `None` for the main data directory.
+    pub fn tablespace_location(&self) -> Option<&str> {
+        self.tablespace_location.as_deref()
+    }
+}
+
+/// The stream returned by `ReplicationClient::base_backup`.
+#[must_use = "streams do nothing unless polled"]
+pub struct BaseBackupStream(proto::BaseBackupStream);
+
+impl Stream for BaseBackupStream {
+    type Item = BaseBackupMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BaseBackupMessage>, Error> {
+        self.0.poll()
+    }
+}
+
+/// A stream of messages sent by the server during streaming replication.
+///
+/// Standby status updates can be queued with `standby_status_update`, and are sent to the server as the stream is
+/// polled.
+pub struct ReplicationStream {
+    stream: CopyBoth,
+    pending: Option<Bytes>,
+    next_timeline: Option<WalPosition>,
+}
+
+impl ReplicationStream {
+    /// Creates a new `ReplicationStream` from the stream returned by `Client::copy_both_simple` for a
+    /// `START_REPLICATION` command.
+    pub fn new(stream: CopyBoth) -> ReplicationStream {
+        ReplicationStream {
+            stream,
+            pending: None,
+            next_timeline: None,
+        }
+    }
+
+    /// Returns the timeline the server switched to and the position at which it begins, if the stream ended because
+    /// the server reached the end of the timeline being streamed.
+    ///
+    /// Replication can be continued by starting a new stream at that position and timeline.
+    pub fn next_timeline(&self) -> Option<WalPosition> {
+        self.next_timeline
+    }
+
+    /// Queues a standby status update reporting the progress of the client.
+    ///
+    /// The LSNs are the positions of the last WAL byte + 1 written, flushed, and applied by the client. If `reply` is
+    /// set, the server will immediately respond with a keepalive message. The update is sent the next time the stream
+    /// is polled, replacing any previously queued update which has not yet been sent.
+    pub fn standby_status_update(
+        &mut self,
+        write_lsn: u64,
+        flush_lsn: u64,
+        apply_lsn: u64,
+        timestamp: SystemTime,
+        reply: bool,
+    ) {
+        let mut buf = vec![];
+        frontend::standby_status_update(
+            write_lsn,
+            flush_lsn,
+            apply_lsn,
+            to_postgres_timestamp(timestamp),
+            reply,
+            &mut buf,
+        );
+        self.pending = Some(Bytes::from(buf));
+    }
+
+    /// Consumes the `ReplicationStream`, returning the underlying copy stream.
+    ///
+    /// Closing the sink half of the copy stream ends replication.
+    pub fn into_inner(self) -> CopyBoth {
+        self.stream
+    }
+
+    fn poll_pending(&mut self) -> Result<(), Error> {
+        if let Some(buf) = self.pending.take() {
+            if let AsyncSink::NotReady(buf) = self.stream.start_send(buf)? {
+                self.pending = Some(buf);
+            }
+        }
+
+        self.stream.poll_complete()?;
+        Ok(())
+    }
+}
+
+impl Stream for ReplicationStream {
+    type Item = ReplicationMessage<Bytes>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<ReplicationMessage<Bytes>>, Error> {
+        self.poll_pending()?;
+
+        match try_ready!(self.stream.poll()) {
+            Some(buf) => {
+                let message = ReplicationMessage::parse(&buf).map_err(Error::parse)?;
+                Ok(Async::Ready(Some(message)))
+            }
+            None => {
+                // at the end of a timeline, the server reports the next one in a result set after the copy
+                if let Some(row) = (self.stream.0).rows().first() {
+                    self.next_timeline = Some(WalPosition {
+                        timeline: parse_column(row, 0)?,
+                        lsn: parse_lsn_column(row, 1)?,
+                    });
+                }
+                Ok(Async::Ready(None))
+            }
+        }
+    }
+}
+
+/// A stream of messages sent by the server during logical replication with the `pgoutput` plugin.
+///
+/// The contents of `XLogData` messages are decoded into `LogicalReplicationMessage`s.
+pub struct LogicalReplicationStream(ReplicationStream);
+
+impl LogicalReplicationStream {
+    /// Creates a new `LogicalReplicationStream` from the stream returned by `Client::copy_both_simple` for a
+    /// `START_REPLICATION ... LOGICAL` command using the `pgoutput` plugin.
+    pub fn new(stream: CopyBoth) -> LogicalReplicationStream {
+        LogicalReplicationStream(ReplicationStream::new(stream))
+    }
+
+    /// Queues a standby status update reporting the progress of the client.
+    ///
+    /// See `ReplicationStream::standby_status_update` for details.
+    pub fn standby_status_update(
+        &mut self,
+        write_lsn: u64,
+        flush_lsn: u64,
+        apply_lsn: u64,
+        timestamp: SystemTime,
+        reply: bool,
+    ) {
+        self.0
+            .standby_status_update(write_lsn, flush_lsn, apply_lsn, timestamp, reply)
+    }
+
+    /// Consumes the `LogicalReplicationStream`, returning the underlying copy stream.
+    ///
+    /// Closing the sink half of the copy stream ends replication.
+    pub fn into_inner(self) -> CopyBoth {
+        self.0.into_inner()
+    }
+}
+
+impl Stream for LogicalReplicationStream {
+    type Item = ReplicationMessage<LogicalReplicationMessage>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<ReplicationMessage<LogicalReplicationMessage>>, Error> {
+        let message = match try_ready!(self.0.poll()) {
+            Some(ReplicationMessage::XLogData(body)) => {
+                let body = body
+                    .map_data(|buf| LogicalReplicationMessage::parse(&buf))
+                    .map_err(Error::parse)?;
+                ReplicationMessage::XLogData(body)
+            }
+            Some(ReplicationMessage::PrimaryKeepAlive(body)) => {
+                ReplicationMessage::PrimaryKeepAlive(body)
+            }
+            Some(_) => return Err(Error::unexpected_message()),
+            None => return Ok(Async::Ready(None)),
+        };
+
+        Ok(Async::Ready(Some(message)))
+    }
+}
+
+fn query_one(
+    client: &mut Client,
+    query: &str,
+) -> impl Future<Item = SimpleQueryRow, Error = Error> {
+    client
+        .simple_query(query)
+        .filter_map(|m| match m {
+            SimpleQueryMessage::Row(row) => Some(row),
+            _ => None,
+        })
+        .collect()
+        .and_then(|rows| {
+            rows.into_iter()
+                .next()
+                .ok_or_else(Error::unexpected_message)
+        })
+}
+
+pub(crate) fn parse_column<T>(row: &SimpleQueryRow, idx: usize) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn error::Error + Sync + Send>>,
+{
+    match parse_nullable_column(row, idx)? {
+        Some(value) => Ok(value),
+        None => Err(Error::from_sql("unexpected NULL".into(), idx)),
+    }
+}
+
+pub(crate) fn parse_nullable_column<T>(row: &SimpleQueryRow, idx: usize) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+    T::Err: Into<Box<dyn error::Error + Sync + Send>>,
+{
+    match row.try_get(idx)? {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|e: T::Err| Error::from_sql(e.into(), idx)),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn parse_lsn_column(row: &SimpleQueryRow, idx: usize) -> Result<u64, Error> {
+    let value = parse_column::<String>(row, idx)?;
+    parse_lsn(&value).ok_or_else(|| Error::from_sql(format!("invalid LSN `{}`", value).into(), idx))
+}
+
+fn parse_lsn(s: &str) -> Option<u64> {
+    let mut it = s.splitn(2, '/');
+    let hi = u32::from_str_radix(it.next()?, 16).ok()?;
+    let lo = u32::from_str_radix(it.next()?, 16).ok()?;
+    Some((u64::from(hi) << 32) | u64::from(lo))
+}
+
+fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn as u32)
+}
+
+fn quote_identifier(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+fn write_options(query: &mut String, options: &[(&str, &str)]) {
+    if options.is_empty() {
+        return;
+    }
+
+    query.push_str(" (");
+    for (i, (name, value)) in options.iter().enumerate() {
+        if i > 0 {
+            query.push_str(", ");
+        }
+        write!(
+            query,
+            "{} '{}'",
+            quote_identifier(name),
+            value.replace('\'', "''")
+        )
+        .unwrap();
+    }
+    query.push(')');
+}
+
+// Replication timestamps are microseconds since midnight on 2000-01-01.
+fn to_postgres_timestamp(timestamp: SystemTime) -> i64 {
+    let epoch = UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_SECS);
+    match timestamp.duration_since(epoch) {
+        Ok(duration) => duration.as_micros() as i64,
+        Err(e) => -(e.duration().as_micros() as i64),
+    }
+}
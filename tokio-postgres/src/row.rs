@@ -2,6 +2,7 @@
 
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::DataRowBody;
+use std::error::Error as StdError;
 use std::fmt;
 use std::ops::Range;
 use std::str;
@@ -9,10 +10,13 @@ use std::sync::Arc;
 
 use crate::proto;
 use crate::row::sealed::{AsName, Sealed};
-use crate::stmt::Column;
-use crate::types::{FromSql, Type, WrongType};
+use crate::stmt::{Column, SimpleColumn};
+use crate::types::{Format, FromSql, FromSqlText, Type, WrongType};
 use crate::Error;
 
+#[cfg(feature = "derive")]
+pub use postgres_derive::FromRow;
+
 mod sealed {
     pub trait Sealed {}
 
@@ -27,6 +31,12 @@ impl AsName for Column {
     }
 }
 
+impl AsName for SimpleColumn {
+    fn as_name(&self) -> &str {
+        self.name()
+    }
+}
+
 impl AsName for String {
     fn as_name(&self) -> &str {
         self
@@ -41,6 +51,9 @@ pub trait RowIndex: Sealed {
     fn __idx<T>(&self, columns: &[T]) -> Option<usize>
     where
         T: AsName;
+
+    #[doc(hidden)]
+    fn __display(&self) -> String;
 }
 
 impl Sealed for usize {}
@@ -57,6 +70,10 @@ impl RowIndex for usize {
             Some(*self)
         }
     }
+
+    fn __display(&self) -> String {
+        self.to_string()
+    }
 }
 
 impl Sealed for str {}
@@ -78,6 +95,10 @@ impl RowIndex for str {
             .iter()
             .position(|d| d.as_name().eq_ignore_ascii_case(self))
     }
+
+    fn __display(&self) -> String {
+        self.to_string()
+    }
 }
 
 impl<'a, T> Sealed for &'a T where T: ?Sized + Sealed {}
@@ -93,6 +114,10 @@ where
     {
         T::__idx(*self, columns)
     }
+
+    fn __display(&self) -> String {
+        T::__display(*self)
+    }
 }
 
 /// A row of data returned from the database by a query.
@@ -100,16 +125,22 @@ pub struct Row {
     statement: proto::Statement,
     body: DataRowBody,
     ranges: Vec<Option<Range<usize>>>,
+    formats: Arc<[Format]>,
 }
 
 impl Row {
     #[allow(clippy::new_ret_no_self)]
-    pub(crate) fn new(statement: proto::Statement, body: DataRowBody) -> Result<Row, Error> {
+    pub(crate) fn new(
+        statement: proto::Statement,
+        body: DataRowBody,
+        formats: Arc<[Format]>,
+    ) -> Result<Row, Error> {
         let ranges = body.ranges().collect().map_err(Error::parse)?;
         Ok(Row {
             statement,
             body,
             ranges,
+            formats,
         })
     }
 
@@ -128,14 +159,138 @@ impl Row {
         self.columns().len()
     }
 
+    /// Returns the format in which the value at the specified index was returned by the database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn format(&self, idx: usize) -> Format {
+        self.as_row_ref().format(idx)
+    }
+
     /// Deserializes a value from the row.
     ///
     /// The value can be specified either by its numeric index in the row, or by its column name.
     ///
+    /// A value returned in the text format can only be deserialized into a string type like `&str` or `String`. Use
+    /// `Row::get_text` to parse it into other types.
+    ///
     /// # Panics
     ///
     /// Panics if the index is out of bounds or if the value cannot be converted to the specified type.
     pub fn get<'a, I, T>(&'a self, idx: I) -> T
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSql<'a>,
+    {
+        self.as_row_ref().get(idx)
+    }
+
+    /// Like `Row::get`, but returns a `Result` rather than panicking.
+    pub fn try_get<'a, I, T>(&'a self, idx: I) -> Result<T, Error>
+    where
+        I: RowIndex,
+        T: FromSql<'a>,
+    {
+        self.as_row_ref().try_get(idx)
+    }
+
+    /// Parses a value returned in the text format from the row.
+    ///
+    /// The value can be specified either by its numeric index in the row, or by its column name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, if the value was not returned in the text format, or if the value cannot
+    /// be converted to the specified type.
+    pub fn get_text<'a, I, T>(&'a self, idx: I) -> T
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        self.as_row_ref().get_text(idx)
+    }
+
+    /// Like `Row::get_text`, but returns a `Result` rather than panicking.
+    pub fn try_get_text<'a, I, T>(&'a self, idx: I) -> Result<T, Error>
+    where
+        I: RowIndex,
+        T: FromSqlText<'a>,
+    {
+        self.as_row_ref().try_get_text(idx)
+    }
+
+    /// Returns a borrowed view of the row.
+    pub fn as_row_ref(&self) -> RowRef<'_> {
+        RowRef::new(
+            self.columns(),
+            self.body.buffer(),
+            &self.ranges,
+            &self.formats,
+        )
+    }
+}
+
+/// A borrowed view of a row of data returned from the database by a query.
+///
+/// Unlike a `Row`, a `RowRef` does not own its data, so it can be created for each row of a query's results without
+/// allocating. See `Query::for_each_row` for details.
+#[derive(Copy, Clone)]
+pub struct RowRef<'a> {
+    columns: &'a [Column],
+    buf: &'a [u8],
+    ranges: &'a [Option<Range<usize>>],
+    formats: &'a [Format],
+}
+
+impl<'a> RowRef<'a> {
+    pub(crate) fn new(
+        columns: &'a [Column],
+        buf: &'a [u8],
+        ranges: &'a [Option<Range<usize>>],
+        formats: &'a [Format],
+    ) -> RowRef<'a> {
+        RowRef {
+            columns,
+            buf,
+            ranges,
+            formats,
+        }
+    }
+
+    /// Returns information about the columns of data in the row.
+    pub fn columns(&self) -> &'a [Column] {
+        self.columns
+    }
+
+    /// Determines if the row contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of values in the row.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the format in which the value at the specified index was returned by the database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn format(&self, idx: usize) -> Format {
+        assert!(idx < self.len(), "column index {} out of bounds", idx);
+        Format::at(self.formats, idx)
+    }
+
+    /// Deserializes a value from the row.
+    ///
+    /// Like `Row::get`, values can borrow from the row's data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds or if the value cannot be converted to the specified type.
+    pub fn get<I, T>(&self, idx: I) -> T
     where
         I: RowIndex + fmt::Display,
         T: FromSql<'a>,
@@ -146,8 +301,8 @@ impl Row {
         }
     }
 
-    /// Like `Row::get`, but returns a `Result` rather than panicking.
-    pub fn try_get<'a, I, T>(&'a self, idx: I) -> Result<T, Error>
+    /// Like `RowRef::get`, but returns a `Result` rather than panicking.
+    pub fn try_get<I, T>(&self, idx: I) -> Result<T, Error>
     where
         I: RowIndex,
         T: FromSql<'a>,
@@ -155,36 +310,176 @@ impl Row {
         self.get_inner(&idx)
     }
 
-    fn get_inner<'a, I, T>(&'a self, idx: &I) -> Result<T, Error>
+    fn get_inner<I, T>(&self, idx: &I) -> Result<T, Error>
     where
         I: RowIndex,
         T: FromSql<'a>,
     {
-        let idx = match idx.__idx(self.columns()) {
+        let idx = match idx.__idx(self.columns) {
             Some(idx) => idx,
-            None => return Err(Error::column()),
+            None => return Err(Error::column(idx.__display())),
         };
 
-        let ty = self.columns()[idx].type_();
+        self.get_raw(idx).map_err(|e| Error::from_sql(e, idx))
+    }
+
+    fn get_raw<T>(&self, idx: usize) -> Result<T, Box<dyn StdError + Sync + Send>>
+    where
+        T: FromSql<'a>,
+    {
+        let ty = self.columns[idx].type_();
+        let buf = self.ranges[idx].clone().map(|r| &self.buf[r]);
+
+        if self.format(idx) == Format::Text {
+            if !T::accepts(&Type::TEXT) {
+                return Err(format!(
+                    "cannot convert a text format value of type `{}` to a non-string type",
+                    ty
+                )
+                .into());
+            }
+            return FromSql::from_sql_nullable(&Type::TEXT, buf);
+        }
+
         if !T::accepts(ty) {
-            return Err(Error::from_sql(Box::new(WrongType::new(ty.clone())), idx));
+            return Err(Box::new(WrongType::new(ty.clone())));
         }
 
-        let buf = self.ranges[idx].clone().map(|r| &self.body.buffer()[r]);
-        FromSql::from_sql_nullable(ty, buf).map_err(|e| Error::from_sql(e, idx))
+        FromSql::from_sql_nullable(ty, buf)
+    }
+
+    /// Parses a value returned in the text format from the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds, if the value was not returned in the text format, or if the value cannot
+    /// be converted to the specified type.
+    pub fn get_text<I, T>(&self, idx: I) -> T
+    where
+        I: RowIndex + fmt::Display,
+        T: FromSqlText<'a>,
+    {
+        match self.get_text_inner(&idx) {
+            Ok(ok) => ok,
+            Err(err) => panic!("error retrieving column {}: {}", idx, err),
+        }
+    }
+
+    /// Like `RowRef::get_text`, but returns a `Result` rather than panicking.
+    pub fn try_get_text<I, T>(&self, idx: I) -> Result<T, Error>
+    where
+        I: RowIndex,
+        T: FromSqlText<'a>,
+    {
+        self.get_text_inner(&idx)
+    }
+
+    fn get_text_inner<I, T>(&self, idx: &I) -> Result<T, Error>
+    where
+        I: RowIndex,
+        T: FromSqlText<'a>,
+    {
+        let idx = match idx.__idx(self.columns) {
+            Some(idx) => idx,
+            None => return Err(Error::column(idx.__display())),
+        };
+
+        self.get_raw_text(idx).map_err(|e| Error::from_sql(e, idx))
+    }
+
+    fn get_raw_text<T>(&self, idx: usize) -> Result<T, Box<dyn StdError + Sync + Send>>
+    where
+        T: FromSqlText<'a>,
+    {
+        let ty = self.columns[idx].type_();
+        if !T::accepts(ty) {
+            return Err(Box::new(WrongType::new(ty.clone())));
+        }
+        if self.format(idx) != Format::Text {
+            return Err("value was not returned in the text format".into());
+        }
+
+        let buf = match self.ranges[idx].clone() {
+            Some(r) => Some(str::from_utf8(&self.buf[r])?),
+            None => None,
+        };
+        FromSqlText::from_sql_text_nullable(ty, buf)
+    }
+}
+
+/// A trait for types that can be created from a `Row`.
+///
+/// # Derive
+///
+/// If the `derive` Cargo feature is enabled, `FromRow` can be derived for structs with named fields. Each field is
+/// looked up by name in the row's columns and deserialized with `FromSql`, so a field of type `Option<T>` maps a `NULL`
+/// value to `None`. The following attributes are supported on fields:
+///
+/// * `#[postgres(name = "...")]` - reads the field from the column with the specified name rather than the field's name.
+/// * `#[postgres(default)]` - uses `Default::default()` for the field if the row has no column with its name.
+/// * `#[postgres(flatten)]` - builds the field from the same row via its own `FromRow` implementation.
+///
+/// Users of the synchronous `postgres` crate should add `#[postgres(crate = "postgres")]` to the struct.
+///
+/// ```ignore
+/// use tokio_postgres::FromRow;
+///
+/// #[derive(FromRow)]
+/// struct Person {
+///     id: i32,
+///     #[postgres(name = "full_name")]
+///     name: String,
+///     nickname: Option<String>,
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Creates a new value of this type from a row.
+    fn from_row(row: &Row) -> Result<Self, Error>;
+}
+
+// WARNING: this function is not considered part of this crate's public API.
+// It is subject to change at any time.
+#[doc(hidden)]
+pub fn __from_row_get<'a, T>(row: &'a Row, name: &str) -> Result<T, Error>
+where
+    T: FromSql<'a>,
+{
+    match __from_row_get_opt(row, name)? {
+        Some(value) => Ok(value),
+        None => Err(Error::column(name.to_string())),
+    }
+}
+
+// WARNING: this function is not considered part of this crate's public API.
+// It is subject to change at any time.
+#[doc(hidden)]
+pub fn __from_row_get_opt<'a, T>(row: &'a Row, name: &str) -> Result<Option<T>, Error>
+where
+    T: FromSql<'a>,
+{
+    match name.__idx(row.columns()) {
+        Some(idx) => row
+            .as_row_ref()
+            .get_raw(idx)
+            .map(Some)
+            .map_err(|e| Error::from_row(e, name)),
+        None => Ok(None),
     }
 }
 
 /// A row of data returned from the database by a simple query.
 pub struct SimpleQueryRow {
-    columns: Arc<[String]>,
+    columns: Arc<[SimpleColumn]>,
     body: DataRowBody,
     ranges: Vec<Option<Range<usize>>>,
 }
 
 impl SimpleQueryRow {
     #[allow(clippy::new_ret_no_self)]
-    pub(crate) fn new(columns: Arc<[String]>, body: DataRowBody) -> Result<SimpleQueryRow, Error> {
+    pub(crate) fn new(
+        columns: Arc<[SimpleColumn]>,
+        body: DataRowBody,
+    ) -> Result<SimpleQueryRow, Error> {
         let ranges = body.ranges().collect().map_err(Error::parse)?;
         Ok(SimpleQueryRow {
             columns,
@@ -193,6 +488,11 @@ impl SimpleQueryRow {
         })
     }
 
+    /// Returns information about the columns of data in the row.
+    pub fn columns(&self) -> &[SimpleColumn] {
+        &self.columns
+    }
+
     /// Determines if the row contains no values.
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -234,7 +534,7 @@ impl SimpleQueryRow {
     {
         let idx = match idx.__idx(&self.columns) {
             Some(idx) => idx,
-            None => return Err(Error::column()),
+            None => return Err(Error::column(idx.__display())),
         };
 
         let buf = self.ranges[idx].clone().map(|r| &self.body.buffer()[r]);
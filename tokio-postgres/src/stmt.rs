@@ -1,4 +1,4 @@
-use crate::types::Type;
+use crate::types::{Oid, Type};
 
 /// Information about a column of a Postgres query.
 #[derive(Debug)]
@@ -22,3 +22,29 @@ impl Column {
         &self.type_
     }
 }
+
+/// Information about a column of a result set returned by a simple query.
+///
+/// Values returned by the simple query protocol are always in the text format, so only the OID of the column's type is
+/// available rather than a full `Type`.
+#[derive(Debug, Clone)]
+pub struct SimpleColumn {
+    name: String,
+    type_oid: Oid,
+}
+
+impl SimpleColumn {
+    pub(crate) fn new(name: String, type_oid: Oid) -> SimpleColumn {
+        SimpleColumn { name, type_oid }
+    }
+
+    /// Returns the name of the column.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the OID of the type of the column.
+    pub fn type_oid(&self) -> Oid {
+        self.type_oid
+    }
+}
@@ -0,0 +1,166 @@
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, ArrayDimension};
+use std::error::Error;
+
+use crate::types::{FromSql, IsNull, Kind, ToSql, Type};
+
+/// A Postgres array with any number of dimensions.
+///
+/// Unlike `Vec<T>`, which always uses an index offset of 1, an `Array` preserves the length and lower bound of each
+/// of its dimensions. Its elements are stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Array<T> {
+    dimensions: Vec<ArrayDimension>,
+    elements: Vec<T>,
+}
+
+impl<T> Array<T> {
+    /// Creates a new array from its elements in row-major order and its dimensions.
+    ///
+    /// An array with no dimensions has no elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements does not match the dimensions.
+    pub fn from_parts(elements: Vec<T>, dimensions: Vec<ArrayDimension>) -> Array<T> {
+        let len = element_count(&dimensions).expect("invalid array dimensions");
+        assert!(
+            elements.len() == len,
+            "array has {} elements but its dimensions require {}",
+            elements.len(),
+            len
+        );
+
+        Array {
+            dimensions,
+            elements,
+        }
+    }
+
+    /// Creates a new one-dimensional array with the specified lower bound.
+    ///
+    /// Postgres represents an array with no elements as one with no dimensions, so an empty vector produces an array
+    /// with no dimensions.
+    pub fn from_vec(elements: Vec<T>, lower_bound: i32) -> Array<T> {
+        let dimensions = if elements.is_empty() {
+            vec![]
+        } else {
+            vec![ArrayDimension {
+                len: elements.len() as i32,
+                lower_bound,
+            }]
+        };
+        Array::from_parts(elements, dimensions)
+    }
+
+    /// Returns the dimensions of the array.
+    pub fn dimensions(&self) -> &[ArrayDimension] {
+        &self.dimensions
+    }
+
+    /// Returns the element at the specified indices, one for each dimension, taking the lower bound of each
+    /// dimension into account.
+    ///
+    /// Returns `None` if the number of indices doesn't match the number of dimensions or an index is out of bounds.
+    pub fn get(&self, indices: &[i32]) -> Option<&T> {
+        if indices.len() != self.dimensions.len() {
+            return None;
+        }
+
+        let mut offset = 0;
+        for (dimension, &index) in self.dimensions.iter().zip(indices) {
+            let index = index.checked_sub(dimension.lower_bound)?;
+            if index < 0 || index >= dimension.len {
+                return None;
+            }
+            offset = offset * dimension.len as usize + index as usize;
+        }
+        self.elements.get(offset)
+    }
+
+    /// Returns the elements of the array in row-major order.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the array, returning its elements in row-major order.
+    pub fn into_elements(self) -> Vec<T> {
+        self.elements
+    }
+}
+
+// The number of elements required by the dimensions, or `None` if a length is negative or the product overflows.
+fn element_count(dimensions: &[ArrayDimension]) -> Option<usize> {
+    if dimensions.is_empty() {
+        return Some(0);
+    }
+
+    dimensions.iter().try_fold(1usize, |acc, d| {
+        if d.len < 0 {
+            return None;
+        }
+        acc.checked_mul(d.len as usize)
+    })
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for Array<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Array<T>, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Array(ref member) => member,
+            _ => panic!("expected array type"),
+        };
+
+        let array = types::array_from_sql(raw)?;
+        let dimensions: Vec<ArrayDimension> = array.dimensions().collect()?;
+        let elements: Vec<T> = array
+            .values()
+            .map(|v| T::from_sql_nullable(member_type, v))
+            .collect()?;
+
+        if element_count(&dimensions) != Some(elements.len()) {
+            return Err("array element count does not match its dimensions".into());
+        }
+
+        Ok(Array {
+            dimensions,
+            elements,
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref member) => T::accepts(member),
+            _ => false,
+        }
+    }
+}
+
+impl<T: ToSql> ToSql for Array<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Array(ref member) => member,
+            _ => panic!("expected array type"),
+        };
+
+        types::array_to_sql(
+            self.dimensions.iter().cloned(),
+            member_type.oid(),
+            self.elements.iter(),
+            |e, w| match e.to_sql(member_type, w)? {
+                IsNull::No => Ok(postgres_protocol::IsNull::No),
+                IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+            },
+            w,
+        )?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Array(ref member) => T::accepts(member),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
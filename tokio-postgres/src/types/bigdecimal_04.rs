@@ -0,0 +1,35 @@
+use bigdecimal_04::num_bigint::{BigInt, Sign};
+use bigdecimal_04::BigDecimal;
+use std::error::Error;
+
+use crate::types::numeric::{self, Decimal};
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+impl<'a> FromSql<'a> for BigDecimal {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<BigDecimal, Box<dyn Error + Sync + Send>> {
+        let value = numeric::decimal_from_sql(raw)?;
+        let mut digits = BigInt::parse_bytes(&value.digits, 10).ok_or("invalid numeric digits")?;
+        if value.negative {
+            digits = -digits;
+        }
+        Ok(BigDecimal::new(digits, value.scale))
+    }
+
+    accepts!(NUMERIC);
+}
+
+impl ToSql for BigDecimal {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let (digits, scale) = self.as_bigint_and_exponent();
+        let value = Decimal {
+            negative: digits.sign() == Sign::Minus,
+            digits: digits.magnitude().to_str_radix(10).into_bytes(),
+            scale,
+        };
+        numeric::decimal_to_sql(&value, w)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+    to_sql_checked!();
+}
@@ -1,8 +1,9 @@
 use chrono_04::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use postgres_protocol::types;
+use std::convert::TryFrom;
 use std::error::Error;
 
-use crate::types::{FromSql, IsNull, ToSql, Type};
+use crate::types::{FromSql, Interval, IntervalError, IsNull, ToSql, Type};
 
 fn base() -> NaiveDateTime {
     NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)
@@ -147,3 +148,66 @@ impl ToSql for NaiveTime {
     accepts!(TIME);
     to_sql_checked!();
 }
+
+/// Converts a `Duration` into an interval with no month or day component.
+impl TryFrom<Duration> for Interval {
+    type Error = IntervalError;
+
+    fn try_from(duration: Duration) -> Result<Interval, IntervalError> {
+        match duration.num_microseconds() {
+            Some(microseconds) => Ok(Interval::new(0, 0, microseconds)),
+            None => Err(IntervalError("duration out of range for an interval")),
+        }
+    }
+}
+
+/// Converts an interval into a `Duration`, treating days as 24 hours long.
+///
+/// Intervals with a month component cannot be converted.
+impl TryFrom<Interval> for Duration {
+    type Error = IntervalError;
+
+    fn try_from(interval: Interval) -> Result<Duration, IntervalError> {
+        interval.total_microseconds().map(Duration::microseconds)
+    }
+}
+
+impl<'a> FromSql<'a> for (NaiveTime, FixedOffset) {
+    fn from_sql(
+        _: &Type,
+        raw: &[u8],
+    ) -> Result<(NaiveTime, FixedOffset), Box<dyn Error + Sync + Send>> {
+        let timetz = types::timetz_from_sql(raw)?;
+        // Postgres allows 24:00:00, which `NaiveTime` can't represent
+        let usec = timetz.time();
+        if !(0..86_400_000_000).contains(&usec) {
+            return Err("time out of range".into());
+        }
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(
+            (usec / 1_000_000) as u32,
+            (usec % 1_000_000 * 1000) as u32,
+        )
+        .ok_or("time out of range")?;
+        let offset = FixedOffset::east_opt(timetz.offset()).ok_or("invalid time zone offset")?;
+        Ok((time, offset))
+    }
+
+    accepts!(TIMETZ);
+}
+
+impl ToSql for (NaiveTime, FixedOffset) {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let delta = self
+            .0
+            .signed_duration_since(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
+        let time = match delta.num_microseconds() {
+            Some(time) => time,
+            None => return Err("value too large to transmit".into()),
+        };
+        types::timetz_to_sql(time, self.1.local_minus_utc(), w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMETZ);
+    to_sql_checked!();
+}
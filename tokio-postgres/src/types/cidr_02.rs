@@ -0,0 +1,43 @@
+use cidr_02::{IpCidr, IpInet};
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+impl<'a> FromSql<'a> for IpInet {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<IpInet, Box<dyn Error + Sync + Send>> {
+        let inet = types::inet_from_sql(raw)?;
+        Ok(IpInet::new(inet.addr(), inet.netmask())?)
+    }
+
+    accepts!(INET);
+}
+
+impl ToSql for IpInet {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::inet_to_sql(self.address(), self.network_length(), w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INET);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for IpCidr {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<IpCidr, Box<dyn Error + Sync + Send>> {
+        let inet = types::inet_from_sql(raw)?;
+        Ok(IpCidr::new(inet.addr(), inet.netmask())?)
+    }
+
+    accepts!(CIDR);
+}
+
+impl ToSql for IpCidr {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::cidr_to_sql(self.first_address(), self.network_length(), w)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(CIDR);
+    to_sql_checked!();
+}
@@ -1,5 +1,5 @@
 use fallible_iterator::FallibleIterator;
-use geo_types_04::{Coordinate, LineString, Point, Rect};
+use geo_types_04::{Coordinate, Line, LineString, Point, Polygon, Rect};
 use postgres_protocol::types;
 use std::error::Error;
 
@@ -75,3 +75,63 @@ impl ToSql for LineString<f64> {
     accepts!(PATH);
     to_sql_checked!();
 }
+
+impl<'a> FromSql<'a> for Line<f64> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let lseg = types::lseg_from_sql(raw)?;
+        Ok(Line::new(
+            Coordinate {
+                x: lseg.start().x(),
+                y: lseg.start().y(),
+            },
+            Coordinate {
+                x: lseg.end().x(),
+                y: lseg.end().y(),
+            },
+        ))
+    }
+
+    accepts!(LSEG);
+}
+
+impl ToSql for Line<f64> {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::lseg_to_sql(self.start.x, self.start.y, self.end.x, self.end.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LSEG);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Polygon<f64> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let polygon = types::polygon_from_sql(raw)?;
+        let points = polygon
+            .points()
+            .map(|p| Ok(Coordinate { x: p.x(), y: p.y() }))
+            .collect()?;
+        Ok(Polygon::new(LineString(points), vec![]))
+    }
+
+    accepts!(POLYGON);
+}
+
+impl ToSql for Polygon<f64> {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if !self.interiors().is_empty() {
+            return Err("Postgres polygons cannot have interior rings".into());
+        }
+
+        // geo_types closes the exterior ring by repeating its first point, but Postgres polygons are implicitly closed
+        let mut points = &self.exterior().0[..];
+        if points.len() > 1 && points.first() == points.last() {
+            points = &points[..points.len() - 1];
+        }
+        types::polygon_to_sql(points.iter().map(|p| (p.x, p.y)), out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(POLYGON);
+    to_sql_checked!();
+}
@@ -0,0 +1,106 @@
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+/// A Postgres `LINE` value, the infinite line satisfying `a*x + b*y + c = 0`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct PgLine {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl PgLine {
+    /// Creates a new line from the coefficients of its equation.
+    pub fn new(a: f64, b: f64, c: f64) -> PgLine {
+        PgLine { a, b, c }
+    }
+
+    /// Returns the `a` coefficient of the line's equation.
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    /// Returns the `b` coefficient of the line's equation.
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Returns the `c` coefficient of the line's equation.
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+}
+
+impl<'a> FromSql<'a> for PgLine {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgLine, Box<dyn Error + Sync + Send>> {
+        let line = types::line_from_sql(raw)?;
+        Ok(PgLine::new(line.a(), line.b(), line.c()))
+    }
+
+    accepts!(LINE);
+}
+
+impl ToSql for PgLine {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::line_to_sql(self.a, self.b, self.c, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LINE);
+    to_sql_checked!();
+}
+
+/// A Postgres `CIRCLE` value.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct PgCircle {
+    x: f64,
+    y: f64,
+    radius: f64,
+}
+
+impl PgCircle {
+    /// Creates a new circle from its center and radius.
+    pub fn new(x: f64, y: f64, radius: f64) -> PgCircle {
+        PgCircle { x, y, radius }
+    }
+
+    /// Returns the x coordinate of the circle's center.
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    /// Returns the y coordinate of the circle's center.
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    /// Returns the radius of the circle.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+impl<'a> FromSql<'a> for PgCircle {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgCircle, Box<dyn Error + Sync + Send>> {
+        let circle = types::circle_from_sql(raw)?;
+        Ok(PgCircle::new(
+            circle.center().x(),
+            circle.center().y(),
+            circle.radius(),
+        ))
+    }
+
+    accepts!(CIRCLE);
+}
+
+impl ToSql for PgCircle {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::circle_to_sql(self.x, self.y, self.radius, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(CIRCLE);
+    to_sql_checked!();
+}
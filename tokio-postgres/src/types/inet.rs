@@ -0,0 +1,76 @@
+use postgres_protocol::types;
+use std::error::Error;
+use std::net::IpAddr;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+/// A Postgres `INET` or `CIDR` value: an IP address along with the length of its network prefix.
+///
+/// A `CIDR` value describes a network rather than a host, so any bits of its address to the right of the netmask
+/// must be zero.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PgInet {
+    addr: IpAddr,
+    netmask: u8,
+}
+
+impl PgInet {
+    /// Creates a new value from an address and netmask length.
+    ///
+    /// The netmask is not validated until the value is sent to the database.
+    pub fn new(addr: IpAddr, netmask: u8) -> PgInet {
+        PgInet { addr, netmask }
+    }
+
+    /// Returns the IP address.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the netmask in bits.
+    pub fn netmask(&self) -> u8 {
+        self.netmask
+    }
+}
+
+/// Creates a value for a single host, with a netmask covering the entire address.
+impl From<IpAddr> for PgInet {
+    fn from(addr: IpAddr) -> PgInet {
+        let netmask = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        PgInet::new(addr, netmask)
+    }
+}
+
+impl<'a> FromSql<'a> for PgInet {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgInet, Box<dyn Error + Sync + Send>> {
+        let inet = types::inet_from_sql(raw)?;
+        Ok(PgInet::new(inet.addr(), inet.netmask()))
+    }
+
+    accepts!(INET, CIDR);
+}
+
+impl ToSql for PgInet {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if *ty == Type::CIDR {
+            types::cidr_to_sql(self.addr, self.netmask, w)?;
+            return Ok(IsNull::No);
+        }
+
+        let max = match self.addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if self.netmask > max {
+            return Err("invalid netmask".into());
+        }
+        types::inet_to_sql(self.addr, self.netmask, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INET, CIDR);
+    to_sql_checked!();
+}
@@ -0,0 +1,124 @@
+use postgres_protocol::types;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+const USEC_PER_SEC: i64 = 1_000_000;
+const USEC_PER_DAY: i64 = 86_400 * USEC_PER_SEC;
+
+/// A Postgres `INTERVAL` value.
+///
+/// Postgres stores the months, days, and time of an interval separately since the length of a month or day depends
+/// on the date it is applied to. For example, `'1 month'` and `'30 days'` are different intervals.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+impl Interval {
+    /// Creates a new interval.
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Interval {
+        Interval {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Returns the number of months in the interval.
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    /// Returns the number of days in the interval.
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the time part of the interval in microseconds.
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    // Days are treated as 24 hours long, but months have no fixed length so they can't be converted.
+    pub(crate) fn total_microseconds(&self) -> Result<i64, IntervalError> {
+        if self.months != 0 {
+            return Err(IntervalError(
+                "interval has a month component with no fixed length",
+            ));
+        }
+
+        i64::from(self.days)
+            .checked_mul(USEC_PER_DAY)
+            .and_then(|days| days.checked_add(self.microseconds))
+            .ok_or(IntervalError("interval out of range"))
+    }
+}
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Interval, Box<dyn Error + Sync + Send>> {
+        let interval = types::interval_from_sql(raw)?;
+        Ok(Interval {
+            months: interval.months(),
+            days: interval.days(),
+            microseconds: interval.microseconds(),
+        })
+    }
+
+    accepts!(INTERVAL);
+}
+
+impl ToSql for Interval {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::interval_to_sql(self.microseconds, self.days, self.months, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+
+/// Converts a `Duration` into an interval with no month or day component.
+///
+/// Precision beyond microseconds is truncated.
+impl TryFrom<Duration> for Interval {
+    type Error = IntervalError;
+
+    fn try_from(duration: Duration) -> Result<Interval, IntervalError> {
+        let microseconds = i64::try_from(duration.as_micros())
+            .map_err(|_| IntervalError("duration out of range for an interval"))?;
+        Ok(Interval::new(0, 0, microseconds))
+    }
+}
+
+/// Converts an interval into a `Duration`, treating days as 24 hours long.
+///
+/// Intervals with a month component or a negative length cannot be converted.
+impl TryFrom<Interval> for Duration {
+    type Error = IntervalError;
+
+    fn try_from(interval: Interval) -> Result<Duration, IntervalError> {
+        let microseconds = interval.total_microseconds()?;
+        if microseconds < 0 {
+            return Err(IntervalError("interval is negative"));
+        }
+        Ok(Duration::from_micros(microseconds as u64))
+    }
+}
+
+/// An error converting between an `Interval` and a duration type.
+#[derive(Debug)]
+pub struct IntervalError(pub(crate) &'static str);
+
+impl fmt::Display for IntervalError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.0)
+    }
+}
+
+impl Error for IntervalError {}
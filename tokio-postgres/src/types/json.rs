@@ -0,0 +1,72 @@
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+/// Writes the header of a `JSON` or `JSONB` value of the specified type.
+///
+/// The header must be followed by the value's JSON text. Along with `json_from_sql`, this allows `ToSql` and `FromSql`
+/// to be implemented for the types of JSON libraries without support built into this crate.
+pub fn json_header_to_sql(ty: &Type, out: &mut Vec<u8>) {
+    if *ty == Type::JSONB {
+        types::jsonb_header_to_sql(out);
+    }
+}
+
+/// Returns the JSON text of a `JSON` or `JSONB` value of the specified type.
+///
+/// The version header of a `JSONB` value is validated and removed.
+pub fn json_from_sql<'a>(
+    ty: &Type,
+    raw: &'a [u8],
+) -> Result<&'a [u8], Box<dyn Error + Sync + Send>> {
+    if *ty == Type::JSONB {
+        types::jsonb_from_sql(raw)
+    } else {
+        Ok(raw)
+    }
+}
+
+/// A Postgres `JSONPATH` value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgJsonPath(String);
+
+impl PgJsonPath {
+    /// Creates a new value from the text of a JSON path expression.
+    ///
+    /// The expression is not validated until the value is sent to the database.
+    pub fn new<S>(path: S) -> PgJsonPath
+    where
+        S: Into<String>,
+    {
+        PgJsonPath(path.into())
+    }
+
+    /// Returns the text of the JSON path expression.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the value, returning the text of the JSON path expression.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl<'a> FromSql<'a> for PgJsonPath {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgJsonPath, Box<dyn Error + Sync + Send>> {
+        types::jsonpath_from_sql(raw).map(PgJsonPath::new)
+    }
+
+    accepts!(JSONPATH);
+}
+
+impl ToSql for PgJsonPath {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::jsonpath_to_sql(&self.0, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(JSONPATH);
+    to_sql_checked!();
+}
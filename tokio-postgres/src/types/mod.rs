@@ -2,22 +2,38 @@
 
 use fallible_iterator::FallibleIterator;
 use postgres_protocol;
-use postgres_protocol::types::{self, ArrayDimension};
+use postgres_protocol::types::{self, ArrayValues};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::cmp;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
-use std::hash::BuildHasher;
+use std::hash::{BuildHasher, Hash};
 use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::types::type_gen::{Inner, Other};
 
+#[doc(inline)]
+pub use postgres_protocol::types::ArrayDimension;
 #[doc(inline)]
 pub use postgres_protocol::Oid;
 
+pub use crate::types::array::Array;
+pub use crate::types::geometry::{PgCircle, PgLine};
+pub use crate::types::inet::PgInet;
+pub use crate::types::interval::{Interval, IntervalError};
+pub use crate::types::json::{json_from_sql, json_header_to_sql, PgJsonPath};
+pub use crate::types::money::PgMoney;
+pub use crate::types::range::{PgMultirange, PgRange};
 pub use crate::types::special::{Date, Timestamp};
+pub use crate::types::text::{Format, FromSqlText};
+pub use crate::types::timetz::PgTimeTz;
+
+#[cfg(feature = "derive")]
+pub use postgres_derive::{FromSql, ToSql};
 
 // Number of seconds from 1970-01-01 to 2000-01-01
 const TIME_SEC_CONVERSION: u64 = 946_684_800;
@@ -44,16 +60,17 @@ macro_rules! accepts {
 #[macro_export]
 macro_rules! to_sql_checked {
     () => {
-        fn to_sql_checked(&self,
-                          ty: &$crate::types::Type,
-                          out: &mut ::std::vec::Vec<u8>)
-                          -> ::std::result::Result<$crate::types::IsNull,
-                                                   Box<dyn ::std::error::Error +
-                                                       ::std::marker::Sync +
-                                                       ::std::marker::Send>> {
+        fn to_sql_checked(
+            &self,
+            ty: &$crate::types::Type,
+            out: &mut ::std::vec::Vec<u8>,
+        ) -> ::std::result::Result<
+            $crate::types::IsNull,
+            Box<dyn ::std::error::Error + ::std::marker::Sync + ::std::marker::Send>,
+        > {
             $crate::types::__to_sql_checked(self, ty, out)
         }
-    }
+    };
 }
 
 // WARNING: this function is not considered part of this crate's public API.
@@ -73,20 +90,83 @@ where
     v.to_sql(ty, out)
 }
 
+// WARNING: this function is not considered part of this crate's public API.
+// It is subject to change at any time.
+#[doc(hidden)]
+pub fn __text_from_sql(buf: &[u8]) -> Result<&str, Box<dyn Error + Sync + Send>> {
+    types::text_from_sql(buf)
+}
+
+// WARNING: this function is not considered part of this crate's public API.
+// It is subject to change at any time.
+#[doc(hidden)]
+pub fn __read_be_i32(buf: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    if buf.len() < 4 {
+        return Err("invalid buffer size".into());
+    }
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(&buf[..4]);
+    *buf = &buf[4..];
+    Ok(i32::from_be_bytes(bytes))
+}
+
+// WARNING: this function is not considered part of this crate's public API.
+// It is subject to change at any time.
+#[doc(hidden)]
+pub fn __read_value<'a, T>(
+    type_: &Type,
+    buf: &mut &'a [u8],
+) -> Result<T, Box<dyn Error + Sync + Send>>
+where
+    T: FromSql<'a>,
+{
+    let len = __read_be_i32(buf)?;
+    let value = if len < 0 {
+        None
+    } else {
+        if len as usize > buf.len() {
+            return Err("invalid buffer size".into());
+        }
+        let (head, tail) = buf.split_at(len as usize);
+        *buf = tail;
+        Some(head)
+    };
+    T::from_sql_nullable(type_, value)
+}
+
+#[cfg(feature = "with-bigdecimal-0_4")]
+mod bigdecimal_04;
 #[cfg(feature = "with-bit-vec-0_5")]
 mod bit_vec_05;
 #[cfg(feature = "with-chrono-0_4")]
 mod chrono_04;
+#[cfg(feature = "with-cidr-0_2")]
+mod cidr_02;
 #[cfg(feature = "with-eui48-0_4")]
 mod eui48_04;
 #[cfg(feature = "with-geo-types-0_4")]
 mod geo_types_04;
+#[cfg(feature = "with-rust_decimal-1")]
+mod rust_decimal_1;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json_1;
+#[cfg(feature = "with-time-0_3")]
+mod time_03;
 #[cfg(feature = "with-uuid-0_7")]
 mod uuid_07;
 
+mod array;
+mod geometry;
+mod inet;
+mod interval;
+mod json;
+mod money;
+#[cfg(any(feature = "with-bigdecimal-0_4", feature = "with-rust_decimal-1"))]
+mod numeric;
+mod range;
 mod special;
+mod text;
+mod timetz;
 mod type_gen;
 
 #[cfg(feature = "with-serde_json-1")]
@@ -159,6 +239,8 @@ pub enum Kind {
     Array(Type),
     /// A range type along with the type of its elements.
     Range(Type),
+    /// A multirange type along with the type of its ranges.
+    Multirange(Type),
     /// A domain type along with its underlying type.
     Domain(Type),
     /// A composite type along with information about its fields.
@@ -249,7 +331,14 @@ impl WrongType {
 /// | `&[u8]`/`Vec<u8>`                 | BYTEA                                         |
 /// | `HashMap<String, Option<String>>` | HSTORE                                        |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE           |
+/// | `PgTimeTz`                        | TIME WITH TIME ZONE                           |
+/// | `Interval`                        | INTERVAL                                      |
+/// | `PgMoney`                         | MONEY                                         |
+/// | `PgJsonPath`                      | JSONPATH                                      |
+/// | `PgLine`                          | LINE                                          |
+/// | `PgCircle`                        | CIRCLE                                        |
 /// | `IpAddr`                          | INET                                          |
+/// | `PgInet`                          | INET, CIDR                                    |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -265,11 +354,23 @@ impl WrongType {
 /// | `chrono::DateTime<FixedOffset>` | TIMESTAMP WITH TIME ZONE            |
 /// | `chrono::NaiveDate`             | DATE                                |
 /// | `chrono::NaiveTime`             | TIME                                |
+/// | `(NaiveTime, FixedOffset)`      | TIME WITH TIME ZONE                 |
+/// | `cidr::IpInet`                  | INET                                |
+/// | `cidr::IpCidr`                  | CIDR                                |
 /// | `eui48::MacAddress`             | MACADDR                             |
 /// | `geo_types::Point<f64>`         | POINT                               |
 /// | `geo_types::Rect<f64>`          | BOX                                 |
 /// | `geo_types::LineString<f64>`    | PATH                                |
+/// | `geo_types::Line<f64>`          | LSEG                                |
+/// | `geo_types::Polygon<f64>`       | POLYGON                             |
+/// | `rust_decimal::Decimal`         | NUMERIC                             |
+/// | `bigdecimal::BigDecimal`        | NUMERIC                             |
 /// | `serde_json::Value`             | JSON, JSONB                         |
+/// | `&RawValue`/`Box<RawValue>`     | JSON, JSONB                         |
+/// | `time::PrimitiveDateTime`       | TIMESTAMP                           |
+/// | `time::OffsetDateTime`          | TIMESTAMP WITH TIME ZONE            |
+/// | `time::Date`                    | DATE                                |
+/// | `time::Time`                    | TIME                                |
 /// | `uuid::Uuid`                    | UUID                                |
 /// | `bit_vec::BitVec`               | BIT, VARBIT                         |
 /// | `eui48::MacAddress`             | MACADDR                             |
@@ -282,8 +383,32 @@ impl WrongType {
 ///
 /// # Arrays
 ///
-/// `FromSql` is implemented for `Vec<T>` where `T` implements `FromSql`, and
-/// corresponds to one-dimensional Postgres arrays.
+/// `FromSql` is implemented for `Vec<T>`, `Box<[T]>`, `[T; N]`, `HashSet<T>`
+/// and `BTreeSet<T>` where `T` implements `FromSql`, and corresponds to
+/// one-dimensional Postgres arrays. Nesting `Vec`s, boxed slices or fixed-size
+/// arrays, as in `Vec<Vec<T>>`, corresponds to multidimensional arrays with one
+/// level of nesting per dimension. An `[T; N]` must have exactly `N` elements.
+///
+/// `Array<T>` corresponds to arrays with any number of dimensions and preserves
+/// the lower bound of each dimension.
+///
+/// # Ranges
+///
+/// `FromSql` is implemented for `PgRange<T>` where `T` implements `FromSql`,
+/// and corresponds to Postgres range types with an element type of `T`.
+/// `PgMultirange<T>` corresponds to the multirange types of those ranges.
+///
+/// # Derive
+///
+/// If the `derive` Cargo feature is enabled, `FromSql` can be derived for Rust
+/// enums (mapping to Postgres enums), single field tuple structs (mapping to
+/// Postgres domains), and structs with named fields (mapping to Postgres
+/// composite types). The Postgres type, enum variant, and field names default
+/// to the Rust names, and can be overridden with `#[postgres(name = "...")]`.
+/// A single field tuple struct marked `#[postgres(transparent)]` is treated as
+/// a wrapper of its field's type rather than as a domain. A type marked
+/// `#[postgres(json)]` is converted with serde as if it were wrapped in `Json`,
+/// which requires the `with-serde_json-1` feature.
 pub trait FromSql<'a>: Sized {
     /// Creates a new value of this type from a buffer of data of the specified
     /// Postgres `Type` in its binary format.
@@ -318,6 +443,28 @@ pub trait FromSql<'a>: Sized {
     /// Determines if a value of this type can be created from the specified
     /// Postgres `Type`.
     fn accepts(ty: &Type) -> bool;
+
+    /// Creates a new value of this type from the values of a (possibly multidimensional) array with elements of the
+    /// specified Postgres `Type`, consuming as many values as the dimensions require.
+    ///
+    /// This is used internally by Rust-Postgres to support nested `Vec`s, and is only implemented by array-like types.
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    fn __from_sql_array(
+        ty: &Type,
+        dimensions: &[ArrayDimension],
+        values: &mut ArrayValues<'a>,
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Err("array contains too many dimensions".into())
+    }
+
+    /// Determines if a value of this type can be created by `__from_sql_array` from an array with elements of the
+    /// specified Postgres `Type`.
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    fn __accepts_array(ty: &Type) -> bool {
+        false
+    }
 }
 
 /// A trait for types which can be created from a Postgres value without borrowing any data.
@@ -349,22 +496,135 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Vec<T> {
         };
 
         let array = types::array_from_sql(raw)?;
-        if array.dimensions().count()? > 1 {
-            return Err("array contains too many dimensions".into());
+        let dimensions = array.dimensions().collect::<Vec<_>>()?;
+        if dimensions.is_empty() {
+            return Ok(vec![]);
         }
 
-        array
-            .values()
-            .map(|v| T::from_sql_nullable(member_type, v))
-            .collect()
+        let mut values = array.values();
+        let vec = Self::__from_sql_array(member_type, &dimensions, &mut values)?;
+        if values.next()?.is_some() {
+            return Err("array contains too many values".into());
+        }
+        Ok(vec)
     }
 
     fn accepts(ty: &Type) -> bool {
         match *ty.kind() {
-            Kind::Array(ref inner) => T::accepts(inner),
+            Kind::Array(ref inner) => Self::__accepts_array(inner),
             _ => false,
         }
     }
+
+    fn __from_sql_array(
+        ty: &Type,
+        dimensions: &[ArrayDimension],
+        values: &mut ArrayValues<'a>,
+    ) -> Result<Vec<T>, Box<dyn Error + Sync + Send>> {
+        let (dimension, rest) = match dimensions.split_first() {
+            Some(split) => split,
+            None => return Err("array contains too few dimensions".into()),
+        };
+        if rest.is_empty() && !T::accepts(ty) {
+            return Err("array contains too few dimensions".into());
+        }
+
+        let capacity = cmp::min(dimension.len.max(0) as usize, values.size_hint().0);
+        let mut vec = Vec::with_capacity(capacity);
+        for _ in 0..dimension.len {
+            let element = if rest.is_empty() {
+                match values.next()? {
+                    Some(v) => T::from_sql_nullable(ty, v)?,
+                    None => return Err("array contains too few values".into()),
+                }
+            } else {
+                T::__from_sql_array(ty, rest, values)?
+            };
+            vec.push(element);
+        }
+        Ok(vec)
+    }
+
+    fn __accepts_array(ty: &Type) -> bool {
+        T::accepts(ty) || T::__accepts_array(ty)
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for Box<[T]> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Box<[T]>, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_sql(ty, raw).map(Vec::into_boxed_slice)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
+
+    fn __from_sql_array(
+        ty: &Type,
+        dimensions: &[ArrayDimension],
+        values: &mut ArrayValues<'a>,
+    ) -> Result<Box<[T]>, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::__from_sql_array(ty, dimensions, values).map(Vec::into_boxed_slice)
+    }
+
+    fn __accepts_array(ty: &Type) -> bool {
+        Vec::<T>::__accepts_array(ty)
+    }
+}
+
+impl<'a, T: FromSql<'a>, const N: usize> FromSql<'a> for [T; N] {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<[T; N], Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_sql(ty, raw).and_then(vec_to_array)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
+
+    fn __from_sql_array(
+        ty: &Type,
+        dimensions: &[ArrayDimension],
+        values: &mut ArrayValues<'a>,
+    ) -> Result<[T; N], Box<dyn Error + Sync + Send>> {
+        Vec::<T>::__from_sql_array(ty, dimensions, values).and_then(vec_to_array)
+    }
+
+    fn __accepts_array(ty: &Type) -> bool {
+        Vec::<T>::__accepts_array(ty)
+    }
+}
+
+fn vec_to_array<T, const N: usize>(vec: Vec<T>) -> Result<[T; N], Box<dyn Error + Sync + Send>> {
+    let len = vec.len();
+    <[T; N]>::try_from(vec)
+        .map_err(|_| format!("expected an array of {} elements but found {}", N, len).into())
+}
+
+impl<'a, T, S> FromSql<'a> for HashSet<T, S>
+where
+    T: FromSql<'a> + Eq + Hash,
+    S: Default + BuildHasher,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<HashSet<T, S>, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_sql(ty, raw).map(|vec| vec.into_iter().collect())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
+}
+
+impl<'a, T> FromSql<'a> for BTreeSet<T>
+where
+    T: FromSql<'a> + Ord,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<BTreeSet<T>, Box<dyn Error + Sync + Send>> {
+        Vec::<T>::from_sql(ty, raw).map(|vec| vec.into_iter().collect())
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        Vec::<T>::accepts(ty)
+    }
 }
 
 impl<'a> FromSql<'a> for Vec<u8> {
@@ -508,7 +768,14 @@ pub enum IsNull {
 /// | `&[u8]`/Vec<u8>`                  | BYTEA                                |
 /// | `HashMap<String, Option<String>>` | HSTORE                               |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE  |
+/// | `PgTimeTz`                        | TIME WITH TIME ZONE                  |
+/// | `Interval`                        | INTERVAL                             |
+/// | `PgMoney`                         | MONEY                                |
+/// | `PgJsonPath`                      | JSONPATH                             |
+/// | `PgLine`                          | LINE                                 |
+/// | `PgCircle`                        | CIRCLE                               |
 /// | `IpAddr`                          | INET                                 |
+/// | `PgInet`                          | INET, CIDR                           |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -524,11 +791,23 @@ pub enum IsNull {
 /// | `chrono::DateTime<FixedOffset>` | TIMESTAMP WITH TIME ZONE            |
 /// | `chrono::NaiveDate`             | DATE                                |
 /// | `chrono::NaiveTime`             | TIME                                |
+/// | `(NaiveTime, FixedOffset)`      | TIME WITH TIME ZONE                 |
+/// | `cidr::IpInet`                  | INET                                |
+/// | `cidr::IpCidr`                  | CIDR                                |
 /// | `eui48::MacAddress`             | MACADDR                             |
 /// | `geo_types::Point<f64>`         | POINT                               |
 /// | `geo_types::Rect<f64>`          | BOX                                 |
 /// | `geo_types::LineString<f64>`    | PATH                                |
+/// | `geo_types::Line<f64>`          | LSEG                                |
+/// | `geo_types::Polygon<f64>`       | POLYGON                             |
+/// | `rust_decimal::Decimal`         | NUMERIC                             |
+/// | `bigdecimal::BigDecimal`        | NUMERIC                             |
 /// | `serde_json::Value`             | JSON, JSONB                         |
+/// | `&RawValue`/`Box<RawValue>`     | JSON, JSONB                         |
+/// | `time::PrimitiveDateTime`       | TIMESTAMP                           |
+/// | `time::OffsetDateTime`          | TIMESTAMP WITH TIME ZONE            |
+/// | `time::Date`                    | DATE                                |
+/// | `time::Time`                    | TIME                                |
 /// | `uuid::Uuid`                    | UUID                                |
 /// | `bit_vec::BitVec`               | BIT, VARBIT                         |
 /// | `eui48::MacAddress`             | MACADDR                             |
@@ -541,8 +820,33 @@ pub enum IsNull {
 ///
 /// # Arrays
 ///
-/// `ToSql` is implemented for `Vec<T>` and `&[T]` where `T` implements `ToSql`,
-/// and corresponds to one-dimensional Postgres arrays with an index offset of 1.
+/// `ToSql` is implemented for `Vec<T>`, `&[T]`, `Box<[T]>`, `[T; N]`,
+/// `HashSet<T>` and `BTreeSet<T>` where `T` implements `ToSql`, and corresponds
+/// to one-dimensional Postgres arrays with an index offset of 1. If `T` is
+/// itself one of those types, as in `Vec<Vec<T>>`, the value corresponds to a
+/// multidimensional array, and all of the nested arrays must have the same
+/// length.
+///
+/// `Array<T>` corresponds to arrays with any number of dimensions and any
+/// lower bounds.
+///
+/// # Ranges
+///
+/// `ToSql` is implemented for `PgRange<T>` where `T` implements `ToSql`, and
+/// corresponds to Postgres range types with an element type of `T`.
+/// `PgMultirange<T>` corresponds to the multirange types of those ranges.
+///
+/// # Derive
+///
+/// If the `derive` Cargo feature is enabled, `ToSql` can be derived for Rust
+/// enums (mapping to Postgres enums), single field tuple structs (mapping to
+/// Postgres domains), and structs with named fields (mapping to Postgres
+/// composite types). The Postgres type, enum variant, and field names default
+/// to the Rust names, and can be overridden with `#[postgres(name = "...")]`.
+/// A single field tuple struct marked `#[postgres(transparent)]` is treated as
+/// a wrapper of its field's type rather than as a domain. A type marked
+/// `#[postgres(json)]` is converted with serde as if it were wrapped in `Json`,
+/// which requires the `with-serde_json-1` feature.
 pub trait ToSql: fmt::Debug {
     /// Converts the value of `self` into the binary format of the specified
     /// Postgres `Type`, appending it to `out`.
@@ -606,37 +910,108 @@ impl<T: ToSql> ToSql for Option<T> {
 
 impl<'a, T: ToSql> ToSql for &'a [T] {
     fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
-        let member_type = match *ty.kind() {
-            Kind::Array(ref member) => member,
-            _ => panic!("expected array type"),
-        };
+        array_to_sql(ty, self.len(), self.iter(), w)?;
+        Ok(IsNull::No)
+    }
 
-        let dimension = ArrayDimension {
-            len: downcast(self.len())?,
-            lower_bound: 1,
-        };
+    fn accepts(ty: &Type) -> bool {
+        array_accepts::<T>(ty)
+    }
+
+    to_sql_checked!();
+}
 
-        types::array_to_sql(
+/// Serializes a one-dimensional array, or a multidimensional array if the elements are themselves arrays of the same
+/// type.
+fn array_to_sql<'a, T, I>(
+    ty: &Type,
+    len: usize,
+    elements: I,
+    w: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Sync + Send>>
+where
+    T: ToSql + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let member_type = match *ty.kind() {
+        Kind::Array(ref member) => member,
+        _ => panic!("expected array type"),
+    };
+
+    let dimension = ArrayDimension {
+        len: downcast(len)?,
+        lower_bound: 1,
+    };
+
+    if T::accepts(member_type) {
+        return types::array_to_sql(
             Some(dimension),
             member_type.oid(),
-            self.iter(),
+            elements,
             |e, w| match e.to_sql(member_type, w)? {
                 IsNull::No => Ok(postgres_protocol::IsNull::No),
                 IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
             },
             w,
-        )?;
-        Ok(IsNull::No)
+        );
     }
 
-    fn accepts(ty: &Type) -> bool {
-        match *ty.kind() {
-            Kind::Array(ref member) => T::accepts(member),
-            _ => false,
+    // The elements are arrays themselves, so serialize each one and splice their values together after checking
+    // that they all have the same shape.
+    let mut bufs = vec![];
+    for element in elements {
+        let mut buf = vec![];
+        if let IsNull::Yes = element.to_sql(ty, &mut buf)? {
+            return Err("multidimensional arrays cannot contain NULL sub-arrays".into());
         }
+        bufs.push(buf);
     }
 
-    to_sql_checked!();
+    let mut inner_dimensions = None;
+    let mut values = vec![];
+    for buf in &bufs {
+        let array = types::array_from_sql(buf)?;
+        let dimensions = array.dimensions().collect::<Vec<_>>()?;
+        match inner_dimensions {
+            Some(ref inner_dimensions) if *inner_dimensions != dimensions => {
+                return Err(
+                    "multidimensional arrays must have sub-arrays with matching dimensions".into(),
+                );
+            }
+            Some(_) => {}
+            None => inner_dimensions = Some(dimensions),
+        }
+        values.extend(array.values().collect::<Vec<_>>()?);
+    }
+
+    let mut dimensions = vec![dimension];
+    match inner_dimensions {
+        // Postgres represents an array with no elements as one with no dimensions
+        Some(ref inner_dimensions) if inner_dimensions.is_empty() => dimensions.clear(),
+        Some(inner_dimensions) => dimensions.extend(inner_dimensions),
+        None => {}
+    }
+
+    types::array_to_sql(
+        dimensions,
+        member_type.oid(),
+        values,
+        |v, w| match v {
+            Some(v) => {
+                w.extend_from_slice(v);
+                Ok(postgres_protocol::IsNull::No)
+            }
+            None => Ok(postgres_protocol::IsNull::Yes),
+        },
+        w,
+    )
+}
+
+fn array_accepts<T: ToSql>(ty: &Type) -> bool {
+    match *ty.kind() {
+        Kind::Array(ref member) => T::accepts(member) || T::accepts(ty),
+        _ => false,
+    }
 }
 
 impl<'a> ToSql for &'a [u8] {
@@ -662,6 +1037,59 @@ impl<T: ToSql> ToSql for Vec<T> {
     to_sql_checked!();
 }
 
+impl<T: ToSql> ToSql for Box<[T]> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        <&[T] as ToSql>::to_sql(&&**self, ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<T: ToSql, const N: usize> ToSql for [T; N] {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        <&[T] as ToSql>::to_sql(&&self[..], ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<T, S> ToSql for HashSet<T, S>
+where
+    T: ToSql,
+{
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        array_to_sql(ty, self.len(), self.iter(), w)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        array_accepts::<T>(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<T: ToSql> ToSql for BTreeSet<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        array_to_sql(ty, self.len(), self.iter(), w)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        array_accepts::<T>(ty)
+    }
+
+    to_sql_checked!();
+}
+
 impl ToSql for Vec<u8> {
     fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
         <&[u8] as ToSql>::to_sql(&&**self, ty, w)
@@ -0,0 +1,76 @@
+use postgres_protocol::types;
+use std::error::Error;
+use std::fmt;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+const DEFAULT_SCALE: u32 = 2;
+
+/// A Postgres `MONEY` value.
+///
+/// Postgres stores money as a count of the currency's smallest unit, and the number of those units in a whole unit
+/// is determined by the server's `lc_monetary` setting rather than by the value itself. Values read from the database
+/// are given a scale of 2, which matches most locales; use `with_scale` if the server is configured otherwise.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PgMoney {
+    amount: i64,
+    scale: u32,
+}
+
+impl PgMoney {
+    /// Creates a new value from an amount in the currency's smallest unit and the number of fractional digits of a
+    /// whole unit.
+    pub fn new(amount: i64, scale: u32) -> PgMoney {
+        PgMoney { amount, scale }
+    }
+
+    /// Returns the amount in the currency's smallest unit, for example cents.
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+
+    /// Returns the number of fractional digits of a whole unit of the currency.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Returns the same amount interpreted with a different scale.
+    pub fn with_scale(self, scale: u32) -> PgMoney {
+        PgMoney::new(self.amount, scale)
+    }
+}
+
+impl fmt::Display for PgMoney {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.amount < 0 {
+            fmt.write_str("-")?;
+        }
+        let scale = self.scale as usize;
+        let digits = format!("{:0width$}", self.amount.unsigned_abs(), width = scale + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        fmt.write_str(whole)?;
+        if !fraction.is_empty() {
+            write!(fmt, ".{}", fraction)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> FromSql<'a> for PgMoney {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgMoney, Box<dyn Error + Sync + Send>> {
+        let amount = types::money_from_sql(raw)?;
+        Ok(PgMoney::new(amount, DEFAULT_SCALE))
+    }
+
+    accepts!(MONEY);
+}
+
+impl ToSql for PgMoney {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::money_to_sql(self.amount, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(MONEY);
+    to_sql_checked!();
+}
@@ -0,0 +1,105 @@
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, NumericSign};
+use std::error::Error;
+
+/// A decimal value in a form which is easy to convert to and from the decimal types of third party crates.
+///
+/// The value is `(-1)^negative * digits * 10^-scale`, where `digits` is the ASCII base-10 representation of the
+/// value's magnitude.
+pub(crate) struct Decimal {
+    pub negative: bool,
+    pub digits: Vec<u8>,
+    pub scale: i64,
+}
+
+pub(crate) fn decimal_to_sql(
+    value: &Decimal,
+    buf: &mut Vec<u8>,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let first = value
+        .digits
+        .iter()
+        .position(|&d| d != b'0')
+        .unwrap_or(value.digits.len());
+    let digits = &value.digits[first..];
+
+    let scale = if value.scale < 0 { 0 } else { value.scale };
+    if scale > i64::from(u16::MAX) {
+        return Err("value out of range for NUMERIC".into());
+    }
+    let scale = scale as u16;
+
+    if digits.is_empty() {
+        types::numeric_to_sql(NumericSign::Positive, 0, scale, vec![], buf)?;
+        return Ok(());
+    }
+
+    // the power of 10 of the first digit, and the power of 10000 of the group containing it
+    let power = digits.len() as i64 - value.scale - 1;
+    let weight = power.div_euclid(4);
+
+    let mut groups = vec![];
+    for (i, &digit) in digits.iter().enumerate() {
+        let power = power - i as i64;
+        let group = (weight - power.div_euclid(4)) as usize;
+        if group == groups.len() {
+            groups.push(0);
+        }
+        groups[group] += i16::from(digit - b'0') * 10i16.pow(power.rem_euclid(4) as u32);
+    }
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+
+    if weight < i64::from(i16::MIN) || weight > i64::from(i16::MAX) {
+        return Err("value out of range for NUMERIC".into());
+    }
+    let sign = if value.negative {
+        NumericSign::Negative
+    } else {
+        NumericSign::Positive
+    };
+
+    types::numeric_to_sql(sign, weight as i16, scale, groups, buf)
+}
+
+pub(crate) fn decimal_from_sql(raw: &[u8]) -> Result<Decimal, Box<dyn Error + Sync + Send>> {
+    let numeric = types::numeric_from_sql(raw)?;
+    let negative = match numeric.sign() {
+        NumericSign::Positive => false,
+        NumericSign::Negative => true,
+        NumericSign::NaN => return Err("cannot convert NaN to a decimal".into()),
+        NumericSign::PositiveInfinity | NumericSign::NegativeInfinity => {
+            return Err("cannot convert an infinite value to a decimal".into())
+        }
+    };
+
+    let mut digits = vec![];
+    let mut count = 0;
+    let mut it = numeric.digits();
+    while let Some(group) = it.next()? {
+        digits.extend_from_slice(format!("{:04}", group).as_bytes());
+        count += 1;
+    }
+
+    // the digits are a multiple of 10000 to the power of the last group's weight
+    let mut scale = -4 * (i64::from(numeric.weight()) - count + 1);
+    let display_scale = i64::from(numeric.scale());
+    if scale < display_scale {
+        digits.resize(digits.len() + (display_scale - scale) as usize, b'0');
+        scale = display_scale;
+    }
+    while scale > display_scale && digits.last() == Some(&b'0') {
+        digits.pop();
+        scale -= 1;
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+
+    Ok(Decimal {
+        negative,
+        digits,
+        scale,
+    })
+}
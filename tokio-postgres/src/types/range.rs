@@ -0,0 +1,281 @@
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, Range, RangeBound};
+use std::error::Error;
+use std::ops::{self, Bound};
+
+use crate::types::{FromSql, IsNull, Kind, ToSql, Type};
+
+/// A Postgres range value, such as an `int4range`, `tstzrange`, or user-defined range type.
+///
+/// Each bound of a nonempty range is inclusive, exclusive, or unbounded. Postgres canonicalizes discrete ranges like
+/// `int4range` and `daterange` to an inclusive lower bound and exclusive upper bound, so a value read back from the
+/// database may not compare equal to the one written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PgRange<T> {
+    /// A range containing no values.
+    Empty,
+    /// A range with the specified lower and upper bounds.
+    Nonempty(Bound<T>, Bound<T>),
+}
+
+impl<T> PgRange<T> {
+    /// Creates a new range from its lower and upper bounds.
+    pub fn new(lower: Bound<T>, upper: Bound<T>) -> PgRange<T> {
+        PgRange::Nonempty(lower, upper)
+    }
+
+    /// Creates a new empty range.
+    pub fn empty() -> PgRange<T> {
+        PgRange::Empty
+    }
+
+    /// Determines if the range is `empty`.
+    pub fn is_empty(&self) -> bool {
+        match *self {
+            PgRange::Empty => true,
+            PgRange::Nonempty(..) => false,
+        }
+    }
+
+    /// Returns the lower bound of the range, or `None` if it is empty.
+    pub fn lower(&self) -> Option<Bound<&T>> {
+        match *self {
+            PgRange::Empty => None,
+            PgRange::Nonempty(ref lower, _) => Some(as_ref(lower)),
+        }
+    }
+
+    /// Returns the upper bound of the range, or `None` if it is empty.
+    pub fn upper(&self) -> Option<Bound<&T>> {
+        match *self {
+            PgRange::Empty => None,
+            PgRange::Nonempty(_, ref upper) => Some(as_ref(upper)),
+        }
+    }
+}
+
+impl<T> From<ops::Range<T>> for PgRange<T> {
+    fn from(range: ops::Range<T>) -> PgRange<T> {
+        PgRange::new(Bound::Included(range.start), Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<ops::RangeInclusive<T>> for PgRange<T> {
+    fn from(range: ops::RangeInclusive<T>) -> PgRange<T> {
+        let (start, end) = range.into_inner();
+        PgRange::new(Bound::Included(start), Bound::Included(end))
+    }
+}
+
+impl<T> From<ops::RangeFrom<T>> for PgRange<T> {
+    fn from(range: ops::RangeFrom<T>) -> PgRange<T> {
+        PgRange::new(Bound::Included(range.start), Bound::Unbounded)
+    }
+}
+
+impl<T> From<ops::RangeTo<T>> for PgRange<T> {
+    fn from(range: ops::RangeTo<T>) -> PgRange<T> {
+        PgRange::new(Bound::Unbounded, Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<ops::RangeToInclusive<T>> for PgRange<T> {
+    fn from(range: ops::RangeToInclusive<T>) -> PgRange<T> {
+        PgRange::new(Bound::Unbounded, Bound::Included(range.end))
+    }
+}
+
+impl<T> From<ops::RangeFull> for PgRange<T> {
+    fn from(_: ops::RangeFull) -> PgRange<T> {
+        PgRange::new(Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for PgRange<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<PgRange<T>, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Range(ref member) => member,
+            _ => panic!("expected range type"),
+        };
+
+        match types::range_from_sql(raw)? {
+            Range::Empty => Ok(PgRange::Empty),
+            Range::Nonempty(lower, upper) => {
+                let lower = bound_from_sql(member_type, lower)?;
+                let upper = bound_from_sql(member_type, upper)?;
+                Ok(PgRange::Nonempty(lower, upper))
+            }
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Range(ref member) => T::accepts(member),
+            _ => false,
+        }
+    }
+}
+
+impl<T: ToSql> ToSql for PgRange<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let member_type = match *ty.kind() {
+            Kind::Range(ref member) => member,
+            _ => panic!("expected range type"),
+        };
+
+        match *self {
+            PgRange::Empty => types::empty_range_to_sql(w),
+            PgRange::Nonempty(ref lower, ref upper) => types::range_to_sql(
+                |w| bound_to_sql(member_type, lower, w),
+                |w| bound_to_sql(member_type, upper, w),
+                w,
+            )?,
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Range(ref member) => T::accepts(member),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+/// A Postgres multirange value, such as an `int4multirange` or `tstzmultirange`.
+///
+/// Postgres sorts and merges the ranges of a multirange and discards empty ones, so a value read back from the database
+/// may not compare equal to the one written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgMultirange<T>(Vec<PgRange<T>>);
+
+impl<T> PgMultirange<T> {
+    /// Creates a new multirange from its ranges.
+    pub fn new(ranges: Vec<PgRange<T>>) -> PgMultirange<T> {
+        PgMultirange(ranges)
+    }
+
+    /// Returns the ranges of the multirange.
+    pub fn ranges(&self) -> &[PgRange<T>] {
+        &self.0
+    }
+
+    /// Consumes the multirange, returning its ranges.
+    pub fn into_ranges(self) -> Vec<PgRange<T>> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<PgRange<T>>> for PgMultirange<T> {
+    fn from(ranges: Vec<PgRange<T>>) -> PgMultirange<T> {
+        PgMultirange(ranges)
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for PgMultirange<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<PgMultirange<T>, Box<dyn Error + Sync + Send>> {
+        let range_type = match *ty.kind() {
+            Kind::Multirange(ref range) => range,
+            _ => panic!("expected multirange type"),
+        };
+        let member_type = match *range_type.kind() {
+            Kind::Range(ref member) => member,
+            _ => panic!("expected range type"),
+        };
+
+        types::multirange_from_sql(raw)?
+            .ranges()
+            .map(|range| match range {
+                Range::Empty => Ok(PgRange::Empty),
+                Range::Nonempty(lower, upper) => {
+                    let lower = bound_from_sql(member_type, lower)?;
+                    let upper = bound_from_sql(member_type, upper)?;
+                    Ok(PgRange::Nonempty(lower, upper))
+                }
+            })
+            .collect()
+            .map(PgMultirange)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Multirange(ref range) => <PgRange<T> as FromSql>::accepts(range),
+            _ => false,
+        }
+    }
+}
+
+impl<T: ToSql> ToSql for PgMultirange<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let range_type = match *ty.kind() {
+            Kind::Multirange(ref range) => range,
+            _ => panic!("expected multirange type"),
+        };
+
+        types::multirange_to_sql(
+            &self.0,
+            |range, w| range.to_sql(range_type, w).map(|_| ()),
+            w,
+        )?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty.kind() {
+            Kind::Multirange(ref range) => <PgRange<T> as ToSql>::accepts(range),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+fn as_ref<T>(bound: &Bound<T>) -> Bound<&T> {
+    match *bound {
+        Bound::Included(ref v) => Bound::Included(v),
+        Bound::Excluded(ref v) => Bound::Excluded(v),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+fn bound_from_sql<'a, T>(
+    ty: &Type,
+    bound: RangeBound<Option<&'a [u8]>>,
+) -> Result<Bound<T>, Box<dyn Error + Sync + Send>>
+where
+    T: FromSql<'a>,
+{
+    match bound {
+        RangeBound::Inclusive(v) => T::from_sql_nullable(ty, v).map(Bound::Included),
+        RangeBound::Exclusive(v) => T::from_sql_nullable(ty, v).map(Bound::Excluded),
+        RangeBound::Unbounded => Ok(Bound::Unbounded),
+    }
+}
+
+fn bound_to_sql<T>(
+    ty: &Type,
+    bound: &Bound<T>,
+    w: &mut Vec<u8>,
+) -> Result<RangeBound<postgres_protocol::IsNull>, Box<dyn Error + Sync + Send>>
+where
+    T: ToSql,
+{
+    let (v, inclusive) = match *bound {
+        Bound::Included(ref v) => (v, true),
+        Bound::Excluded(ref v) => (v, false),
+        Bound::Unbounded => return Ok(RangeBound::Unbounded),
+    };
+
+    let is_null = match v.to_sql(ty, w)? {
+        IsNull::No => postgres_protocol::IsNull::No,
+        IsNull::Yes => postgres_protocol::IsNull::Yes,
+    };
+    if inclusive {
+        Ok(RangeBound::Inclusive(is_null))
+    } else {
+        Ok(RangeBound::Exclusive(is_null))
+    }
+}
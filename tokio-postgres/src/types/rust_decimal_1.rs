@@ -0,0 +1,59 @@
+use rust_decimal_1::Decimal;
+use std::error::Error;
+use std::str;
+
+use crate::types::numeric::{self, Decimal as RawDecimal};
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+// rust_decimal stores a 96 bit mantissa and a scale of at most 28.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+const MAX_SCALE: i64 = 28;
+
+impl<'a> FromSql<'a> for Decimal {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Decimal, Box<dyn Error + Sync + Send>> {
+        let mut value = numeric::decimal_from_sql(raw)?;
+
+        while value.scale > MAX_SCALE && value.digits.last() == Some(&b'0') {
+            value.digits.pop();
+            value.scale -= 1;
+        }
+        if value.scale > MAX_SCALE {
+            return Err("value has too many fractional digits for Decimal".into());
+        }
+
+        let mut mantissa = str::from_utf8(&value.digits)?
+            .parse::<u128>()
+            .ok()
+            .filter(|&m| m <= MAX_MANTISSA)
+            .ok_or("value out of range for Decimal")?;
+        let mut scale = value.scale;
+        while scale < 0 {
+            mantissa = mantissa
+                .checked_mul(10)
+                .filter(|&m| m <= MAX_MANTISSA)
+                .ok_or("value out of range for Decimal")?;
+            scale += 1;
+        }
+
+        let mantissa = mantissa as i128;
+        let mantissa = if value.negative { -mantissa } else { mantissa };
+        Ok(Decimal::from_i128_with_scale(mantissa, scale as u32))
+    }
+
+    accepts!(NUMERIC);
+}
+
+impl ToSql for Decimal {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let value = RawDecimal {
+            negative: self.is_sign_negative(),
+            digits: self.mantissa().unsigned_abs().to_string().into_bytes(),
+            scale: i64::from(self.scale()),
+        };
+        numeric::decimal_to_sql(&value, w)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(NUMERIC);
+    to_sql_checked!();
+}
@@ -1,12 +1,16 @@
 use serde_1::{Deserialize, Serialize};
+use serde_json_1::value::RawValue;
 use serde_json_1::Value;
 use std::error::Error;
 use std::fmt::Debug;
-use std::io::Read;
+use std::str;
 
-use crate::types::{FromSql, IsNull, ToSql, Type};
+use crate::types::{json_from_sql, json_header_to_sql, FromSql, IsNull, ToSql, Type};
 
 /// A wrapper type to allow arbitrary `Serialize`/`Deserialize` types to convert to Postgres JSON values.
+///
+/// To avoid wrapping every value of a type, `ToSql` and `FromSql` can instead be derived for it with the
+/// `#[postgres(json)]` attribute, which converts it the same way.
 #[derive(Debug)]
 pub struct Json<T>(pub T);
 
@@ -14,15 +18,8 @@ impl<'a, T> FromSql<'a> for Json<T>
 where
     T: Deserialize<'a>,
 {
-    fn from_sql(ty: &Type, mut raw: &'a [u8]) -> Result<Json<T>, Box<dyn Error + Sync + Send>> {
-        if *ty == Type::JSONB {
-            let mut b = [0; 1];
-            raw.read_exact(&mut b)?;
-            // We only support version 1 of the jsonb binary format
-            if b[0] != 1 {
-                return Err("unsupported JSONB encoding version".into());
-            }
-        }
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Json<T>, Box<dyn Error + Sync + Send>> {
+        let raw = json_from_sql(ty, raw)?;
         serde_json_1::de::from_slice(raw)
             .map(Json)
             .map_err(Into::into)
@@ -36,9 +33,7 @@ where
     T: Serialize + Debug,
 {
     fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
-        if *ty == Type::JSONB {
-            out.push(1);
-        }
+        json_header_to_sql(ty, out);
         serde_json_1::ser::to_writer(out, &self.0)?;
         Ok(IsNull::No)
     }
@@ -63,3 +58,40 @@ impl ToSql for Value {
     accepts!(JSON, JSONB);
     to_sql_checked!();
 }
+
+impl<'a> FromSql<'a> for &'a RawValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<&'a RawValue, Box<dyn Error + Sync + Send>> {
+        let raw = str::from_utf8(json_from_sql(ty, raw)?)?;
+        serde_json_1::from_str(raw).map_err(Into::into)
+    }
+
+    accepts!(JSON, JSONB);
+}
+
+impl ToSql for &RawValue {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        json_header_to_sql(ty, out);
+        out.extend_from_slice(self.get().as_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(JSON, JSONB);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Box<RawValue> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Box<RawValue>, Box<dyn Error + Sync + Send>> {
+        <&RawValue>::from_sql(ty, raw).map(RawValue::to_owned)
+    }
+
+    accepts!(JSON, JSONB);
+}
+
+impl ToSql for Box<RawValue> {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        (&**self).to_sql(ty, out)
+    }
+
+    accepts!(JSON, JSONB);
+    to_sql_checked!();
+}
@@ -0,0 +1,163 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::types::{Type, WasNull};
+
+/// The format in which a value is transferred to or from the database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// The text format, as produced by the type's output function and consumed by its input function.
+    Text,
+    /// The binary format, as used by `ToSql` and `FromSql`.
+    #[default]
+    Binary,
+}
+
+impl Format {
+    /// Returns the format of the value at the specified index, following the protocol's rules: no formats means
+    /// binary, a single format applies to every value, and otherwise there is one format per value.
+    pub(crate) fn at(formats: &[Format], idx: usize) -> Format {
+        match formats {
+            [] => Format::Binary,
+            [format] => *format,
+            formats => formats[idx],
+        }
+    }
+
+    pub(crate) fn code(self) -> i16 {
+        match self {
+            Format::Text => 0,
+            Format::Binary => 1,
+        }
+    }
+}
+
+/// A trait for types that can be created from a Postgres value in its text format.
+///
+/// Values are returned in their text format when it is requested with `Client::query_with_formats` or
+/// `Client::bind_with_formats`. This allows the values of types without a binary `FromSql` implementation, such as
+/// `TSVECTOR` or the types of extensions, to be read from a `Row` with `Row::get_text`.
+///
+/// # Types
+///
+/// The following implementations are provided by this crate, along with the corresponding Postgres types:
+///
+/// | Rust type                     | Postgres type(s)      |
+/// |-------------------------------|-----------------------|
+/// | `&str`/`String`/`Box<str>`    | any                   |
+/// | `bool`                        | BOOL                  |
+/// | `i16`                         | SMALLINT, SMALLSERIAL |
+/// | `i32`                         | INT, SERIAL           |
+/// | `u32`                         | OID                   |
+/// | `i64`                         | BIGINT, BIGSERIAL     |
+/// | `f32`                         | REAL                  |
+/// | `f64`                         | DOUBLE PRECISION      |
+///
+/// In addition, `FromSqlText` is implemented for `Option<T>` where `T` implements `FromSqlText`. An `Option<T>`
+/// represents a nullable Postgres value.
+pub trait FromSqlText<'a>: Sized {
+    /// Creates a new value of this type from the text format of a value of the specified Postgres `Type`.
+    ///
+    /// The caller of this method is responsible for ensuring that this type is compatible with the Postgres `Type`.
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Self, Box<dyn Error + Sync + Send>>;
+
+    /// Creates a new value of this type from a `NULL` SQL value.
+    ///
+    /// The caller of this method is responsible for ensuring that this type is compatible with the Postgres `Type`.
+    ///
+    /// The default implementation returns `Err(Box::new(WasNull))`.
+    #[allow(unused_variables)]
+    fn from_sql_text_null(ty: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Err(Box::new(WasNull))
+    }
+
+    /// A convenience function that delegates to `from_sql_text` and `from_sql_text_null` depending on the value of
+    /// `raw`.
+    fn from_sql_text_nullable(
+        ty: &Type,
+        raw: Option<&'a str>,
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match raw {
+            Some(raw) => Self::from_sql_text(ty, raw),
+            None => Self::from_sql_text_null(ty),
+        }
+    }
+
+    /// Determines if a value of this type can be created from the specified Postgres `Type`.
+    fn accepts(ty: &Type) -> bool;
+}
+
+impl<'a, T: FromSqlText<'a>> FromSqlText<'a> for Option<T> {
+    fn from_sql_text(ty: &Type, raw: &'a str) -> Result<Option<T>, Box<dyn Error + Sync + Send>> {
+        T::from_sql_text(ty, raw).map(Some)
+    }
+
+    fn from_sql_text_null(_: &Type) -> Result<Option<T>, Box<dyn Error + Sync + Send>> {
+        Ok(None)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <T as FromSqlText<'_>>::accepts(ty)
+    }
+}
+
+impl<'a> FromSqlText<'a> for &'a str {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<&'a str, Box<dyn Error + Sync + Send>> {
+        Ok(raw)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+impl<'a> FromSqlText<'a> for String {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<String, Box<dyn Error + Sync + Send>> {
+        Ok(raw.to_string())
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+impl<'a> FromSqlText<'a> for Box<str> {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<Box<str>, Box<dyn Error + Sync + Send>> {
+        Ok(raw.into())
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+impl<'a> FromSqlText<'a> for bool {
+    fn from_sql_text(_: &Type, raw: &'a str) -> Result<bool, Box<dyn Error + Sync + Send>> {
+        match raw {
+            "t" => Ok(true),
+            "f" => Ok(false),
+            _ => Err("invalid boolean".into()),
+        }
+    }
+
+    accepts!(BOOL);
+}
+
+macro_rules! parse_from_text {
+    ($t:ty, $($expected:ident),+) => {
+        impl<'a> FromSqlText<'a> for $t {
+            fn from_sql_text(_: &Type, raw: &'a str) -> Result<$t, Box<dyn Error + Sync + Send>> {
+                <$t>::from_str(raw).map_err(Into::into)
+            }
+
+            accepts!($($expected),+);
+        }
+    }
+}
+
+parse_from_text!(i16, INT2);
+parse_from_text!(i32, INT4);
+parse_from_text!(u32, OID);
+parse_from_text!(i64, INT8);
+parse_from_text!(f32, FLOAT4);
+parse_from_text!(f64, FLOAT8);
@@ -0,0 +1,111 @@
+use postgres_protocol::types;
+use std::convert::TryFrom;
+use std::error::Error;
+use time_03::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+fn base() -> PrimitiveDateTime {
+    Date::from_calendar_date(2000, Month::January, 1)
+        .unwrap()
+        .midnight()
+}
+
+impl<'a> FromSql<'a> for PrimitiveDateTime {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PrimitiveDateTime, Box<dyn Error + Sync + Send>> {
+        let t = types::timestamp_from_sql(raw)?;
+        base()
+            .checked_add(Duration::microseconds(t))
+            .ok_or_else(|| "value too large to decode".into())
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl ToSql for PrimitiveDateTime {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let time = match i64::try_from((*self - base()).whole_microseconds()) {
+            Ok(time) => time,
+            Err(_) => return Err("value too large to transmit".into()),
+        };
+        types::timestamp_to_sql(time, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMP);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for OffsetDateTime {
+    fn from_sql(type_: &Type, raw: &[u8]) -> Result<OffsetDateTime, Box<dyn Error + Sync + Send>> {
+        let primitive = PrimitiveDateTime::from_sql(type_, raw)?;
+        Ok(primitive.assume_utc())
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl ToSql for OffsetDateTime {
+    fn to_sql(
+        &self,
+        type_: &Type,
+        w: &mut Vec<u8>,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let utc = self.to_offset(UtcOffset::UTC);
+        PrimitiveDateTime::new(utc.date(), utc.time()).to_sql(type_, w)
+    }
+
+    accepts!(TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Date {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Date, Box<dyn Error + Sync + Send>> {
+        let jd = types::date_from_sql(raw)?;
+        base()
+            .date()
+            .checked_add(Duration::days(i64::from(jd)))
+            .ok_or_else(|| "value too large to decode".into())
+    }
+
+    accepts!(DATE);
+}
+
+impl ToSql for Date {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let jd = (*self - base().date()).whole_days();
+        if jd > i64::from(i32::MAX) || jd < i64::from(i32::MIN) {
+            return Err("value too large to transmit".into());
+        }
+
+        types::date_to_sql(jd as i32, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(DATE);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Time {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Time, Box<dyn Error + Sync + Send>> {
+        let usec = types::time_from_sql(raw)?;
+        // Postgres allows 24:00:00, which `Time` can't represent
+        if !(0..86_400_000_000).contains(&usec) {
+            return Err("time out of range".into());
+        }
+        Ok(Time::MIDNIGHT + Duration::microseconds(usec))
+    }
+
+    accepts!(TIME);
+}
+
+impl ToSql for Time {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let delta = *self - Time::MIDNIGHT;
+        types::time_to_sql(delta.whole_microseconds() as i64, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIME);
+    to_sql_checked!();
+}
@@ -0,0 +1,48 @@
+use postgres_protocol::types;
+use std::error::Error;
+
+use crate::types::{FromSql, IsNull, ToSql, Type};
+
+/// A Postgres `TIMETZ` value: a time of day along with the offset of its time zone from UTC.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgTimeTz {
+    time: i64,
+    offset: i32,
+}
+
+impl PgTimeTz {
+    /// Creates a new value from the number of microseconds since midnight and the time zone's offset from UTC in
+    /// seconds, positive east of Greenwich.
+    pub fn new(time: i64, offset: i32) -> PgTimeTz {
+        PgTimeTz { time, offset }
+    }
+
+    /// Returns the number of microseconds since midnight.
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// Returns the time zone's offset from UTC in seconds, positive east of Greenwich.
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+impl<'a> FromSql<'a> for PgTimeTz {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgTimeTz, Box<dyn Error + Sync + Send>> {
+        let timetz = types::timetz_from_sql(raw)?;
+        Ok(PgTimeTz::new(timetz.time(), timetz.offset()))
+    }
+
+    accepts!(TIMETZ);
+}
+
+impl ToSql for PgTimeTz {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        types::timetz_to_sql(self.time, self.offset, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMETZ);
+    to_sql_checked!();
+}
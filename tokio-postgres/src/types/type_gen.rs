@@ -172,10 +172,25 @@ pub enum Inner {
     DateRangeArray,
     Int8Range,
     Int8RangeArray,
+    Jsonpath,
+    JsonpathArray,
     Regnamespace,
     RegnamespaceArray,
     Regrole,
     RegroleArray,
+    Int4Multirange,
+    NumMultirange,
+    TsMultirange,
+    TstzMultirange,
+    DateMultirange,
+    Int8Multirange,
+    AnyMultirange,
+    Int4MultirangeArray,
+    NumMultirangeArray,
+    TsMultirangeArray,
+    TstzMultirangeArray,
+    DateMultirangeArray,
+    Int8MultirangeArray,
     Other(Arc<Other>),
 }
 
@@ -341,10 +356,25 @@ impl Inner {
             3913 => Some(Inner::DateRangeArray),
             3926 => Some(Inner::Int8Range),
             3927 => Some(Inner::Int8RangeArray),
+            4072 => Some(Inner::Jsonpath),
+            4073 => Some(Inner::JsonpathArray),
             4089 => Some(Inner::Regnamespace),
             4090 => Some(Inner::RegnamespaceArray),
             4096 => Some(Inner::Regrole),
             4097 => Some(Inner::RegroleArray),
+            4451 => Some(Inner::Int4Multirange),
+            4532 => Some(Inner::NumMultirange),
+            4533 => Some(Inner::TsMultirange),
+            4534 => Some(Inner::TstzMultirange),
+            4535 => Some(Inner::DateMultirange),
+            4536 => Some(Inner::Int8Multirange),
+            4537 => Some(Inner::AnyMultirange),
+            6150 => Some(Inner::Int4MultirangeArray),
+            6151 => Some(Inner::NumMultirangeArray),
+            6152 => Some(Inner::TsMultirangeArray),
+            6153 => Some(Inner::TstzMultirangeArray),
+            6155 => Some(Inner::DateMultirangeArray),
+            6157 => Some(Inner::Int8MultirangeArray),
             _ => None,
         }
     }
@@ -510,10 +540,25 @@ impl Inner {
             Inner::DateRangeArray => 3913,
             Inner::Int8Range => 3926,
             Inner::Int8RangeArray => 3927,
+            Inner::Jsonpath => 4072,
+            Inner::JsonpathArray => 4073,
             Inner::Regnamespace => 4089,
             Inner::RegnamespaceArray => 4090,
             Inner::Regrole => 4096,
             Inner::RegroleArray => 4097,
+            Inner::Int4Multirange => 4451,
+            Inner::NumMultirange => 4532,
+            Inner::TsMultirange => 4533,
+            Inner::TstzMultirange => 4534,
+            Inner::DateMultirange => 4535,
+            Inner::Int8Multirange => 4536,
+            Inner::AnyMultirange => 4537,
+            Inner::Int4MultirangeArray => 6150,
+            Inner::NumMultirangeArray => 6151,
+            Inner::TsMultirangeArray => 6152,
+            Inner::TstzMultirangeArray => 6153,
+            Inner::DateMultirangeArray => 6155,
+            Inner::Int8MultirangeArray => 6157,
             Inner::Other(ref u) => u.oid,
         }
     }
@@ -679,10 +724,25 @@ impl Inner {
             Inner::DateRangeArray => &Kind::Array(Type(Inner::DateRange)),
             Inner::Int8Range => &Kind::Range(Type(Inner::Int8)),
             Inner::Int8RangeArray => &Kind::Array(Type(Inner::Int8Range)),
+            Inner::Jsonpath => &Kind::Simple,
+            Inner::JsonpathArray => &Kind::Array(Type(Inner::Jsonpath)),
             Inner::Regnamespace => &Kind::Simple,
             Inner::RegnamespaceArray => &Kind::Array(Type(Inner::Regnamespace)),
             Inner::Regrole => &Kind::Simple,
             Inner::RegroleArray => &Kind::Array(Type(Inner::Regrole)),
+            Inner::Int4Multirange => &Kind::Multirange(Type(Inner::Int4Range)),
+            Inner::NumMultirange => &Kind::Multirange(Type(Inner::NumRange)),
+            Inner::TsMultirange => &Kind::Multirange(Type(Inner::TsRange)),
+            Inner::TstzMultirange => &Kind::Multirange(Type(Inner::TstzRange)),
+            Inner::DateMultirange => &Kind::Multirange(Type(Inner::DateRange)),
+            Inner::Int8Multirange => &Kind::Multirange(Type(Inner::Int8Range)),
+            Inner::AnyMultirange => &Kind::Pseudo,
+            Inner::Int4MultirangeArray => &Kind::Array(Type(Inner::Int4Multirange)),
+            Inner::NumMultirangeArray => &Kind::Array(Type(Inner::NumMultirange)),
+            Inner::TsMultirangeArray => &Kind::Array(Type(Inner::TsMultirange)),
+            Inner::TstzMultirangeArray => &Kind::Array(Type(Inner::TstzMultirange)),
+            Inner::DateMultirangeArray => &Kind::Array(Type(Inner::DateMultirange)),
+            Inner::Int8MultirangeArray => &Kind::Array(Type(Inner::Int8Multirange)),
             Inner::Other(ref u) => &u.kind,
         }
     }
@@ -848,10 +908,25 @@ impl Inner {
             Inner::DateRangeArray => "_daterange",
             Inner::Int8Range => "int8range",
             Inner::Int8RangeArray => "_int8range",
+            Inner::Jsonpath => "jsonpath",
+            Inner::JsonpathArray => "_jsonpath",
             Inner::Regnamespace => "regnamespace",
             Inner::RegnamespaceArray => "_regnamespace",
             Inner::Regrole => "regrole",
             Inner::RegroleArray => "_regrole",
+            Inner::Int4Multirange => "int4multirange",
+            Inner::NumMultirange => "nummultirange",
+            Inner::TsMultirange => "tsmultirange",
+            Inner::TstzMultirange => "tstzmultirange",
+            Inner::DateMultirange => "datemultirange",
+            Inner::Int8Multirange => "int8multirange",
+            Inner::AnyMultirange => "anymultirange",
+            Inner::Int4MultirangeArray => "_int4multirange",
+            Inner::NumMultirangeArray => "_nummultirange",
+            Inner::TsMultirangeArray => "_tsmultirange",
+            Inner::TstzMultirangeArray => "_tstzmultirange",
+            Inner::DateMultirangeArray => "_datemultirange",
+            Inner::Int8MultirangeArray => "_int8multirange",
             Inner::Other(ref u) => &u.name,
         }
     }
@@ -1334,6 +1409,12 @@ impl Type {
     /// INT8RANGE&#91;&#93;
     pub const INT8_RANGE_ARRAY: Type = Type(Inner::Int8RangeArray);
 
+    /// JSONPATH - JSON path
+    pub const JSONPATH: Type = Type(Inner::Jsonpath);
+
+    /// JSONPATH&#91;&#93;
+    pub const JSONPATH_ARRAY: Type = Type(Inner::JsonpathArray);
+
     /// REGNAMESPACE - registered namespace
     pub const REGNAMESPACE: Type = Type(Inner::Regnamespace);
 
@@ -1345,4 +1426,43 @@ impl Type {
 
     /// REGROLE&#91;&#93;
     pub const REGROLE_ARRAY: Type = Type(Inner::RegroleArray);
+
+    /// INT4MULTIRANGE - multirange of integers
+    pub const INT4_MULTIRANGE: Type = Type(Inner::Int4Multirange);
+
+    /// NUMMULTIRANGE - multirange of numerics
+    pub const NUM_MULTIRANGE: Type = Type(Inner::NumMultirange);
+
+    /// TSMULTIRANGE - multirange of timestamps without time zone
+    pub const TS_MULTIRANGE: Type = Type(Inner::TsMultirange);
+
+    /// TSTZMULTIRANGE - multirange of timestamps with time zone
+    pub const TSTZ_MULTIRANGE: Type = Type(Inner::TstzMultirange);
+
+    /// DATEMULTIRANGE - multirange of dates
+    pub const DATE_MULTIRANGE: Type = Type(Inner::DateMultirange);
+
+    /// INT8MULTIRANGE - multirange of bigints
+    pub const INT8_MULTIRANGE: Type = Type(Inner::Int8Multirange);
+
+    /// ANYMULTIRANGE
+    pub const ANY_MULTIRANGE: Type = Type(Inner::AnyMultirange);
+
+    /// INT4MULTIRANGE&#91;&#93;
+    pub const INT4_MULTIRANGE_ARRAY: Type = Type(Inner::Int4MultirangeArray);
+
+    /// NUMMULTIRANGE&#91;&#93;
+    pub const NUM_MULTIRANGE_ARRAY: Type = Type(Inner::NumMultirangeArray);
+
+    /// TSMULTIRANGE&#91;&#93;
+    pub const TS_MULTIRANGE_ARRAY: Type = Type(Inner::TsMultirangeArray);
+
+    /// TSTZMULTIRANGE&#91;&#93;
+    pub const TSTZ_MULTIRANGE_ARRAY: Type = Type(Inner::TstzMultirangeArray);
+
+    /// DATEMULTIRANGE&#91;&#93;
+    pub const DATE_MULTIRANGE_ARRAY: Type = Type(Inner::DateMultirangeArray);
+
+    /// INT8MULTIRANGE&#91;&#93;
+    pub const INT8_MULTIRANGE_ARRAY: Type = Type(Inner::Int8MultirangeArray);
 }
@@ -0,0 +1,147 @@
+// These tests modify the process environment, so they run in their own test binary to avoid racing with the others.
+
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+use tokio_postgres::config::Config;
+
+fn check(s: &str, config: &Config) {
+    assert_eq!(Config::parse_with_env(s).expect(s), *config, "`{}`", s);
+}
+
+#[test]
+fn service_and_env() {
+    let path = env::temp_dir().join(format!("pg_service_{}.conf", process::id()));
+    fs::write(
+        &path,
+        "# comment\n\
+         [other]\n\
+         user=nobody\n\
+         \n\
+         [mydb]\n\
+         host=servicehost\n\
+         port=1234\n\
+         user=serviceuser\n\
+         user=ignored\n\
+         dbname=servicedb\n",
+    )
+    .unwrap();
+    env::set_var("PGSERVICEFILE", &path);
+
+    check(
+        "service=mydb user=foo",
+        Config::new()
+            .host("servicehost")
+            .port(1234)
+            .user("foo")
+            .dbname("servicedb"),
+    );
+    // a URL host without a port uses the service's
+    check(
+        "postgresql://otherhost/?service=mydb",
+        Config::new()
+            .host("otherhost")
+            .port(1234)
+            .user("serviceuser")
+            .dbname("servicedb"),
+    );
+    check(
+        "postgresql://otherhost:5433/?service=mydb",
+        Config::new()
+            .host("otherhost")
+            .port(5433)
+            .user("serviceuser")
+            .dbname("servicedb"),
+    );
+    assert!(Config::parse_with_env("service=missing").is_err());
+    // parsing without the environment doesn't read the service file
+    assert_eq!(
+        "service=missing user=foo".parse::<Config>().unwrap(),
+        *Config::new().user("foo"),
+    );
+
+    env::set_var("PGHOST", "envhost1,envhost2");
+    env::set_var("PGUSER", "envuser");
+    env::set_var("PGAPPNAME", "envapp");
+    env::set_var("PGCONNECT_TIMEOUT", "5");
+    assert_eq!(
+        Config::from_env().unwrap(),
+        *Config::new()
+            .host("envhost1")
+            .host("envhost2")
+            .user("envuser")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+    assert_eq!(
+        Config::parse_with_env("user=foo").unwrap(),
+        *Config::new()
+            .host("envhost1")
+            .host("envhost2")
+            .user("foo")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+    // the service takes precedence over the environment
+    env::set_var("PGSERVICE", "mydb");
+    assert_eq!(
+        Config::from_env().unwrap(),
+        *Config::new()
+            .host("servicehost")
+            .port(1234)
+            .user("serviceuser")
+            .dbname("servicedb")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+    // and the connection string over the service
+    assert_eq!(
+        Config::parse_with_env("host=strhost dbname=strdb").unwrap(),
+        *Config::new()
+            .host("strhost")
+            .port(1234)
+            .user("serviceuser")
+            .dbname("strdb")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+
+    env::remove_var("PGSERVICE");
+    env::set_var("PGPORT", "4321");
+    assert_eq!(
+        Config::parse_with_env("postgresql://strhost/").unwrap(),
+        *Config::new()
+            .host("strhost")
+            .port(4321)
+            .user("envuser")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+    assert_eq!(
+        Config::parse_with_env("postgresql://strhost:5433/").unwrap(),
+        *Config::new()
+            .host("strhost")
+            .port(5433)
+            .user("envuser")
+            .application_name("envapp")
+            .connect_timeout(Duration::from_secs(5)),
+    );
+
+    env::set_var("PGSSLMODE", "bogus");
+    assert!(Config::from_env().is_err());
+
+    for var in &[
+        "PGSERVICEFILE",
+        "PGSERVICE",
+        "PGHOST",
+        "PGPORT",
+        "PGUSER",
+        "PGAPPNAME",
+        "PGCONNECT_TIMEOUT",
+        "PGSSLMODE",
+    ] {
+        env::remove_var(var);
+    }
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,166 @@
+use futures::{Future, Stream};
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::binary_copy::{BinaryCopyInWriter, BinaryCopyOutStream};
+use tokio_postgres::types::Type;
+use tokio_postgres::Client;
+
+use crate::connect;
+
+fn setup(runtime: &mut Runtime) -> Client {
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(
+            client
+                .simple_query("CREATE TEMPORARY TABLE foo (id INT4, name TEXT)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    client
+}
+
+#[test]
+fn write_basic() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime);
+
+    let statement = runtime
+        .block_on(client.prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)"))
+        .unwrap();
+    let (writer, stream) = BinaryCopyInWriter::new(&[Type::INT4, Type::TEXT]);
+    let copy = client.copy_in(&statement, &[], stream);
+    let write = writer
+        .write(&[&1i32, &"foobar"])
+        .and_then(|writer| writer.write(&[&2i32, &None::<&str>]))
+        .and_then(|writer| writer.finish());
+    let (rows, ()) = runtime.block_on(copy.join(write)).unwrap();
+    assert_eq!(rows, 2);
+
+    let select = runtime
+        .block_on(client.prepare("SELECT id, name FROM foo ORDER BY id"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+    assert_eq!(rows[0].get::<_, Option<&str>>(1), Some("foobar"));
+    assert_eq!(rows[1].get::<_, i32>(0), 2);
+    assert_eq!(rows[1].get::<_, Option<&str>>(1), None);
+}
+
+#[test]
+fn write_many_rows() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime);
+
+    let statement = runtime
+        .block_on(client.prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)"))
+        .unwrap();
+    let (writer, stream) = BinaryCopyInWriter::new(&[Type::INT4, Type::TEXT]);
+    let copy = client.copy_in(&statement, &[], stream);
+    let write = futures::stream::iter_ok(0..10_000i32)
+        .fold(writer, |writer, i| {
+            writer.write(&[&i, &format!("the value for {}", i)])
+        })
+        .and_then(|writer| writer.finish());
+    let (rows, ()) = runtime.block_on(copy.join(write)).unwrap();
+    assert_eq!(rows, 10_000);
+
+    let select = runtime
+        .block_on(client.prepare("SELECT id, name FROM foo ORDER BY id"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, i32>(0), i as i32);
+        assert_eq!(row.get::<_, &str>(1), format!("the value for {}", i));
+    }
+}
+
+#[test]
+fn write_dropped() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime);
+
+    let statement = runtime
+        .block_on(client.prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)"))
+        .unwrap();
+    let (writer, stream) = BinaryCopyInWriter::new(&[Type::INT4, Type::TEXT]);
+    let copy = client.copy_in(&statement, &[], stream);
+    let write = writer.write(&[&1i32, &"foobar"]).map(drop);
+    runtime.block_on(copy.join(write)).unwrap_err();
+
+    let select = runtime
+        .block_on(client.prepare("SELECT id FROM foo"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn read_basic() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime);
+
+    runtime
+        .block_on(
+            client
+                .simple_query("INSERT INTO foo (id, name) VALUES (1, 'foobar'), (2, NULL)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("COPY foo (id, name) TO STDOUT (FORMAT binary)"))
+        .unwrap();
+    let stream = client.copy_out(&statement, &[]);
+    let rows = runtime
+        .block_on(BinaryCopyOutStream::new(stream, &[Type::INT4, Type::TEXT]).collect())
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+
+    assert_eq!(rows[0].get::<i32>(0), 1);
+    assert_eq!(rows[0].get::<Option<&str>>(1), Some("foobar"));
+    assert_eq!(rows[1].get::<i32>(0), 2);
+    assert_eq!(rows[1].get::<Option<&str>>(1), None);
+    assert!(rows[0].try_get::<&str>(0).is_err());
+    assert!(rows[0].try_get::<i32>(2).is_err());
+}
+
+#[test]
+fn read_many_rows() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime);
+
+    runtime
+        .block_on(
+            client
+                .simple_query(
+                    "INSERT INTO foo (id, name)
+                     SELECT i, 'the value for ' || i FROM generate_series(0, 9999) i",
+                )
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("COPY foo (id, name) TO STDOUT (FORMAT binary)"))
+        .unwrap();
+    let stream = client.copy_out(&statement, &[]);
+    let rows = runtime
+        .block_on(BinaryCopyOutStream::new(stream, &[Type::INT4, Type::TEXT]).collect())
+        .unwrap();
+    assert_eq!(rows.len(), 10_000);
+
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<i32>(0), i as i32);
+        assert_eq!(row.get::<&str>(1), format!("the value for {}", i));
+    }
+}
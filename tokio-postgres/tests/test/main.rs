@@ -3,8 +3,11 @@
 use futures::sync::mpsc;
 use futures::{future, stream, try_ready};
 use log::debug;
+use std::env;
 use std::error::Error;
 use std::fmt::Write;
+use std::fs;
+use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
@@ -14,12 +17,17 @@ use tokio::timer::Delay;
 use tokio_postgres::error::SqlState;
 use tokio_postgres::impls;
 use tokio_postgres::tls::NoTlsStream;
-use tokio_postgres::types::{Kind, Type};
-use tokio_postgres::{AsyncMessage, Client, Connection, NoTls, SimpleQueryMessage};
+use tokio_postgres::types::{Format, Kind, Type};
+use tokio_postgres::{AsyncMessage, Client, Connection, FromRow, NoTls, Row, SimpleQueryMessage};
 
+mod binary_copy;
 mod parse;
 #[cfg(feature = "runtime")]
+mod pool;
+mod replication;
+#[cfg(feature = "runtime")]
 mod runtime;
+mod statement_cache;
 mod types;
 
 fn connect(
@@ -81,6 +89,65 @@ fn plain_password_ok() {
     smoke_test("user=pass_user password=password dbname=postgres");
 }
 
+#[test]
+#[cfg(unix)]
+fn passfile() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let path = env::temp_dir().join(format!("pgpass_{}", process::id()));
+    fs::write(
+        &path,
+        "# comment\n\
+         localhost:5433:postgres:md5_user:wrong\n\
+         *:*:postgres:pass\\:user:wrong\n\
+         otherhost:*:*:*:wrong\n\
+         *:5433:*:md5_user:pass\\word\n\
+         *:*:postgres:pass_user:password\n",
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+    let connect = |s: &str| {
+        let mut config = s.parse::<tokio_postgres::Config>().unwrap();
+        config.passfile(&path);
+        TcpStream::connect(&"127.0.0.1:5433".parse().unwrap())
+            .map_err(|e| panic!("{}", e))
+            .and_then(move |s| config.connect_raw(s, NoTls))
+    };
+
+    let _ = runtime
+        .block_on(connect("user=pass_user dbname=postgres"))
+        .unwrap();
+    runtime
+        .block_on(connect(
+            "host=localhost port=5433 user=md5_user dbname=postgres",
+        ))
+        .err()
+        .unwrap();
+    let _ = runtime
+        .block_on(connect(
+            "host=somehost port=5433 user=md5_user dbname=postgres",
+        ))
+        .unwrap();
+    // the explicit password takes precedence
+    runtime
+        .block_on(connect("user=pass_user password=foo dbname=postgres"))
+        .err()
+        .unwrap();
+
+    // the file is ignored unless it is private to its owner
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    runtime
+        .block_on(connect("user=pass_user dbname=postgres"))
+        .err()
+        .unwrap();
+
+    fs::remove_file(&path).unwrap();
+}
+
 #[test]
 fn md5_password_missing() {
     let _ = env_logger::try_init();
@@ -189,6 +256,57 @@ fn insert_select() {
     runtime.block_on(tests).unwrap();
 }
 
+#[test]
+fn query_as() {
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        id: i32,
+        name: String,
+    }
+
+    impl FromRow for Person {
+        fn from_row(row: &Row) -> Result<Person, tokio_postgres::Error> {
+            Ok(Person {
+                id: row.try_get("id")?,
+                name: row.try_get("name")?,
+            })
+        }
+    }
+
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT * FROM (VALUES (1, 'alice'), (2, 'bob')) AS t (id, name)"))
+        .unwrap();
+    let people = runtime
+        .block_on(client.query_as::<Person>(&stmt, &[]))
+        .unwrap();
+    assert_eq!(
+        people,
+        vec![
+            Person {
+                id: 1,
+                name: "alice".to_string(),
+            },
+            Person {
+                id: 2,
+                name: "bob".to_string(),
+            },
+        ]
+    );
+
+    let stmt = runtime.block_on(client.prepare("SELECT 1 AS id")).unwrap();
+    let err = runtime
+        .block_on(client.query_as::<Person>(&stmt, &[]))
+        .unwrap_err();
+    assert_eq!(err.to_string(), "invalid column `name`");
+}
+
 #[test]
 fn query_portal() {
     let _ = env_logger::try_init();
@@ -233,6 +351,333 @@ fn query_portal() {
     assert_eq!(r3.len(), 0);
 }
 
+#[test]
+fn query_text_format() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let statement = runtime
+        .block_on(
+            client.prepare("SELECT to_tsvector('simple', $1), $2::INT4, $3::BOOL, NULL::INT8"),
+        )
+        .unwrap();
+    let rows = runtime
+        .block_on(
+            client
+                .query_with_formats(
+                    &statement,
+                    &[&"fat cats", &"42", &true],
+                    &[Format::Binary, Format::Text, Format::Binary],
+                    &[Format::Text],
+                )
+                .collect(),
+        )
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].format(0), Format::Text);
+    assert_eq!(rows[0].get::<_, &str>(0), "'cats':2 'fat':1");
+    assert_eq!(rows[0].get::<_, &str>(1), "42");
+    assert_eq!(rows[0].get_text::<_, i32>(1), 42);
+    assert!(rows[0].get_text::<_, bool>(2));
+    assert_eq!(rows[0].get_text::<_, Option<i64>>(3), None);
+
+    let err = rows[0].try_get::<_, i32>(1).unwrap_err();
+    assert!(err.to_string().contains("text format"), "{}", err);
+    let err = rows[0].try_get_text::<_, i64>(1).unwrap_err();
+    assert!(err.to_string().contains("cannot convert"), "{}", err);
+}
+
+#[test]
+fn query_mixed_formats() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("SELECT 1::INT4, 'a b'::TSVECTOR"))
+        .unwrap();
+    let rows = runtime
+        .block_on(
+            client
+                .query_with_formats(&statement, &[], &[], &[Format::Binary, Format::Text])
+                .collect(),
+        )
+        .unwrap();
+
+    assert_eq!(rows[0].format(0), Format::Binary);
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+    assert_eq!(rows[0].get_text::<_, String>(1), "'a' 'b'");
+
+    let err = rows[0].try_get_text::<_, i32>(0).unwrap_err();
+    assert!(err.to_string().contains("text format"), "{}", err);
+}
+
+#[test]
+fn query_portal_text_format() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.simple_query("BEGIN").for_each(|_| Ok(())))
+        .unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("SELECT generate_series(1, $1::INT4)"))
+        .unwrap();
+    let portal = runtime
+        .block_on(client.bind_with_formats(&statement, &[&"3"], &[Format::Text], &[Format::Text]))
+        .unwrap();
+
+    let rows = runtime
+        .block_on(client.query_portal(&portal, 0).collect())
+        .unwrap();
+    let values = rows
+        .iter()
+        .map(|r| r.get_text::<_, i32>(0))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn for_each_row() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare(
+            "SELECT i, repeat('a', i), NULLIF(i % 2, 0)::TEXT::BYTEA FROM generate_series(1, 3) i",
+        ))
+        .unwrap();
+
+    let mut rows = vec![];
+    runtime
+        .block_on(client.query(&statement, &[]).for_each_row(|row| {
+            assert_eq!(row.len(), 3);
+            let s: &str = row.try_get(1)?;
+            let b: Option<&[u8]> = row.try_get(2)?;
+            rows.push((row.get::<_, i32>(0), s.to_string(), b.map(|b| b.to_vec())));
+            Ok(())
+        }))
+        .unwrap();
+    assert_eq!(
+        rows,
+        [
+            (1, "a".to_string(), Some(b"1".to_vec())),
+            (2, "aa".to_string(), None),
+            (3, "aaa".to_string(), Some(b"1".to_vec())),
+        ]
+    );
+
+    let err = runtime
+        .block_on(
+            client
+                .query(&statement, &[])
+                .for_each_row(|row| row.try_get::<_, i64>(0).map(|_| ())),
+        )
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("error deserializing column 0"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn poll_row_ref() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.simple_query("BEGIN").for_each(|_| Ok(())))
+        .unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("SELECT 'row ' || generate_series(1, 3)"))
+        .unwrap();
+    let portal = runtime.block_on(client.bind(&statement, &[])).unwrap();
+
+    let mut query = client.query_portal(&portal, 2);
+    let mut next = || {
+        let f = future::poll_fn(|| query.poll_row_ref(|row| Ok(row.get::<_, &str>(0).len())));
+        runtime.block_on(f).unwrap()
+    };
+    assert_eq!(next(), Some(5));
+    assert_eq!(next(), Some(5));
+    assert_eq!(next(), None);
+
+    let mut values = vec![];
+    runtime
+        .block_on(client.query_portal(&portal, 0).for_each_row(|row| {
+            values.push(row.get::<_, String>(0));
+            Ok(())
+        }))
+        .unwrap();
+    assert_eq!(values, ["row 3"]);
+}
+
+#[test]
+fn pipeline() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(
+            client
+                .simple_query("CREATE TEMPORARY TABLE foo (id SERIAL, name TEXT)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let insert = runtime
+        .block_on(client.prepare("INSERT INTO foo (name) VALUES ($1)"))
+        .unwrap();
+    let select = runtime
+        .block_on(client.prepare("SELECT name FROM foo ORDER BY id"))
+        .unwrap();
+
+    let mut pipeline = client.pipeline();
+    pipeline
+        .execute(&insert, &[&"alice"])
+        .execute(&insert, &[&"bob"])
+        .query(&select, &[]);
+    assert_eq!(pipeline.len(), 3);
+    let results = runtime.block_on(pipeline.send()).unwrap();
+    assert!(pipeline.is_empty());
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap().rows_affected(), 1);
+    assert!(results[0].as_ref().unwrap().rows().is_empty());
+    assert_eq!(results[1].as_ref().unwrap().rows_affected(), 1);
+    let rows = results[2].as_ref().unwrap().rows();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<_, &str>(0), "alice");
+    assert_eq!(rows[1].get::<_, &str>(0), "bob");
+
+    let results = runtime.block_on(client.pipeline().send()).unwrap();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn pipeline_abort_on_error() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(
+            client
+                .simple_query("CREATE TEMPORARY TABLE foo (id INT PRIMARY KEY)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let insert = runtime
+        .block_on(client.prepare("INSERT INTO foo (id) VALUES ($1)"))
+        .unwrap();
+    let count = runtime
+        .block_on(client.prepare("SELECT COUNT(*) FROM foo"))
+        .unwrap();
+
+    let results = runtime
+        .block_on(
+            client
+                .pipeline()
+                .execute(&insert, &[&1i32])
+                .execute(&insert, &[&1i32])
+                .execute(&insert, &[&2i32])
+                .send(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    let err = results[1].as_ref().err().unwrap();
+    assert_eq!(err.code(), Some(&SqlState::UNIQUE_VIOLATION));
+    assert!(results[2].as_ref().err().unwrap().is_pipeline_aborted());
+
+    // the statements share an implicit transaction, which is rolled back
+    let rows = runtime
+        .block_on(client.query(&count, &[]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get::<_, i64>(0), 0);
+
+    let results = runtime
+        .block_on(
+            client
+                .pipeline()
+                .abort_on_error(false)
+                .execute(&insert, &[&1i32])
+                .execute(&insert, &[&1i32])
+                .query(&count, &[])
+                .send(),
+        )
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    let err = results[1].as_ref().err().unwrap();
+    assert_eq!(err.code(), Some(&SqlState::UNIQUE_VIOLATION));
+    let rows = results[2].as_ref().unwrap().rows();
+    assert_eq!(rows[0].get::<_, i64>(0), 1);
+}
+
+#[test]
+fn pipeline_serialization_error() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let statement = runtime.block_on(client.prepare("SELECT $1::INT4")).unwrap();
+    let err = match runtime.block_on(
+        client
+            .pipeline()
+            .query(&statement, &[&1i32])
+            .query(&statement, &[&"a"])
+            .send(),
+    ) {
+        Ok(_) => panic!("unexpected success"),
+        Err(e) => e,
+    };
+    assert!(
+        err.to_string().contains("error serializing parameter 0"),
+        "{}",
+        err
+    );
+
+    let rows = runtime
+        .block_on(client.query(&statement, &[&1i32]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+}
+
 #[test]
 fn cancel_query_raw() {
     let _ = env_logger::try_init();
@@ -832,6 +1277,69 @@ fn simple_query() {
     assert_eq!(messages.len(), 5);
 }
 
+#[test]
+fn simple_query_described() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let f = client
+        .simple_query_described(
+            "CREATE TEMPORARY TABLE foo (id SERIAL, name TEXT);
+            INSERT INTO foo (name) VALUES ('steven');
+            SELECT id, name FROM foo;
+            SELECT 1::INT8 AS a WHERE false;",
+        )
+        .collect();
+    let messages = runtime.block_on(f).unwrap();
+
+    match messages[0] {
+        SimpleQueryMessage::CommandComplete(0) => {}
+        _ => panic!("unexpected message"),
+    }
+    match messages[1] {
+        SimpleQueryMessage::CommandComplete(1) => {}
+        _ => panic!("unexpected message"),
+    }
+    match &messages[2] {
+        SimpleQueryMessage::RowDescription(columns) => {
+            assert_eq!(columns.len(), 2);
+            assert_eq!(columns[0].name(), "id");
+            assert_eq!(columns[0].type_oid(), Type::INT4.oid());
+            assert_eq!(columns[1].name(), "name");
+            assert_eq!(columns[1].type_oid(), Type::TEXT.oid());
+        }
+        _ => panic!("unexpected message"),
+    }
+    match &messages[3] {
+        SimpleQueryMessage::Row(row) => {
+            assert_eq!(row.columns()[1].name(), "name");
+            assert_eq!(row.get("name"), Some("steven"));
+        }
+        _ => panic!("unexpected message"),
+    }
+    match messages[4] {
+        SimpleQueryMessage::CommandComplete(1) => {}
+        _ => panic!("unexpected message"),
+    }
+    match &messages[5] {
+        SimpleQueryMessage::RowDescription(columns) => {
+            assert_eq!(columns.len(), 1);
+            assert_eq!(columns[0].name(), "a");
+            assert_eq!(columns[0].type_oid(), Type::INT8.oid());
+        }
+        _ => panic!("unexpected message"),
+    }
+    match messages[6] {
+        SimpleQueryMessage::CommandComplete(0) => {}
+        _ => panic!("unexpected message"),
+    }
+    assert_eq!(messages.len(), 7);
+}
+
 #[test]
 fn poll_idle_running() {
     struct DelayStream(Delay);
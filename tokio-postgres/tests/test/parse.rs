@@ -1,5 +1,5 @@
 use std::time::Duration;
-use tokio_postgres::config::{Config, TargetSessionAttrs};
+use tokio_postgres::config::{Config, ReplicationMode, SslMode, TargetSessionAttrs};
 
 fn check(s: &str, config: &Config) {
     assert_eq!(s.parse::<Config>().expect(s), *config, "`{}`", s);
@@ -36,6 +36,42 @@ fn settings() {
     );
 }
 
+#[test]
+fn ssl() {
+    check(
+        "sslmode=verify-full sslrootcert=root.crt sslcert=client.crt sslkey=client.key \
+         sslpassword=hunter2 sslcrl=root.crl sslsni=0",
+        Config::new()
+            .ssl_mode(SslMode::VerifyFull)
+            .ssl_root_cert("root.crt")
+            .ssl_cert("client.crt")
+            .ssl_key("client.key")
+            .ssl_password("hunter2")
+            .ssl_crl("root.crl")
+            .ssl_sni(false),
+    );
+    check("sslmode=allow", Config::new().ssl_mode(SslMode::Allow));
+    check(
+        "sslmode=verify-ca",
+        Config::new().ssl_mode(SslMode::VerifyCa),
+    );
+    assert!("sslmode=foo".parse::<Config>().is_err());
+}
+
+#[test]
+fn replication() {
+    check(
+        "replication=database",
+        Config::new().replication_mode(ReplicationMode::Logical),
+    );
+    check(
+        "replication=true",
+        Config::new().replication_mode(ReplicationMode::Physical),
+    );
+    check("replication=false", &Config::new());
+    assert!("replication=foo".parse::<Config>().is_err());
+}
+
 #[test]
 fn url() {
     check("postgresql://", &Config::new());
@@ -88,6 +124,10 @@ fn url() {
         "postgresql:///mydb?host=localhost&port=5433",
         Config::new().dbname("mydb").host("localhost").port(5433),
     );
+    check(
+        "postgresql://localhost/mydb?port=5433",
+        Config::new().host("localhost").dbname("mydb").port(5433),
+    );
     check(
         "postgresql://[2001:db8::1234]/database",
         Config::new()
@@ -0,0 +1,143 @@
+use futures::{Future, Stream};
+use std::time::Duration;
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::pool::{Builder, Pool, Recycle};
+use tokio_postgres::{NoTls, SimpleQueryMessage};
+
+fn pool(builder: &Builder) -> Pool<NoTls> {
+    let config = "host=localhost port=5433 user=postgres".parse().unwrap();
+    builder.build(config, NoTls)
+}
+
+#[test]
+fn reuse() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(&Builder::new());
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    let pid = runtime
+        .block_on(
+            client
+                .simple_query("SELECT pg_backend_pid()")
+                .filter_map(|m| match m {
+                    SimpleQueryMessage::Row(row) => Some(row.get(0).unwrap().to_string()),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .unwrap();
+    drop(client);
+
+    let state = pool.state();
+    assert_eq!(state.connections, 1);
+    assert_eq!(state.idle_connections, 1);
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    let pid2 = runtime
+        .block_on(
+            client
+                .simple_query("SELECT pg_backend_pid()")
+                .filter_map(|m| match m {
+                    SimpleQueryMessage::Row(row) => Some(row.get(0).unwrap().to_string()),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .unwrap();
+    assert_eq!(pid, pid2);
+}
+
+#[test]
+fn checkout_timeout() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(
+        Builder::new()
+            .max_size(1)
+            .checkout_timeout(Some(Duration::from_millis(100))),
+    );
+
+    let client = runtime.block_on(pool.get()).unwrap();
+    let err = runtime.block_on(pool.get()).err().unwrap();
+    assert_eq!(err.to_string(), "timed out waiting for a connection");
+
+    drop(client);
+    runtime.block_on(pool.get()).unwrap();
+    assert_eq!(pool.state().connections, 1);
+}
+
+#[test]
+fn waiter_receives_returned_connection() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(Builder::new().max_size(1));
+
+    let client = runtime.block_on(pool.get()).unwrap();
+    let checkout = pool.get().map(|_| ());
+    let release = futures::future::lazy(move || {
+        drop(client);
+        Ok(())
+    });
+    runtime.block_on(checkout.join(release)).unwrap();
+    assert_eq!(pool.state().connections, 1);
+}
+
+#[test]
+fn recycle() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(Builder::new().max_size(1).recycle(Recycle::DiscardAll));
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    runtime
+        .block_on(
+            client
+                .simple_query("CREATE TEMPORARY TABLE foo (id INT)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+    drop(client);
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    runtime
+        .block_on(
+            client
+                .simple_query("CREATE TEMPORARY TABLE foo (id INT)")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+}
+
+#[test]
+fn discard_closed() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(Builder::new().max_size(1));
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    let _ = runtime.block_on(
+        client
+            .simple_query("SELECT pg_terminate_backend(pg_backend_pid())")
+            .for_each(|_| Ok(())),
+    );
+    drop(client);
+
+    let mut client = runtime.block_on(pool.get()).unwrap();
+    runtime
+        .block_on(client.simple_query("SELECT 1").for_each(|_| Ok(())))
+        .unwrap();
+}
+
+#[test]
+fn min_size() {
+    let mut runtime = Runtime::new().unwrap();
+    let pool = pool(Builder::new().min_size(3));
+
+    let client = runtime.block_on(pool.get()).unwrap();
+    drop(client);
+    runtime
+        .block_on(tokio::timer::Delay::new(
+            std::time::Instant::now() + Duration::from_millis(500),
+        ))
+        .unwrap();
+
+    let state = pool.state();
+    assert_eq!(state.connections, 3);
+    assert_eq!(state.idle_connections, 3);
+}
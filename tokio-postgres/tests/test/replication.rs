@@ -0,0 +1,495 @@
+use futures::{future, Future, Sink, Stream};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+use tokio::net::TcpStream;
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::replication::{
+    BaseBackupMessage, LogicalReplicationMessage, LogicalReplicationStream, ReplicaIdentity,
+    ReplicationClient, ReplicationMessage, TupleData,
+};
+use tokio_postgres::{Client, Config, NoTls, SimpleQueryMessage};
+
+use crate::connect;
+
+fn setup(runtime: &mut Runtime, s: &str) -> Client {
+    let (client, connection) = runtime.block_on(connect(s)).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+    client
+}
+
+fn message(tag: u8, body: &[u8]) -> Vec<u8> {
+    let mut buf = vec![tag];
+    buf.extend_from_slice(&(body.len() as i32 + 4).to_be_bytes());
+    buf.extend_from_slice(body);
+    buf
+}
+
+fn read_message(stream: &mut impl Read) -> u8 {
+    let mut header = [0; 5];
+    stream.read_exact(&mut header).unwrap();
+    let len = i32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+    let mut body = vec![0; len as usize - 4];
+    stream.read_exact(&mut body).unwrap();
+    header[0]
+}
+
+// Starts a server which answers the first query by entering copy both mode, sending `copy` as CopyData messages, and
+// then ending the copy before the client does. Once the client has ended its half, `results` are sent.
+fn copy_done_server(copy: Vec<Vec<u8>>, results: Vec<u8>) -> (SocketAddr, JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+
+        let mut len = [0; 4];
+        stream.read_exact(&mut len).unwrap();
+        let mut startup = vec![0; i32::from_be_bytes(len) as usize - 4];
+        stream.read_exact(&mut startup).unwrap();
+        stream.write_all(&message(b'R', &[0, 0, 0, 0])).unwrap();
+        stream.write_all(&message(b'Z', b"I")).unwrap();
+
+        assert_eq!(read_message(&mut stream), b'Q');
+        stream.write_all(&message(b'W', &[0, 0, 0])).unwrap();
+        for data in copy {
+            stream.write_all(&message(b'd', &data)).unwrap();
+        }
+        stream.write_all(&message(b'c', &[])).unwrap();
+
+        assert_eq!(read_message(&mut stream), b'c');
+        stream.write_all(&results).unwrap();
+        stream.write_all(&message(b'Z', b"I")).unwrap();
+
+        assert_eq!(read_message(&mut stream), b'X');
+    });
+
+    (addr, server)
+}
+
+fn setup_raw(runtime: &mut Runtime, addr: SocketAddr) -> Client {
+    let config = "user=postgres".parse::<Config>().unwrap();
+    let connect = TcpStream::connect(&addr)
+        .map_err(|e| panic!("{}", e))
+        .and_then(move |s| config.connect_raw(s, NoTls));
+    let (client, connection) = runtime.block_on(connect).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+    client
+}
+
+fn simple_query(runtime: &mut Runtime, client: &mut Client, query: &str) -> Vec<Vec<String>> {
+    runtime
+        .block_on(
+            client
+                .simple_query(query)
+                .filter_map(|m| match m {
+                    SimpleQueryMessage::Row(row) => Some(
+                        (0..row.len())
+                            .map(|i| row.get(i).unwrap_or("").to_string())
+                            .collect(),
+                    ),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .unwrap()
+}
+
+fn next(
+    runtime: &mut Runtime,
+    stream: LogicalReplicationStream,
+) -> (
+    ReplicationMessage<LogicalReplicationMessage>,
+    LogicalReplicationStream,
+) {
+    let (message, stream) = runtime
+        .block_on(stream.into_future().map_err(|(e, _)| e))
+        .unwrap();
+    (message.unwrap(), stream)
+}
+
+fn next_change(
+    runtime: &mut Runtime,
+    mut stream: LogicalReplicationStream,
+) -> (LogicalReplicationMessage, LogicalReplicationStream) {
+    loop {
+        let (message, s) = next(runtime, stream);
+        stream = s;
+        if let ReplicationMessage::XLogData(body) = message {
+            return (body.into_data(), stream);
+        }
+    }
+}
+
+fn text(data: &TupleData) -> Option<&str> {
+    match data {
+        TupleData::Text(buf) => Some(std::str::from_utf8(buf).unwrap()),
+        TupleData::Null => None,
+        _ => panic!("unexpected tuple data"),
+    }
+}
+
+#[test]
+fn logical() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = setup(&mut runtime, "user=postgres");
+    let mut repl_client = setup(&mut runtime, "user=postgres replication=database");
+
+    simple_query(
+        &mut runtime,
+        &mut client,
+        "DROP PUBLICATION IF EXISTS replication_logical;
+         DROP TABLE IF EXISTS replication_logical;
+         CREATE TABLE replication_logical (id INT4 PRIMARY KEY, name TEXT);
+         CREATE PUBLICATION replication_logical FOR TABLE replication_logical;",
+    );
+
+    let slot = simple_query(
+        &mut runtime,
+        &mut repl_client,
+        "CREATE_REPLICATION_SLOT replication_logical TEMPORARY LOGICAL pgoutput",
+    );
+    assert_eq!(slot[0][0], "replication_logical");
+
+    simple_query(
+        &mut runtime,
+        &mut client,
+        "INSERT INTO replication_logical (id, name) VALUES (1, 'alice');
+         UPDATE replication_logical SET name = NULL WHERE id = 1;
+         DELETE FROM replication_logical WHERE id = 1;
+         TRUNCATE replication_logical;",
+    );
+
+    let copy = repl_client.copy_both_simple(
+        "START_REPLICATION SLOT replication_logical LOGICAL 0/0 \
+         (proto_version '1', publication_names 'replication_logical')",
+    );
+    let stream = LogicalReplicationStream::new(copy);
+
+    let (message, stream) = next_change(&mut runtime, stream);
+    match message {
+        LogicalReplicationMessage::Begin(_) => {}
+        _ => panic!("expected begin"),
+    }
+
+    let (message, stream) = next_change(&mut runtime, stream);
+    let rel_id = match message {
+        LogicalReplicationMessage::Relation(body) => {
+            assert_eq!(body.namespace().unwrap(), "public");
+            assert_eq!(body.name().unwrap(), "replication_logical");
+            assert_eq!(body.replica_identity(), ReplicaIdentity::Default);
+            let columns = body
+                .columns()
+                .iter()
+                .map(|c| (c.name().unwrap().to_string(), c.flags()))
+                .collect::<Vec<_>>();
+            assert_eq!(columns, [("id".to_string(), 1), ("name".to_string(), 0)]);
+            body.rel_id()
+        }
+        _ => panic!("expected relation"),
+    };
+
+    let (message, stream) = next_change(&mut runtime, stream);
+    match message {
+        LogicalReplicationMessage::Insert(body) => {
+            assert_eq!(body.rel_id(), rel_id);
+            let data = body.tuple().tuple_data();
+            assert_eq!(text(&data[0]), Some("1"));
+            assert_eq!(text(&data[1]), Some("alice"));
+        }
+        _ => panic!("expected insert"),
+    }
+
+    let (message, stream) = next_change(&mut runtime, stream);
+    match message {
+        LogicalReplicationMessage::Update(body) => {
+            assert_eq!(body.rel_id(), rel_id);
+            assert!(body.old_tuple().is_none());
+            assert!(body.key_tuple().is_none());
+            let data = body.new_tuple().tuple_data();
+            assert_eq!(text(&data[0]), Some("1"));
+            assert_eq!(text(&data[1]), None);
+        }
+        _ => panic!("expected update"),
+    }
+
+    let (message, stream) = next_change(&mut runtime, stream);
+    match message {
+        LogicalReplicationMessage::Delete(body) => {
+            assert_eq!(body.rel_id(), rel_id);
+            let data = body.key_tuple().unwrap().tuple_data();
+            assert_eq!(text(&data[0]), Some("1"));
+        }
+        _ => panic!("expected delete"),
+    }
+
+    // the relation's schema is resent since the truncation invalidated it
+    let (message, stream) = next_change(&mut runtime, stream);
+    let (message, stream) = match message {
+        LogicalReplicationMessage::Relation(_) => next_change(&mut runtime, stream),
+        message => (message, stream),
+    };
+    match message {
+        LogicalReplicationMessage::Truncate(body) => assert_eq!(body.rel_ids(), [rel_id]),
+        _ => panic!("expected truncate"),
+    }
+
+    let (message, mut stream) = next_change(&mut runtime, stream);
+    let commit_lsn = match message {
+        LogicalReplicationMessage::Commit(body) => body.end_lsn(),
+        _ => panic!("expected commit"),
+    };
+
+    stream.standby_status_update(commit_lsn, commit_lsn, commit_lsn, SystemTime::now(), true);
+    loop {
+        let (message, s) = next(&mut runtime, stream);
+        stream = s;
+        if let ReplicationMessage::PrimaryKeepAlive(_) = message {
+            break;
+        }
+    }
+
+    let mut copy = stream.into_inner();
+    runtime.block_on(future::poll_fn(|| copy.close())).unwrap();
+    runtime.block_on(copy.for_each(|_| Ok(()))).unwrap();
+
+    let rows = simple_query(&mut runtime, &mut repl_client, "SELECT 1");
+    assert_eq!(rows[0][0], "1");
+
+    simple_query(
+        &mut runtime,
+        &mut client,
+        "DROP PUBLICATION replication_logical;
+         DROP TABLE replication_logical;",
+    );
+}
+
+#[test]
+fn identify_system() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = ReplicationClient::new(setup(&mut runtime, "user=postgres replication=true"));
+
+    let system = runtime.block_on(client.identify_system()).unwrap();
+    assert!(system.systemid().parse::<u64>().is_ok());
+    assert_eq!(system.timeline(), 1);
+    assert!(system.xlogpos() > 0);
+    assert_eq!(system.dbname(), None);
+
+    let mut client = ReplicationClient::new(setup(
+        &mut runtime,
+        "user=postgres dbname=postgres replication=database",
+    ));
+    let system = runtime.block_on(client.identify_system()).unwrap();
+    assert_eq!(system.dbname(), Some("postgres"));
+
+    // the initial timeline has no history file
+    runtime.block_on(client.timeline_history(1)).unwrap_err();
+}
+
+#[test]
+fn slots() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client =
+        ReplicationClient::new(setup(&mut runtime, "user=postgres replication=database"));
+
+    let slot = runtime
+        .block_on(client.create_physical_replication_slot("replication_slots_physical", true, true))
+        .unwrap();
+    assert_eq!(slot.name(), "replication_slots_physical");
+    assert!(slot.consistent_point().is_some());
+    assert_eq!(slot.output_plugin(), None);
+
+    let slot = runtime
+        .block_on(client.create_logical_replication_slot(
+            "replication_slots_logical",
+            true,
+            "pgoutput",
+        ))
+        .unwrap();
+    assert_eq!(slot.name(), "replication_slots_logical");
+    assert!(slot.consistent_point().is_some());
+    assert!(slot.snapshot_name().is_some());
+    assert_eq!(slot.output_plugin(), Some("pgoutput"));
+
+    runtime
+        .block_on(client.drop_replication_slot("replication_slots_physical", false))
+        .unwrap();
+    runtime
+        .block_on(client.drop_replication_slot("replication_slots_physical", false))
+        .unwrap_err();
+}
+
+#[test]
+fn physical() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = ReplicationClient::new(setup(&mut runtime, "user=postgres replication=true"));
+
+    let system = runtime.block_on(client.identify_system()).unwrap();
+    // start at the beginning of the current WAL segment so there is data to stream
+    let start = system.xlogpos() & !0xFF_FFFF;
+
+    let mut stream = client.start_physical_replication(None, start, Some(system.timeline()));
+    let body = loop {
+        let (message, s) = runtime
+            .block_on(stream.into_future().map_err(|(e, _)| e))
+            .unwrap();
+        stream = s;
+        if let ReplicationMessage::XLogData(body) = message.unwrap() {
+            break body;
+        }
+    };
+    assert_eq!(body.wal_start(), start);
+    assert!(!body.data().is_empty());
+
+    let lsn = body.wal_start() + body.data().len() as u64;
+    stream.standby_status_update(lsn, lsn, lsn, SystemTime::now(), false);
+    let mut copy = stream.into_inner();
+    runtime.block_on(future::poll_fn(|| copy.close())).unwrap();
+    runtime.block_on(copy.for_each(|_| Ok(()))).unwrap();
+
+    let system = runtime.block_on(client.identify_system()).unwrap();
+    assert_eq!(system.timeline(), 1);
+}
+
+#[test]
+fn base_backup() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = ReplicationClient::new(setup(&mut runtime, "user=postgres replication=true"));
+
+    let messages = runtime
+        .block_on(
+            client
+                .base_backup(&[
+                    ("label", "rust-postgres test's backup"),
+                    ("checkpoint", "fast"),
+                    ("progress", "true"),
+                    ("manifest", "yes"),
+                ])
+                .collect(),
+        )
+        .unwrap();
+
+    let mut it = messages.iter();
+    let start = match it.next() {
+        Some(BaseBackupMessage::Start(position)) => *position,
+        _ => panic!("expected start"),
+    };
+    match it.next() {
+        Some(BaseBackupMessage::Tablespace(tablespace)) => {
+            assert_eq!(tablespace.oid(), None);
+            assert_eq!(tablespace.location(), None);
+            assert!(tablespace.size().is_some());
+        }
+        _ => panic!("expected tablespace"),
+    }
+
+    let mut archives = vec![];
+    let mut manifest = vec![];
+    let mut in_manifest = false;
+    let mut end = None;
+    for message in it {
+        match message {
+            BaseBackupMessage::Archive(archive) => {
+                archives.push((archive.name().to_string(), 0));
+            }
+            BaseBackupMessage::Data(data) if in_manifest => manifest.extend_from_slice(data),
+            BaseBackupMessage::Data(data) => archives.last_mut().unwrap().1 += data.len(),
+            BaseBackupMessage::Manifest => in_manifest = true,
+            BaseBackupMessage::Progress(_) => {}
+            BaseBackupMessage::End(position) => end = Some(*position),
+            _ => panic!("unexpected message"),
+        }
+    }
+
+    assert_eq!(archives.len(), 1);
+    assert_eq!(archives[0].0, "base.tar");
+    assert!(archives[0].1 > 0);
+    assert!(std::str::from_utf8(&manifest)
+        .unwrap()
+        .contains("PostgreSQL-Backup-Manifest-Version"));
+
+    let end = end.unwrap();
+    assert_eq!(start.timeline(), end.timeline());
+    assert!(end.lsn() >= start.lsn());
+}
+
+#[test]
+fn copy_both_server_done() {
+    let mut runtime = Runtime::new().unwrap();
+    let (addr, server) = copy_done_server(
+        vec![b"hello".to_vec(), b"world".to_vec()],
+        message(b'C', b"COPY_BOTH\0"),
+    );
+    let mut client = setup_raw(&mut runtime, addr);
+
+    let data = runtime
+        .block_on(client.copy_both_simple("START_REPLICATION").collect())
+        .unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(&data[0][..], b"hello");
+    assert_eq!(&data[1][..], b"world");
+
+    drop(client);
+    runtime.run().unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn physical_end_of_timeline() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let mut xlog_data = vec![b'w'];
+    xlog_data.extend_from_slice(&0x0100_0000u64.to_be_bytes());
+    xlog_data.extend_from_slice(&0x0100_0004u64.to_be_bytes());
+    xlog_data.extend_from_slice(&0i64.to_be_bytes());
+    xlog_data.extend_from_slice(b"wal!");
+
+    let mut description = 2i16.to_be_bytes().to_vec();
+    for (name, oid) in &[("next_tli", 20i32), ("next_tli_startpos", 25)] {
+        description.extend_from_slice(name.as_bytes());
+        description.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0]);
+        description.extend_from_slice(&oid.to_be_bytes());
+        description.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+    }
+    let mut row = 2i16.to_be_bytes().to_vec();
+    for value in &["2", "0/1000004"] {
+        row.extend_from_slice(&(value.len() as i32).to_be_bytes());
+        row.extend_from_slice(value.as_bytes());
+    }
+    let mut results = message(b'T', &description);
+    results.extend(message(b'D', &row));
+    results.extend(message(b'C', b"START_STREAMING\0"));
+
+    let (addr, server) = copy_done_server(vec![xlog_data], results);
+    let mut client = ReplicationClient::new(setup_raw(&mut runtime, addr));
+
+    let mut stream = client.start_physical_replication(None, 0x0100_0000, Some(1));
+    let (message, s) = runtime
+        .block_on(stream.into_future().map_err(|(e, _)| e))
+        .unwrap();
+    stream = s;
+    match message {
+        Some(ReplicationMessage::XLogData(body)) => assert_eq!(&body.data()[..], b"wal!"),
+        _ => panic!("expected XLogData"),
+    }
+    assert!(stream.next_timeline().is_none());
+
+    let (message, stream) = runtime
+        .block_on(stream.into_future().map_err(|(e, _)| e))
+        .unwrap();
+    assert!(message.is_none());
+    let next = stream.next_timeline().unwrap();
+    assert_eq!(next.timeline(), 2);
+    assert_eq!(next.lsn(), 0x0100_0004);
+
+    drop(stream);
+    drop(client);
+    runtime.run().unwrap();
+    server.join().unwrap();
+}
@@ -1,9 +1,14 @@
+use futures::future::{self, FutureResult};
 use futures::{Future, Stream};
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::thread;
 use std::time::{Duration, Instant};
 use tokio::runtime::current_thread::Runtime;
 use tokio::timer::Delay;
 use tokio_postgres::error::SqlState;
-use tokio_postgres::NoTls;
+use tokio_postgres::tls::{ChannelBinding, MakeTlsConnect, TlsConnect};
+use tokio_postgres::{NoTls, Socket};
 
 fn smoke_test(s: &str) {
     let mut runtime = Runtime::new().unwrap();
@@ -100,3 +105,80 @@ fn cancel_query() {
 
     let ((), ()) = runtime.block_on(sleep.join(cancel)).unwrap();
 }
+
+// A TLS implementation whose handshake always fails.
+struct FailingTls;
+
+impl MakeTlsConnect<Socket> for FailingTls {
+    type Stream = Socket;
+    type TlsConnect = FailingTls;
+    type Error = io::Error;
+
+    fn make_tls_connect(&mut self, _: &str) -> Result<FailingTls, io::Error> {
+        Ok(FailingTls)
+    }
+}
+
+impl TlsConnect<Socket> for FailingTls {
+    type Stream = Socket;
+    type Error = io::Error;
+    type Future = FutureResult<(Socket, ChannelBinding), io::Error>;
+
+    fn connect(self, _: Socket) -> FutureResult<(Socket, ChannelBinding), io::Error> {
+        future::err(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            "handshake failed",
+        ))
+    }
+}
+
+#[test]
+fn prefer_handshake_failure() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let f = tokio_postgres::connect(
+        "host=localhost port=5433 user=postgres sslmode=prefer",
+        FailingTls,
+    );
+    let (mut client, connection) = runtime.block_on(f).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let execute = client.simple_query("SELECT 1").for_each(|_| Ok(()));
+    runtime.block_on(execute).unwrap();
+
+    let f = tokio_postgres::connect(
+        "host=localhost port=5433 user=postgres sslmode=require",
+        FailingTls,
+    );
+    runtime.block_on(f).err().unwrap();
+}
+
+#[test]
+fn prefer_handshake_failure_multiple_hosts() {
+    // the first host accepts TLS and then closes both that and the plaintext retry's connection
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0; 8];
+        stream.read_exact(&mut buf).unwrap();
+        stream.write_all(b"S").unwrap();
+        drop(stream);
+
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.read_exact(&mut buf).unwrap();
+    });
+
+    let mut runtime = Runtime::new().unwrap();
+    let f = tokio_postgres::connect(
+        &format!(
+            "host=127.0.0.1,localhost port={},5433 user=postgres sslmode=prefer",
+            port
+        ),
+        FailingTls,
+    );
+    let (_client, _connection) = runtime.block_on(f).unwrap();
+
+    server.join().unwrap();
+}
@@ -0,0 +1,237 @@
+use futures::{Future, Stream};
+use tokio::net::TcpStream;
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::Type;
+use tokio_postgres::{Client, Config, NoTls, SimpleQueryMessage};
+
+fn connect(runtime: &mut Runtime, capacity: usize) -> Client {
+    let mut config = "user=postgres".parse::<Config>().unwrap();
+    config.statement_cache_capacity(capacity);
+    let handshake = TcpStream::connect(&"127.0.0.1:5433".parse().unwrap())
+        .map_err(|e| panic!("{}", e))
+        .and_then(move |s| config.connect_raw(s, NoTls));
+    let (client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+    client
+}
+
+fn prepared_statements(runtime: &mut Runtime, client: &mut Client) -> i64 {
+    let rows = runtime
+        .block_on(
+            client
+                .simple_query("SELECT count(*) FROM pg_prepared_statements")
+                .filter_map(|m| match m {
+                    SimpleQueryMessage::Row(row) => Some(row.get(0).unwrap().parse().unwrap()),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .unwrap();
+    rows[0]
+}
+
+#[test]
+fn reuse() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 10);
+
+    let a = runtime.block_on(client.prepare("SELECT $1::INT4")).unwrap();
+    let b = runtime.block_on(client.prepare("SELECT $1::INT4")).unwrap();
+    let c = runtime
+        .block_on(client.prepare_typed("SELECT $1", &[Type::INT4]))
+        .unwrap();
+    drop((a, b, c));
+
+    assert_eq!(prepared_statements(&mut runtime, &mut client), 2);
+
+    let statement = runtime.block_on(client.prepare("SELECT $1::INT4")).unwrap();
+    let rows = runtime
+        .block_on(client.query(&statement, &[&1i32]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+    assert_eq!(prepared_statements(&mut runtime, &mut client), 2);
+}
+
+#[test]
+fn disabled() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 0);
+
+    let a = runtime.block_on(client.prepare("SELECT 1")).unwrap();
+    let b = runtime.block_on(client.prepare("SELECT 1")).unwrap();
+    assert_eq!(prepared_statements(&mut runtime, &mut client), 2);
+
+    drop((a, b));
+    assert_eq!(prepared_statements(&mut runtime, &mut client), 0);
+}
+
+#[test]
+fn eviction() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 2);
+
+    for query in &["SELECT 1", "SELECT 2", "SELECT 1", "SELECT 3"] {
+        runtime.block_on(client.prepare(query)).unwrap();
+    }
+
+    let rows = runtime
+        .block_on(
+            client
+                .simple_query("SELECT statement FROM pg_prepared_statements ORDER BY statement")
+                .filter_map(|m| match m {
+                    SimpleQueryMessage::Row(row) => Some(row.get(0).unwrap().to_string()),
+                    _ => None,
+                })
+                .collect(),
+        )
+        .unwrap();
+    assert_eq!(rows, ["SELECT 1", "SELECT 3"]);
+}
+
+#[test]
+fn result_type_change() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 10);
+
+    runtime
+        .block_on(
+            client
+                .simple_query(
+                    "CREATE TEMPORARY TABLE foo (id INT4);
+                     INSERT INTO foo (id) VALUES (1);",
+                )
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let select = runtime
+        .block_on(client.prepare("SELECT * FROM foo"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+    assert_eq!(rows[0].len(), 1);
+
+    runtime
+        .block_on(
+            client
+                .simple_query("ALTER TABLE foo ADD COLUMN name TEXT DEFAULT 'alice'")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let rows = runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+    assert_eq!(rows[0].len(), 2);
+    assert_eq!(rows[0].get::<_, &str>("name"), "alice");
+
+    let select = runtime
+        .block_on(client.prepare("SELECT * FROM foo"))
+        .unwrap();
+    assert_eq!(select.columns().len(), 2);
+
+    runtime
+        .block_on(
+            client
+                .simple_query("ALTER TABLE foo DROP COLUMN name")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let n = runtime.block_on(client.execute(&select, &[])).unwrap();
+    assert_eq!(n, 1);
+
+    let select = runtime
+        .block_on(client.prepare("SELECT * FROM foo"))
+        .unwrap();
+    assert_eq!(select.columns().len(), 1);
+}
+
+#[test]
+fn result_type_change_with_params() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 10);
+
+    runtime
+        .block_on(
+            client
+                .simple_query(
+                    "CREATE TEMPORARY TABLE foo (id INT4);
+                     INSERT INTO foo (id) VALUES (1);",
+                )
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let select = runtime
+        .block_on(client.prepare("SELECT *, $1::TEXT AS a, $2::TEXT AS b FROM foo"))
+        .unwrap();
+    runtime
+        .block_on(
+            client
+                .simple_query("ALTER TABLE foo ADD COLUMN name TEXT DEFAULT 'alice'")
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let rows = runtime
+        .block_on(client.query(&select, &[&"bob", &None::<&str>]).collect())
+        .unwrap();
+    assert_eq!(rows[0].len(), 4);
+    assert_eq!(rows[0].get::<_, &str>("name"), "alice");
+    assert_eq!(rows[0].get::<_, &str>("a"), "bob");
+    assert_eq!(rows[0].get::<_, Option<&str>>("b"), None);
+}
+
+#[test]
+fn result_type_change_in_transaction() {
+    let mut runtime = Runtime::new().unwrap();
+    let mut client = connect(&mut runtime, 10);
+
+    runtime
+        .block_on(
+            client
+                .simple_query(
+                    "CREATE TEMPORARY TABLE foo (id INT4);
+                     INSERT INTO foo (id) VALUES (1);",
+                )
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    let select = runtime
+        .block_on(client.prepare("SELECT * FROM foo"))
+        .unwrap();
+    runtime
+        .block_on(client.query(&select, &[]).collect())
+        .unwrap();
+
+    runtime
+        .block_on(
+            client
+                .simple_query(
+                    "BEGIN;
+                     ALTER TABLE foo ADD COLUMN name TEXT DEFAULT 'alice';",
+                )
+                .for_each(|_| Ok(())),
+        )
+        .unwrap();
+
+    // the transaction is aborted by the error, so the statement can't be re-prepared
+    let err = runtime
+        .block_on(client.query(&select, &[]).for_each(|_| Ok(())))
+        .unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::FEATURE_NOT_SUPPORTED));
+
+    runtime
+        .block_on(client.simple_query("ROLLBACK").for_each(|_| Ok(())))
+        .unwrap();
+
+    let select = runtime
+        .block_on(client.prepare("SELECT * FROM foo"))
+        .unwrap();
+    assert_eq!(select.columns().len(), 1);
+}
@@ -0,0 +1,62 @@
+use bigdecimal_04::BigDecimal;
+use futures::{Future, Stream};
+use tokio::runtime::current_thread::Runtime;
+
+use crate::connect;
+use crate::types::test_type;
+
+#[test]
+fn test_big_decimal_params() {
+    test_type(
+        "NUMERIC",
+        &[
+            (Some("0".parse::<BigDecimal>().unwrap()), "0"),
+            (Some("1.50".parse::<BigDecimal>().unwrap()), "1.50"),
+            (
+                Some("-12345.6789".parse::<BigDecimal>().unwrap()),
+                "-12345.6789",
+            ),
+            (Some("1e20".parse::<BigDecimal>().unwrap()), "1e20"),
+            (
+                Some(
+                    "0.00000000000000000000000000000000000001"
+                        .parse::<BigDecimal>()
+                        .unwrap(),
+                ),
+                "0.00000000000000000000000000000000000001",
+            ),
+            (
+                Some(
+                    "123456789012345678901234567890123456789.987654321"
+                        .parse::<BigDecimal>()
+                        .unwrap(),
+                ),
+                "123456789012345678901234567890123456789.987654321",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_big_decimal_special() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime
+        .block_on(client.prepare(
+            "SELECT 'NaN'::NUMERIC, '-Infinity'::NUMERIC, 1.500::NUMERIC, $1::NUMERIC::TEXT",
+        ))
+        .unwrap();
+    let value = "-2.50".parse::<BigDecimal>().unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[&value]).collect())
+        .unwrap();
+    assert!(rows[0].try_get::<_, BigDecimal>(0).is_err());
+    assert!(rows[0].try_get::<_, BigDecimal>(1).is_err());
+    assert_eq!(rows[0].get::<_, BigDecimal>(2).to_string(), "1.500");
+    assert_eq!(rows[0].get::<_, &str>(3), "-2.50");
+}
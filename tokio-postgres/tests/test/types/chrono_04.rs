@@ -1,5 +1,9 @@
-use chrono_04::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
-use tokio_postgres::types::{Date, Timestamp};
+use chrono_04::{
+    DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use std::convert::TryFrom;
+use std::ops::Bound;
+use tokio_postgres::types::{Date, FromSql, Interval, PgRange, Timestamp, Type};
 
 use crate::types::test_type;
 
@@ -146,3 +150,98 @@ fn test_time_params() {
         ],
     );
 }
+
+#[test]
+fn test_interval_duration() {
+    let interval = Interval::try_from(Duration::microseconds(-90_061_000_001)).unwrap();
+    assert_eq!(interval, Interval::new(0, 0, -90_061_000_001));
+    Interval::try_from(Duration::seconds(i64::MAX / 1000)).unwrap_err();
+
+    assert_eq!(
+        Duration::try_from(Interval::new(0, -1, -3_661_000_000)).unwrap(),
+        Duration::seconds(-90_061)
+    );
+    Duration::try_from(Interval::new(-1, 0, 0)).unwrap_err();
+}
+
+#[test]
+fn test_date_range_params() {
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+    test_type(
+        "DATERANGE",
+        &[
+            (
+                Some(PgRange::from(date("2010-02-09")..date("2010-03-01"))),
+                "'[2010-02-09,2010-03-01)'",
+            ),
+            (
+                Some(PgRange::from(date("2010-02-10")..date("2010-03-02"))),
+                "'(2010-02-09,2010-03-01]'",
+            ),
+            (Some(PgRange::empty()), "'empty'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_tstz_range_params() {
+    fn time(s: &str) -> DateTime<Utc> {
+        Utc.datetime_from_str(s, "%Y-%m-%d %H:%M:%S.%f").unwrap()
+    }
+    test_type(
+        "TSTZRANGE",
+        &[
+            (
+                Some(PgRange::new(
+                    Bound::Included(time("2010-02-09 23:11:45.120200000")),
+                    Bound::Unbounded,
+                )),
+                "'[2010-02-09 23:11:45.1202Z,)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_time_tz_params() {
+    test_type(
+        "TIMETZ",
+        &[
+            (
+                Some((
+                    NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                    FixedOffset::east_opt(0).unwrap(),
+                )),
+                "'00:00:00+00'",
+            ),
+            (
+                Some((
+                    NaiveTime::from_hms_micro_opt(4, 5, 6, 789_000).unwrap(),
+                    FixedOffset::west_opt(5 * 3600).unwrap(),
+                )),
+                "'04:05:06.789-05'",
+            ),
+            (
+                Some((
+                    NaiveTime::from_hms_opt(23, 11, 45).unwrap(),
+                    FixedOffset::east_opt(9 * 3600 + 1800).unwrap(),
+                )),
+                "'23:11:45+09:30'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_time_tz_out_of_range() {
+    for &usec in &[86_400_000_000i64, -1, i64::MAX] {
+        let mut buf = usec.to_be_bytes().to_vec();
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        assert!(<(NaiveTime, FixedOffset)>::from_sql(&Type::TIMETZ, &buf).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::types::test_type;
+use cidr_02::{IpCidr, IpInet};
+
+#[test]
+fn test_inet_params() {
+    test_type(
+        "INET",
+        &[
+            (
+                Some(IpInet::new("127.0.0.1".parse().unwrap(), 32).unwrap()),
+                "'127.0.0.1'",
+            ),
+            (
+                Some(IpInet::new("192.168.1.5".parse().unwrap(), 24).unwrap()),
+                "'192.168.1.5/24'",
+            ),
+            (
+                Some(IpInet::new("2001:db8::1".parse().unwrap(), 64).unwrap()),
+                "'2001:db8::1/64'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_cidr_params() {
+    test_type(
+        "CIDR",
+        &[
+            (
+                Some(IpCidr::new("10.0.0.0".parse().unwrap(), 8).unwrap()),
+                "'10.0.0.0/8'",
+            ),
+            (
+                Some(IpCidr::new("2001:db8::".parse().unwrap(), 32).unwrap()),
+                "'2001:db8::/32'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
@@ -0,0 +1,96 @@
+use geo_types_04::{Coordinate, Line, LineString, Point, Polygon, Rect};
+
+use crate::types::test_type;
+
+#[test]
+fn test_point_params() {
+    test_type(
+        "POINT",
+        &[
+            (Some(Point::new(0.0, 0.0)), "POINT(0, 0)"),
+            (Some(Point::new(-3.15, 1.618)), "POINT(-3.15, 1.618)"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_box_params() {
+    test_type(
+        "BOX",
+        &[
+            (
+                Some(Rect {
+                    min: Coordinate { x: -3.15, y: 1.618 },
+                    max: Coordinate {
+                        x: 160.0,
+                        y: 69701.5615,
+                    },
+                }),
+                "BOX(POINT(160.0, 69701.5615), POINT(-3.15, 1.618))",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_path_params() {
+    let points = vec![
+        Coordinate { x: 0., y: 0. },
+        Coordinate { x: -3.15, y: 1.618 },
+        Coordinate {
+            x: 160.0,
+            y: 69701.5615,
+        },
+    ];
+    test_type(
+        "PATH",
+        &[
+            (
+                Some(LineString(points)),
+                "path '((0, 0), (-3.15, 1.618), (160.0, 69701.5615))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(Line::new(
+                    Coordinate { x: 0., y: 0. },
+                    Coordinate { x: -2.5, y: 1.618 },
+                )),
+                "lseg '((0, 0), (-2.5, 1.618))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_polygon_params() {
+    let points = vec![
+        Coordinate { x: 0., y: 0. },
+        Coordinate { x: -2.5, y: 1.618 },
+        Coordinate {
+            x: 160.0,
+            y: 69701.5615,
+        },
+    ];
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon::new(LineString(points), vec![])),
+                "polygon '((0, 0), (-2.5, 1.618), (160.0, 69701.5615))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
@@ -1,28 +1,41 @@
 use futures::{Future, Stream};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::error::Error;
 use std::f32;
 use std::f64;
 use std::fmt;
 use std::net::IpAddr;
+use std::ops::Bound;
 use std::result;
 use std::time::{Duration, UNIX_EPOCH};
 use tokio::runtime::current_thread::Runtime;
 use tokio_postgres::to_sql_checked;
-use tokio_postgres::types::{FromSql, FromSqlOwned, IsNull, Kind, ToSql, Type, WrongType};
+use tokio_postgres::types::{
+    Array, ArrayDimension, FromSql, FromSqlOwned, Interval, IsNull, Kind, PgCircle, PgInet,
+    PgJsonPath, PgLine, PgMoney, PgMultirange, PgRange, PgTimeTz, ToSql, Type, WrongType,
+};
 
 use crate::connect;
 
+#[cfg(feature = "with-bigdecimal-0_4")]
+mod bigdecimal_04;
 #[cfg(feature = "with-bit-vec-0_7")]
 mod bit_vec_07;
 #[cfg(feature = "with-chrono-0_4")]
 mod chrono_04;
+#[cfg(feature = "with-cidr-0_2")]
+mod cidr_02;
 #[cfg(feature = "with-eui48-0_4")]
 mod eui48_04;
-#[cfg(feature = "with-geo-0_10")]
-mod geo_010;
+#[cfg(feature = "with-geo-types-0_4")]
+mod geo_types_04;
+#[cfg(feature = "with-rust_decimal-1")]
+mod rust_decimal_1;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json_1;
+#[cfg(feature = "with-time-0_3")]
+mod time_03;
 #[cfg(feature = "with-uuid-0_7")]
 mod uuid_07;
 
@@ -344,6 +357,163 @@ fn test_array_params() {
     );
 }
 
+#[test]
+fn test_multidimensional_array_params() {
+    test_type(
+        "integer[]",
+        &[
+            (Some(vec![vec![1i32, 2], vec![3, 4]]), "ARRAY[[1,2],[3,4]]"),
+            (Some(vec![vec![1i32, 2, 3]]), "ARRAY[[1,2,3]]"),
+            (Some(vec![]), "ARRAY[]::integer[]"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "integer[]",
+        &[(
+            Some(vec![vec![vec![Some(1i32)], vec![None]]]),
+            "ARRAY[[[1],[NULL]]]",
+        )],
+    );
+}
+
+#[test]
+fn test_multidimensional_array_errors() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT $1::integer[]"))
+        .unwrap();
+    let query = client
+        .query(&stmt, &[&vec![vec![1i32, 2], vec![3]]])
+        .collect();
+    assert!(runtime.block_on(query).is_err());
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT ARRAY[[1,2],[3,4]], ARRAY[1,2]"))
+        .unwrap();
+    let query = client.query(&stmt, &[]).collect();
+    let rows = runtime.block_on(query).unwrap();
+    assert!(rows[0].try_get::<_, Vec<i32>>(0).is_err());
+    assert!(rows[0].try_get::<_, Vec<Vec<i32>>>(1).is_err());
+    assert!(rows[0].try_get::<_, [i32; 3]>(1).is_err());
+    assert_eq!(rows[0].get::<_, [[i32; 2]; 2]>(0), [[1, 2], [3, 4]]);
+}
+
+#[test]
+fn test_fixed_size_array_params() {
+    test_type(
+        "integer[]",
+        &[
+            (Some([1i32, 2, 3]), "ARRAY[1,2,3]"),
+            (Some([-4i32, 5, 6]), "ARRAY[-4,5,6]"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_boxed_slice_params() {
+    test_type(
+        "text[]",
+        &[
+            (
+                Some(vec!["a".to_string(), "b".to_string()].into_boxed_slice()),
+                "ARRAY['a','b']",
+            ),
+            (Some(Box::new([]) as Box<[String]>), "ARRAY[]::text[]"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_set_params() {
+    test_type(
+        "integer[]",
+        &[
+            (Some(HashSet::from([1i32, 2, 3])), "ARRAY[1,2,3]"),
+            (Some(HashSet::new()), "ARRAY[]::integer[]"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "text[]",
+        &[
+            (
+                Some(BTreeSet::from(["a".to_string(), "b".to_string()])),
+                "ARRAY['b','a']",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_array_dimensions() {
+    test_type(
+        "integer[]",
+        &[
+            (
+                Some(Array::from_parts(
+                    vec![1i32, 2, 3, 4, 5, 6],
+                    vec![
+                        ArrayDimension {
+                            len: 2,
+                            lower_bound: 0,
+                        },
+                        ArrayDimension {
+                            len: 3,
+                            lower_bound: 2,
+                        },
+                    ],
+                )),
+                "'[0:1][2:4]={{1,2,3},{4,5,6}}'",
+            ),
+            (Some(Array::from_vec(vec![1i32, 2], 1)), "ARRAY[1,2]"),
+            (Some(Array::from_vec(vec![], 1)), "ARRAY[]::integer[]"),
+            (None, "NULL"),
+        ],
+    );
+
+    let array = Array::from_parts(
+        vec!['a', 'b', 'c', 'd'],
+        vec![
+            ArrayDimension {
+                len: 2,
+                lower_bound: -1,
+            },
+            ArrayDimension {
+                len: 2,
+                lower_bound: 1,
+            },
+        ],
+    );
+    assert_eq!(array.get(&[-1, 1]), Some(&'a'));
+    assert_eq!(array.get(&[0, 2]), Some(&'d'));
+    assert_eq!(array.get(&[1, 1]), None);
+    assert_eq!(array.get(&[0]), None);
+}
+
+#[test]
+fn test_array_truncated() {
+    // a one dimensional text array claiming i32::MAX elements but containing a single NULL
+    let mut buf = vec![];
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&Type::TEXT.oid().to_be_bytes());
+    buf.extend_from_slice(&i32::MAX.to_be_bytes());
+    buf.extend_from_slice(&1i32.to_be_bytes());
+    buf.extend_from_slice(&(-1i32).to_be_bytes());
+
+    assert!(Vec::<Option<String>>::from_sql(&Type::TEXT_ARRAY, &buf).is_err());
+    assert!(Array::<Option<String>>::from_sql(&Type::TEXT_ARRAY, &buf).is_err());
+}
+
 #[allow(clippy::eq_op)]
 fn test_nan_param<T>(sql_type: &str)
 where
@@ -626,6 +796,332 @@ fn system_time() {
     );
 }
 
+#[test]
+fn interval() {
+    test_type(
+        "INTERVAL",
+        &[
+            (Some(Interval::new(0, 0, 0)), "'0'"),
+            (
+                Some(Interval::new(14, 3, 14_706_000_001)),
+                "'1 year 2 months 3 days 04:05:06.000001'",
+            ),
+            (
+                Some(Interval::new(-1, 1, -1)),
+                "'-1 month +1 day -00:00:00.000001'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn interval_duration() {
+    let interval = Interval::try_from(Duration::new(90_061, 1_500)).unwrap();
+    assert_eq!(interval, Interval::new(0, 0, 90_061_000_001));
+
+    assert_eq!(
+        Duration::try_from(Interval::new(0, 1, 3_661_000_000)).unwrap(),
+        Duration::from_secs(90_061)
+    );
+    Duration::try_from(Interval::new(1, 0, 0)).unwrap_err();
+    Duration::try_from(Interval::new(0, -1, 0)).unwrap_err();
+    Duration::try_from(Interval::new(0, i32::MAX, i64::MAX)).unwrap_err();
+    Interval::try_from(Duration::from_secs(u64::MAX)).unwrap_err();
+}
+
+#[test]
+fn timetz() {
+    test_type(
+        "TIMETZ",
+        &[
+            (Some(PgTimeTz::new(0, 0)), "'00:00:00+00'"),
+            (
+                Some(PgTimeTz::new(14_706_789_000, -18_000)),
+                "'04:05:06.789-05'",
+            ),
+            (
+                Some(PgTimeTz::new(86_399_999_999, 34_200)),
+                "'23:59:59.999999+09:30'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn money() {
+    test_type(
+        "MONEY",
+        &[
+            (Some(PgMoney::new(0, 2)), "'0'"),
+            (Some(PgMoney::new(-1234, 2)), "'-12.34'"),
+            (Some(PgMoney::new(100_000_001, 2)), "'1000000.01'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn money_display() {
+    assert_eq!(PgMoney::new(-1234, 2).to_string(), "-12.34");
+    assert_eq!(PgMoney::new(5, 3).to_string(), "0.005");
+    assert_eq!(PgMoney::new(42, 0).to_string(), "42");
+    assert_eq!(
+        PgMoney::new(i64::MIN, 2).to_string(),
+        "-92233720368547758.08"
+    );
+}
+
+#[test]
+fn jsonpath() {
+    test_type(
+        "JSONPATH",
+        &[
+            (Some(PgJsonPath::new("$.\"a\"[*]")), "'$.a[*]'"),
+            (
+                Some(PgJsonPath::new("$.\"a\"[*]?(@ > 1)")),
+                "'$.a[*] ? (@ > 1)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn int4range() {
+    test_type(
+        "INT4RANGE",
+        &[
+            (Some(PgRange::from(1..10)), "'[1,10)'"),
+            (Some(PgRange::from(1..10)), "'(0,9]'"),
+            (
+                Some(PgRange::new(Bound::Unbounded, Bound::Excluded(10))),
+                "'(,10)'",
+            ),
+            (
+                Some(PgRange::new(Bound::Included(-5), Bound::Unbounded)),
+                "'[-5,)'",
+            ),
+            (Some(PgRange::from(..)), "'(,)'"),
+            (Some(PgRange::empty()), "'empty'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn int8range() {
+    test_type(
+        "INT8RANGE",
+        &[
+            (
+                Some(PgRange::from(i64::from(i32::MAX)..i64::MAX)),
+                "'[2147483647,9223372036854775807)'",
+            ),
+            (Some(PgRange::empty()), "'[5,5)'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn tsrange() {
+    test_type(
+        "TSRANGE",
+        &[
+            (
+                Some(PgRange::new(
+                    Bound::Excluded(UNIX_EPOCH),
+                    Bound::Included(UNIX_EPOCH + Duration::from_millis(1_010)),
+                )),
+                "'(1970-01-01 00:00:00,1970-01-01 00:00:01.01]'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn user_defined_range() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = connect("user=postgres");
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let batch = client
+        .simple_query("CREATE TYPE pg_temp.floatrange AS RANGE (subtype = float8)")
+        .for_each(|_| Ok(()));
+    runtime.block_on(batch).unwrap();
+
+    let range = PgRange::new(Bound::Included(1.5), Bound::Excluded(2.5));
+
+    let prepare = client.prepare("SELECT $1::pg_temp.floatrange, $1::pg_temp.floatrange::TEXT");
+    let stmt = runtime.block_on(prepare).unwrap();
+    let query = client.query(&stmt, &[&range]).collect();
+    let rows = runtime.block_on(query).unwrap();
+    assert_eq!(range, rows[0].get::<_, PgRange<f64>>(0));
+    assert_eq!("[1.5,2.5)", rows[0].get::<_, &str>(1));
+    assert!(rows[0].try_get::<_, PgRange<i32>>(0).is_err());
+}
+
+#[test]
+fn int4multirange() {
+    test_type(
+        "INT4MULTIRANGE",
+        &[
+            (
+                Some(PgMultirange::new(vec![
+                    PgRange::from(1..3),
+                    PgRange::new(Bound::Included(5), Bound::Unbounded),
+                ])),
+                "'{[1,3), [5,)}'",
+            ),
+            (
+                Some(PgMultirange::new(vec![PgRange::from(1..10)])),
+                "'{[1,3), empty, [2,10)}'",
+            ),
+            (Some(PgMultirange::new(vec![])), "'{}'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn user_defined_multirange() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = connect("user=postgres");
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let batch = client
+        .simple_query(
+            "CREATE TYPE pg_temp.floatrange AS RANGE (
+                subtype = float8,
+                multirange_type_name = pg_temp.floatmultirange
+            )",
+        )
+        .for_each(|_| Ok(()));
+    runtime.block_on(batch).unwrap();
+
+    let prepare = client.prepare("SELECT $1::pg_temp.floatmultirange");
+    let stmt = runtime.block_on(prepare).unwrap();
+    let ty = &stmt.params()[0];
+    assert_eq!(ty.name(), "floatmultirange");
+    match *ty.kind() {
+        Kind::Multirange(ref range) => {
+            assert_eq!(range.name(), "floatrange");
+            assert_eq!(*range.kind(), Kind::Range(Type::FLOAT8));
+        }
+        _ => panic!("unexpected kind"),
+    }
+
+    let multirange = PgMultirange::new(vec![
+        PgRange::new(Bound::Unbounded, Bound::Included(-1.5)),
+        PgRange::new(Bound::Excluded(1.5), Bound::Excluded(2.5)),
+    ]);
+    let query = client.query(&stmt, &[&multirange]).collect();
+    let rows = runtime.block_on(query).unwrap();
+    assert_eq!(multirange, rows[0].get::<_, PgMultirange<f64>>(0));
+}
+
+#[test]
+fn test_multirange_truncated() {
+    // a multirange claiming i32::MAX ranges but containing none
+    let buf = i32::MAX.to_be_bytes();
+
+    assert!(PgMultirange::<i32>::from_sql(&Type::INT4_MULTIRANGE, &buf).is_err());
+}
+
+#[test]
+fn inet_netmask() {
+    test_type(
+        "INET",
+        &[
+            (
+                Some(PgInet::from("127.0.0.1".parse::<IpAddr>().unwrap())),
+                "'127.0.0.1'",
+            ),
+            (
+                Some(PgInet::new("192.168.1.5".parse().unwrap(), 24)),
+                "'192.168.1.5/24'",
+            ),
+            (
+                Some(PgInet::new("2001:db8::1".parse().unwrap(), 64)),
+                "'2001:db8::1/64'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn cidr() {
+    test_type(
+        "CIDR",
+        &[
+            (
+                Some(PgInet::new("10.0.0.0".parse().unwrap(), 8)),
+                "'10.0.0.0/8'",
+            ),
+            (
+                Some(PgInet::new("2001:db8::".parse().unwrap(), 32)),
+                "'2001:db8::/32'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn cidr_host_bits() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime.block_on(client.prepare("SELECT $1::CIDR")).unwrap();
+    let inet = PgInet::new("192.168.1.5".parse().unwrap(), 24);
+    let query = client.query(&stmt, &[&inet]).collect();
+    match runtime.block_on(query) {
+        Ok(_) => panic!("unexpected success"),
+        Err(ref e) if e.to_string().contains("netmask") => {}
+        Err(e) => panic!("unexpected error {}", e),
+    }
+}
+
+#[test]
+fn line() {
+    test_type(
+        "LINE",
+        &[
+            (Some(PgLine::new(1., -1., 0.)), "'{1, -1, 0}'"),
+            (Some(PgLine::new(0., 2.5, -3.)), "'{0, 2.5, -3}'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn circle() {
+    test_type(
+        "CIRCLE",
+        &[
+            (Some(PgCircle::new(0., 0., 1.)), "'<(0, 0), 1>'"),
+            (
+                Some(PgCircle::new(-2.5, 1.618, 2.5)),
+                "'((-2.5, 1.618), 2.5)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
 #[test]
 fn inet() {
     test_type(
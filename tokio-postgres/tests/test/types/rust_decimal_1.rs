@@ -0,0 +1,95 @@
+use futures::{Future, Stream};
+use rust_decimal_1::Decimal;
+use std::ops::Bound;
+use tokio::runtime::current_thread::Runtime;
+use tokio_postgres::types::PgRange;
+
+use crate::connect;
+use crate::types::test_type;
+
+#[test]
+fn test_decimal_params() {
+    test_type(
+        "NUMERIC",
+        &[
+            (Some("0".parse::<Decimal>().unwrap()), "0"),
+            (Some("1.50".parse::<Decimal>().unwrap()), "1.50"),
+            (
+                Some("-12345.6789".parse::<Decimal>().unwrap()),
+                "-12345.6789",
+            ),
+            (Some("10000".parse::<Decimal>().unwrap()), "10000"),
+            (Some("0.00001".parse::<Decimal>().unwrap()), "0.00001"),
+            (
+                Some("79228162514264337593543950335".parse::<Decimal>().unwrap()),
+                "79228162514264337593543950335",
+            ),
+            (
+                Some("0.0000000000000000000000000001".parse::<Decimal>().unwrap()),
+                "0.0000000000000000000000000001",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_decimal_scale() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT 1.500::NUMERIC, $1::NUMERIC::TEXT"))
+        .unwrap();
+    let value = "-2.50".parse::<Decimal>().unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[&value]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get::<_, Decimal>(0).to_string(), "1.500");
+    assert_eq!(rows[0].get::<_, &str>(1), "-2.50");
+}
+
+#[test]
+fn test_decimal_precision_loss() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime
+        .block_on(client.prepare(
+            "SELECT 'NaN'::NUMERIC, 'Infinity'::NUMERIC, \
+             79228162514264337593543950336::NUMERIC, \
+             0.00000000000000000000000000001::NUMERIC, \
+             1.00000000000000000000000000000000::NUMERIC",
+        ))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[]).collect())
+        .unwrap();
+    for i in 0..4 {
+        assert!(rows[0].try_get::<_, Decimal>(i).is_err());
+    }
+    assert_eq!(rows[0].get::<_, Decimal>(4), Decimal::new(1, 0));
+}
+
+#[test]
+fn test_num_range_params() {
+    test_type(
+        "NUMRANGE",
+        &[
+            (
+                Some(PgRange::new(
+                    Bound::Excluded("-1.5".parse::<Decimal>().unwrap()),
+                    Bound::Included("12345.6789".parse::<Decimal>().unwrap()),
+                )),
+                "'(-1.5,12345.6789]'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
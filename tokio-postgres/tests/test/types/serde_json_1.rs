@@ -1,5 +1,10 @@
+use futures::{Future, Stream};
+use serde_json_1::json;
+use serde_json_1::value::RawValue;
 use serde_json_1::Value;
+use tokio::runtime::current_thread::Runtime;
 
+use crate::connect;
 use crate::types::test_type;
 
 #[test]
@@ -37,3 +42,29 @@ fn test_jsonb_params() {
         ],
     )
 }
+
+#[test]
+fn test_raw_value_params() {
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime.block_on(connect("user=postgres")).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.spawn(connection);
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT '{\"a\": [1, 2]}'::JSON, '{\"a\": [1, 2]}'::JSONB"))
+        .unwrap();
+    let query = client.query(&stmt, &[]).collect();
+    let rows = runtime.block_on(query).unwrap();
+    assert_eq!(rows[0].get::<_, &RawValue>(0).get(), "{\"a\": [1, 2]}");
+    assert_eq!(rows[0].get::<_, Box<RawValue>>(1).get(), "{\"a\": [1, 2]}");
+
+    let raw = RawValue::from_string("{\"b\": null}".to_string()).unwrap();
+    let stmt = runtime
+        .block_on(client.prepare("SELECT $1::JSON, $2::JSONB"))
+        .unwrap();
+    let query = client.query(&stmt, &[&raw, &&*raw]).collect();
+    let rows = runtime.block_on(query).unwrap();
+    assert_eq!(rows[0].get::<_, &RawValue>(0).get(), "{\"b\": null}");
+    assert_eq!(rows[0].get::<_, Value>(1), json!({ "b": null }));
+}
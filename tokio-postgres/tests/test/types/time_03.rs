@@ -0,0 +1,138 @@
+use time_03::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use tokio_postgres::types::{Date as PgDate, FromSql, Timestamp, Type};
+
+use crate::types::test_type;
+
+fn date(year: i32, month: Month, day: u8) -> Date {
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+fn time(hour: u8, minute: u8, second: u8, microsecond: u32) -> Time {
+    Time::from_hms_micro(hour, minute, second, microsecond).unwrap()
+}
+
+fn primitive_date_time_checks() -> Vec<(PrimitiveDateTime, &'static str)> {
+    vec![
+        (
+            PrimitiveDateTime::new(date(1970, Month::January, 1), time(0, 0, 0, 10_000)),
+            "'1970-01-01 00:00:00.010000'",
+        ),
+        (
+            PrimitiveDateTime::new(date(1965, Month::September, 25), time(11, 19, 33, 100_314)),
+            "'1965-09-25 11:19:33.100314'",
+        ),
+        (
+            PrimitiveDateTime::new(date(2010, Month::February, 9), time(23, 11, 45, 120_200)),
+            "'2010-02-09 23:11:45.120200'",
+        ),
+    ]
+}
+
+#[test]
+fn test_primitive_date_time_params() {
+    let mut checks = primitive_date_time_checks()
+        .into_iter()
+        .map(|(v, s)| (Some(v), s))
+        .collect::<Vec<_>>();
+    checks.push((None, "NULL"));
+    test_type("TIMESTAMP", &checks);
+}
+
+#[test]
+fn test_with_special_primitive_date_time_params() {
+    let mut checks = primitive_date_time_checks()
+        .into_iter()
+        .map(|(v, s)| (Timestamp::Value(v), s))
+        .collect::<Vec<_>>();
+    checks.push((Timestamp::PosInfinity, "'infinity'"));
+    checks.push((Timestamp::NegInfinity, "'-infinity'"));
+    test_type("TIMESTAMP", &checks);
+}
+
+#[test]
+fn test_offset_date_time_params() {
+    let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            (
+                Some(
+                    PrimitiveDateTime::new(date(1970, Month::January, 1), time(0, 0, 0, 10_000))
+                        .assume_utc(),
+                ),
+                "'1970-01-01 00:00:00.010000+00'",
+            ),
+            (
+                Some(
+                    PrimitiveDateTime::new(date(2010, Month::February, 9), time(18, 11, 45, 0))
+                        .assume_offset(offset),
+                ),
+                "'2010-02-09 23:11:45+00'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_with_special_offset_date_time_params() {
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            (
+                Timestamp::Value(
+                    PrimitiveDateTime::new(date(1965, Month::September, 25), time(11, 19, 33, 0))
+                        .assume_utc(),
+                ),
+                "'1965-09-25 11:19:33+00'",
+            ),
+            (Timestamp::<OffsetDateTime>::PosInfinity, "'infinity'"),
+            (Timestamp::<OffsetDateTime>::NegInfinity, "'-infinity'"),
+        ],
+    );
+}
+
+#[test]
+fn test_date_params() {
+    test_type(
+        "DATE",
+        &[
+            (Some(date(1970, Month::January, 1)), "'1970-01-01'"),
+            (Some(date(1965, Month::September, 25)), "'1965-09-25'"),
+            (Some(date(2010, Month::February, 9)), "'2010-02-09'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_with_special_date_params() {
+    test_type(
+        "DATE",
+        &[
+            (PgDate::Value(date(1970, Month::January, 1)), "'1970-01-01'"),
+            (PgDate::PosInfinity, "'infinity'"),
+            (PgDate::NegInfinity, "'-infinity'"),
+        ],
+    );
+}
+
+#[test]
+fn test_time_params() {
+    test_type(
+        "TIME",
+        &[
+            (Some(time(0, 0, 0, 10_000)), "'00:00:00.010000'"),
+            (Some(time(11, 19, 33, 100_314)), "'11:19:33.100314'"),
+            (Some(time(23, 11, 45, 120_200)), "'23:11:45.120200'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_time_out_of_range() {
+    for &usec in &[86_400_000_000i64, -1, i64::MAX] {
+        assert!(Time::from_sql(&Type::TIME, &usec.to_be_bytes()).is_err());
+    }
+}